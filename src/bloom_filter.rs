@@ -0,0 +1,238 @@
+//! # Parquet Bloom Filters
+//!
+//! This module adds split-block Bloom filters to serialized Parquet files, so
+//! readers can skip row groups that cannot contain a looked-up value (e.g. a
+//! station identifier or an exact coordinate).
+//!
+//! The Polars writer never emits Bloom filters, so they are added after
+//! serialization: the footer is decoded, one filter per requested column and
+//! row group is appended after the column data, and the footer is written back
+//! with the filter offsets. Filters are sized for a 1% false-positive rate from
+//! the number of distinct values in each row group, and values are hashed in
+//! their Parquet physical representation (xxHash64), as the format specifies.
+
+use polars::prelude::*;
+use polars_parquet::parquet::bloom_filter::{hash_byte, hash_native, insert};
+use polars_parquet_format::thrift::protocol::{TCompactInputProtocol, TCompactOutputProtocol};
+use polars_parquet_format::{
+    BloomFilterAlgorithm, BloomFilterCompression, BloomFilterHash, BloomFilterHeader, FileMetaData,
+    SplitBlockAlgorithm, Uncompressed, XxHash,
+};
+
+/// Magic bytes at the start and end of a Parquet file
+const PARQUET_MAGIC: &[u8; 4] = b"PAR1";
+
+/// False-positive probability the filters are sized for
+const FALSE_POSITIVE_PROBABILITY: f64 = 0.01;
+
+/// Smallest filter: a single 32-byte block
+const MIN_FILTER_BYTES: usize = 32;
+
+/// Largest filter, as recommended by the Parquet specification
+const MAX_FILTER_BYTES: usize = 128 * 1024 * 1024;
+
+/// Whether values of this type can be added to a Bloom filter
+pub fn supports_bloom_filter(dtype: &DataType) -> bool {
+    matches!(
+        dtype,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+            | DataType::String
+            | DataType::Date
+            | DataType::Datetime(_, _)
+            | DataType::Duration(_)
+    )
+}
+
+/// Checks that every Bloom filter column is in `schema` with a supported type
+pub fn validate_bloom_filter_columns(
+    schema: &Schema,
+    columns: &[String],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for column in columns {
+        let dtype = schema.get(column).ok_or_else(|| {
+            format!(
+                "Bloom filter column '{}' is not in the output (columns: {})",
+                column,
+                schema
+                    .iter_names()
+                    .map(|name| name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+        if !supports_bloom_filter(dtype) {
+            return Err(format!(
+                "Bloom filters are not supported for column '{}' of type {}",
+                column, dtype
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Adds a Bloom filter on each of `columns` to the Parquet file `parquet`.
+///
+/// `df` must be the DataFrame the file was written from, so that the rows of
+/// each row group can be found by position. Returns the file unchanged when
+/// `columns` is empty.
+///
+/// # Errors
+///
+/// Returns an error if a column is missing or has an unsupported type (see
+/// [`validate_bloom_filter_columns`]), or if `parquet` is not a valid Parquet file.
+pub fn add_bloom_filters(
+    parquet: Vec<u8>,
+    df: &DataFrame,
+    columns: &[String],
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    if columns.is_empty() {
+        return Ok(parquet);
+    }
+    validate_bloom_filter_columns(df.schema(), columns)?;
+
+    let len = parquet.len();
+    if len < 12 || &parquet[len - 4..] != PARQUET_MAGIC {
+        return Err("Cannot add Bloom filters: not a Parquet file".into());
+    }
+    let footer_len = u32::from_le_bytes(parquet[len - 8..len - 4].try_into()?) as usize;
+    let data_end = (len - 8)
+        .checked_sub(footer_len)
+        .ok_or("Cannot add Bloom filters: invalid Parquet footer length")?;
+    let mut metadata = FileMetaData::read_from_in_protocol(&mut TCompactInputProtocol::new(
+        &parquet[data_end..len - 8],
+        footer_len,
+    ))?;
+
+    let mut output = parquet;
+    output.truncate(data_end);
+
+    let mut row_offset = 0;
+    for row_group in &mut metadata.row_groups {
+        let num_rows = usize::try_from(row_group.num_rows)?;
+        let rows = df.slice(row_offset, num_rows);
+        row_offset += row_group.num_rows;
+
+        for chunk in &mut row_group.columns {
+            let Some(column_metadata) = chunk.meta_data.as_mut() else {
+                continue;
+            };
+            let [name] = column_metadata.path_in_schema.as_slice() else {
+                continue;
+            };
+            if !columns.contains(name) {
+                continue;
+            }
+
+            let series = rows.column(name)?.as_materialized_series();
+            let mut bitset = vec![0u8; filter_size(series.n_unique()?)];
+            for hash in value_hashes(series)? {
+                insert(&mut bitset, hash);
+            }
+
+            let offset = output.len();
+            let header = BloomFilterHeader {
+                num_bytes: i32::try_from(bitset.len())?,
+                algorithm: BloomFilterAlgorithm::BLOCK(SplitBlockAlgorithm {}),
+                hash: BloomFilterHash::XXHASH(XxHash {}),
+                compression: BloomFilterCompression::UNCOMPRESSED(Uncompressed {}),
+            };
+            header.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut output))?;
+            output.extend_from_slice(&bitset);
+
+            column_metadata.bloom_filter_offset = Some(i64::try_from(offset)?);
+            column_metadata.bloom_filter_length = Some(i32::try_from(output.len() - offset)?);
+        }
+    }
+
+    let footer_start = output.len();
+    metadata.write_to_out_protocol(&mut TCompactOutputProtocol::new(&mut output))?;
+    let footer_len = u32::try_from(output.len() - footer_start)?;
+    output.extend_from_slice(&footer_len.to_le_bytes());
+    output.extend_from_slice(PARQUET_MAGIC);
+
+    Ok(output)
+}
+
+/// Size in bytes of a filter holding `distinct_values` values
+fn filter_size(distinct_values: usize) -> usize {
+    let bits =
+        -8.0 * distinct_values as f64 / (1.0 - FALSE_POSITIVE_PROBABILITY.powf(1.0 / 8.0)).ln();
+    ((bits / 8.0).ceil() as usize)
+        .next_power_of_two()
+        .clamp(MIN_FILTER_BYTES, MAX_FILTER_BYTES)
+}
+
+/// Hashes the non-null values of `series` as they are stored in Parquet
+fn value_hashes(series: &Series) -> Result<Vec<u64>, Box<dyn std::error::Error>> {
+    let hashes = match series.dtype() {
+        // Stored as INT32
+        DataType::Int8
+        | DataType::Int16
+        | DataType::Int32
+        | DataType::UInt8
+        | DataType::UInt16
+        | DataType::Date => {
+            let values = series.to_physical_repr().cast(&DataType::Int32)?;
+            values
+                .i32()?
+                .into_iter()
+                .flatten()
+                .map(hash_native)
+                .collect()
+        }
+        DataType::UInt32 => series
+            .u32()?
+            .into_iter()
+            .flatten()
+            .map(|value| hash_native(value as i32))
+            .collect(),
+        // Stored as INT64
+        DataType::Int64 | DataType::Datetime(_, _) | DataType::Duration(_) => {
+            let values = series.to_physical_repr();
+            values
+                .i64()?
+                .into_iter()
+                .flatten()
+                .map(hash_native)
+                .collect()
+        }
+        DataType::UInt64 => series
+            .u64()?
+            .into_iter()
+            .flatten()
+            .map(|value| hash_native(value as i64))
+            .collect(),
+        DataType::Float32 => series
+            .f32()?
+            .into_iter()
+            .flatten()
+            .map(hash_native)
+            .collect(),
+        DataType::Float64 => series
+            .f64()?
+            .into_iter()
+            .flatten()
+            .map(hash_native)
+            .collect(),
+        DataType::String => series.str()?.into_iter().flatten().map(hash_byte).collect(),
+        other => {
+            return Err(format!(
+                "Bloom filters are not supported for column '{}' of type {}",
+                series.name(),
+                other
+            )
+            .into());
+        }
+    };
+    Ok(hashes)
+}
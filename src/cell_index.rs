@@ -0,0 +1,187 @@
+//! # Cell Index
+//!
+//! This module builds the optional companion index written next to a Parquet
+//! output, which maps coordinate index tuples (e.g. `(time_idx, lat_idx, lon_idx)`)
+//! to row numbers so random-access consumers can jump straight to a grid cell.
+//!
+//! ## File Format
+//!
+//! The index is stored at `<output>.cellidx` (see [`cell_index_path`]). All
+//! integers are little-endian:
+//!
+//! - the magic bytes `NCIX` followed by a `u32` format version (currently 1)
+//! - a `u32` dimension count, then each dimension name as a `u32` byte length
+//!   followed by its UTF-8 bytes, in the order of the index tuples
+//! - a `u64` entry count, then each entry as one `u32` index per dimension
+//!   followed by the `u64` row number
+//!
+//! Entries are sorted by index tuple, so lookups can binary search the file.
+
+use std::error::Error;
+
+/// Magic bytes at the start of every cell index file
+const MAGIC: &[u8; 4] = b"NCIX";
+/// Current version of the cell index file format
+const FORMAT_VERSION: u32 = 1;
+
+/// Returns the path of the cell index stored next to an output file
+pub fn cell_index_path(output_path: &str) -> String {
+    format!("{}.cellidx", output_path)
+}
+
+/// Mapping from coordinate index tuples to output row numbers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CellIndex {
+    dimensions: Vec<String>,
+    /// `(indices, row)` entries, sorted by `indices`
+    entries: Vec<(Vec<u32>, u64)>,
+}
+
+impl CellIndex {
+    /// Builds the index of an extraction whose `i`-th row holds `combinations[i]`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a combination does not have one index per dimension or
+    /// an index does not fit in 32 bits.
+    pub fn from_combinations(
+        dimensions: Vec<String>,
+        combinations: &[Vec<usize>],
+    ) -> Result<Self, Box<dyn Error>> {
+        let mut entries = Vec::with_capacity(combinations.len());
+        for (row, combination) in combinations.iter().enumerate() {
+            if combination.len() != dimensions.len() {
+                return Err(format!(
+                    "Coordinate combination {:?} does not match dimensions {:?}",
+                    combination, dimensions
+                )
+                .into());
+            }
+            let indices = combination
+                .iter()
+                .map(|&idx| u32::try_from(idx))
+                .collect::<Result<Vec<u32>, _>>()
+                .map_err(|_| format!("Index in {:?} is too large for a cell index", combination))?;
+            entries.push((indices, row as u64));
+        }
+        entries.sort_unstable();
+
+        Ok(Self {
+            dimensions,
+            entries,
+        })
+    }
+
+    /// Names of the dimensions, in the order of the index tuples
+    pub fn dimensions(&self) -> &[String] {
+        &self.dimensions
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Drops the entries of rows at or beyond `rows`, e.g. after a row limit
+    pub fn truncate(&mut self, rows: usize) {
+        self.entries.retain(|(_, row)| *row < rows as u64);
+    }
+
+    /// Returns the row holding the cell at `indices`, if it was extracted
+    pub fn row_of(&self, indices: &[usize]) -> Option<u64> {
+        let key = indices
+            .iter()
+            .map(|&idx| u32::try_from(idx).ok())
+            .collect::<Option<Vec<u32>>>()?;
+        self.entries
+            .binary_search_by(|(entry, _)| entry.as_slice().cmp(key.as_slice()))
+            .ok()
+            .map(|position| self.entries[position].1)
+    }
+
+    /// Serializes the index in the format described in the module documentation
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let names_len: usize = self.dimensions.iter().map(|name| 4 + name.len()).sum();
+        let entry_len = 4 * self.dimensions.len() + 8;
+        let mut bytes = Vec::with_capacity(8 + 4 + names_len + 8 + entry_len * self.entries.len());
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&(self.dimensions.len() as u32).to_le_bytes());
+        for name in &self.dimensions {
+            bytes.extend_from_slice(&(name.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+        }
+        bytes.extend_from_slice(&(self.entries.len() as u64).to_le_bytes());
+        for (indices, row) in &self.entries {
+            for idx in indices {
+                bytes.extend_from_slice(&idx.to_le_bytes());
+            }
+            bytes.extend_from_slice(&row.to_le_bytes());
+        }
+        bytes
+    }
+
+    /// Parses an index written by [`CellIndex::to_bytes`]
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Box<dyn Error>> {
+        let mut reader = ByteReader { bytes };
+        if reader.take(4)? != MAGIC {
+            return Err("Not a cell index file".into());
+        }
+        let version = reader.u32()?;
+        if version != FORMAT_VERSION {
+            return Err(format!("Unsupported cell index version {}", version).into());
+        }
+
+        let dimension_count = reader.u32()? as usize;
+        let mut dimensions = Vec::with_capacity(dimension_count);
+        for _ in 0..dimension_count {
+            let len = reader.u32()? as usize;
+            dimensions.push(String::from_utf8(reader.take(len)?.to_vec())?);
+        }
+
+        let entry_count = reader.u64()? as usize;
+        let mut entries = Vec::with_capacity(entry_count.min(reader.bytes.len()));
+        for _ in 0..entry_count {
+            let indices = (0..dimension_count)
+                .map(|_| reader.u32())
+                .collect::<Result<Vec<u32>, _>>()?;
+            entries.push((indices, reader.u64()?));
+        }
+        if !reader.bytes.is_empty() {
+            return Err("Trailing bytes after cell index entries".into());
+        }
+
+        Ok(Self {
+            dimensions,
+            entries,
+        })
+    }
+}
+
+/// Cursor over the bytes of a cell index file
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Box<dyn Error>> {
+        if self.bytes.len() < len {
+            return Err("Truncated cell index file".into());
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+        Ok(head)
+    }
+
+    fn u32(&mut self) -> Result<u32, Box<dyn Error>> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, Box<dyn Error>> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into()?))
+    }
+}
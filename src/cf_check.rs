@@ -0,0 +1,265 @@
+//! # CF Compliance Check Module
+//!
+//! This module runs the subset of the [CF conventions](https://cfconventions.org/)
+//! that nc2parquet relies on when it turns dimensions into columns. It backs
+//! `nc2parquet convert --strict-cf`, which refuses to convert a file that
+//! violates any of these rules:
+//!
+//! - every dimension of a checked variable has a coordinate variable (a 1-D
+//!   variable with the dimension's name),
+//! - coordinate values are strictly monotonic,
+//! - coordinate variables carry a `units` attribute,
+//! - a `_FillValue` is a single value of the same type as its variable.
+//!
+//! The file structure is read with [`read_netcdf_info`], the same description
+//! printed by `nc2parquet info`; coordinate values and `_FillValue` types are
+//! read from the open file.
+
+use crate::info::{data_variable_names, read_netcdf_info};
+use crate::storage::{StorageBackend, StorageFactory};
+use anyhow::{Context, Result};
+use log::debug;
+use netcdf::AttributeValue;
+use netcdf::types::{FloatType, IntType, NcVariableType};
+use serde::{Deserialize, Serialize};
+
+/// A single CF rule broken by a variable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CfViolation {
+    /// Variable (or dimension, for a missing coordinate) the rule applies to
+    pub variable: String,
+    /// Description of the violation
+    pub message: String,
+}
+
+impl std::fmt::Display for CfViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.variable, self.message)
+    }
+}
+
+/// Result of checking a NetCDF file against the CF rules
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CfCheckReport {
+    pub path: String,
+    /// Data variables whose dimensions and attributes were checked
+    pub variables: Vec<String>,
+    pub violations: Vec<CfViolation>,
+}
+
+impl CfCheckReport {
+    /// Whether no rule was violated
+    pub fn is_compliant(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Fails with every violation listed when the file is not compliant
+    pub fn ensure_compliant(&self) -> Result<()> {
+        if self.is_compliant() {
+            return Ok(());
+        }
+        let listing: Vec<String> = self
+            .violations
+            .iter()
+            .map(|violation| format!("  - {}", violation))
+            .collect();
+        anyhow::bail!(
+            "{} is not CF-compliant ({} violation(s)):\n{}",
+            self.path,
+            self.violations.len(),
+            listing.join("\n")
+        )
+    }
+}
+
+/// Checks a NetCDF file, local or on S3, against the CF rules.
+///
+/// When `variable` is given only that variable and its coordinates are
+/// checked; otherwise every data variable of the file is.
+pub async fn check_cf_compliance(file_path: &str, variable: Option<&str>) -> Result<CfCheckReport> {
+    // Handle S3 paths - download to temporary file first
+    let (temp_file, local_path) = if file_path.starts_with("s3://") {
+        let storage = StorageFactory::from_path(file_path).await?;
+        let data = storage
+            .read(file_path)
+            .await
+            .context("Failed to read S3 file for CF check")?;
+
+        let temp_file = crate::storage::temp_file().context("Failed to create temporary file")?;
+        let temp_path = temp_file.path().to_path_buf();
+        tokio::fs::write(&temp_path, data)
+            .await
+            .context("Failed to write temporary file")?;
+
+        (Some(temp_file), temp_path.to_string_lossy().to_string())
+    } else {
+        (None, StorageFactory::local_path(file_path)?.into_owned())
+    };
+
+    let file = netcdf::open(&local_path)
+        .with_context(|| format!("Failed to open NetCDF file: {}", file_path))?;
+    let report = check_file(&file, file_path, variable)?;
+    file.close().context("Failed to close NetCDF file")?;
+
+    // Keep temp file alive until after we close the netcdf file
+    drop(temp_file);
+
+    Ok(report)
+}
+
+/// Checks an open NetCDF file; `path` is only used to label the report
+pub fn check_file(
+    file: &netcdf::File,
+    path: &str,
+    variable: Option<&str>,
+) -> Result<CfCheckReport> {
+    let variables = match variable {
+        Some(name) => {
+            if file.variable(name).is_none() {
+                anyhow::bail!("Variable '{}' not found in NetCDF file", name);
+            }
+            vec![name.to_string()]
+        }
+        None => data_variable_names(file),
+    };
+    let info = read_netcdf_info(file, path, None, false, None);
+
+    let mut violations = Vec::new();
+    let mut dimensions: Vec<&str> = Vec::new();
+    for name in &variables {
+        let Some(var_info) = info.variables.iter().find(|var| &var.name == name) else {
+            continue;
+        };
+        for dim in &var_info.dimensions {
+            if !dimensions.contains(&dim.as_str()) {
+                dimensions.push(dim);
+            }
+        }
+    }
+
+    for dim in &dimensions {
+        let coordinate = info
+            .variables
+            .iter()
+            .find(|var| var.name == *dim && var.dimensions.len() == 1 && var.dimensions[0] == *dim);
+        let Some(coordinate) = coordinate else {
+            violations.push(CfViolation {
+                variable: dim.to_string(),
+                message: "dimension has no coordinate variable".to_string(),
+            });
+            continue;
+        };
+
+        if !coordinate.attributes.contains_key("units") {
+            violations.push(CfViolation {
+                variable: coordinate.name.clone(),
+                message: "coordinate variable has no 'units' attribute".to_string(),
+            });
+        }
+        if let Some(var) = file.variable(dim)
+            && let Some(message) = monotonicity_violation(&var)?
+        {
+            violations.push(CfViolation {
+                variable: coordinate.name.clone(),
+                message,
+            });
+        }
+    }
+
+    for name in variables
+        .iter()
+        .map(String::as_str)
+        .chain(dimensions.iter().copied())
+    {
+        if let Some(var) = file.variable(name)
+            && let Some(message) = fill_value_violation(&var)?
+        {
+            violations.push(CfViolation {
+                variable: name.to_string(),
+                message,
+            });
+        }
+    }
+
+    debug!(
+        "CF check of {} found {} violation(s)",
+        path,
+        violations.len()
+    );
+    Ok(CfCheckReport {
+        path: path.to_string(),
+        variables,
+        violations,
+    })
+}
+
+/// Describes why coordinate values are not strictly monotonic, if they are not
+fn monotonicity_violation(var: &netcdf::Variable) -> Result<Option<String>> {
+    if !matches!(
+        var.vartype(),
+        NcVariableType::Int(_) | NcVariableType::Float(_)
+    ) {
+        return Ok(Some("coordinate variable is not numeric".to_string()));
+    }
+    let values = var
+        .get_values::<f64, _>(..)
+        .with_context(|| format!("Failed to read coordinate variable '{}'", var.name()))?;
+
+    if values.iter().any(|value| value.is_nan()) {
+        return Ok(Some("coordinate variable contains NaN values".to_string()));
+    }
+    let increasing = values.windows(2).all(|pair| pair[0] < pair[1]);
+    let decreasing = values.windows(2).all(|pair| pair[0] > pair[1]);
+    if increasing || decreasing {
+        Ok(None)
+    } else {
+        Ok(Some(
+            "coordinate values are not strictly monotonic".to_string(),
+        ))
+    }
+}
+
+/// Describes why a variable's `_FillValue` is invalid, if it is
+fn fill_value_violation(var: &netcdf::Variable) -> Result<Option<String>> {
+    let Some(attribute) = var.attribute("_FillValue") else {
+        return Ok(None);
+    };
+    let value = attribute
+        .value()
+        .with_context(|| format!("Failed to read _FillValue of '{}'", var.name()))?;
+
+    let matches = matches!(
+        (&value, var.vartype()),
+        (AttributeValue::Uchar(_), NcVariableType::Int(IntType::U8))
+            | (AttributeValue::Schar(_), NcVariableType::Int(IntType::I8))
+            | (AttributeValue::Ushort(_), NcVariableType::Int(IntType::U16))
+            | (AttributeValue::Short(_), NcVariableType::Int(IntType::I16))
+            | (AttributeValue::Uint(_), NcVariableType::Int(IntType::U32))
+            | (AttributeValue::Int(_), NcVariableType::Int(IntType::I32))
+            | (
+                AttributeValue::Ulonglong(_),
+                NcVariableType::Int(IntType::U64)
+            )
+            | (
+                AttributeValue::Longlong(_),
+                NcVariableType::Int(IntType::I64)
+            )
+            | (
+                AttributeValue::Float(_),
+                NcVariableType::Float(FloatType::F32)
+            )
+            | (
+                AttributeValue::Double(_),
+                NcVariableType::Float(FloatType::F64)
+            )
+    );
+    if matches {
+        Ok(None)
+    } else {
+        Ok(Some(format!(
+            "_FillValue {:?} does not match the variable type {:?}",
+            value,
+            var.vartype()
+        )))
+    }
+}
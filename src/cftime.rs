@@ -0,0 +1,333 @@
+//! # CF Time Axes
+//!
+//! This module interprets CF-style time coordinates, whose `units` attribute has
+//! the form `<unit> since <reference time>`, e.g.
+//! `hours since 2000-01-01 00:00:00 +05:00`.
+//!
+//! ## Time Zones
+//!
+//! The reference time may carry a UTC offset. When it does not, a `time_zone`
+//! attribute on the coordinate variable (e.g. `"-03:00"`) is used instead, and
+//! references with neither are assumed to be in UTC. Decoded timestamps are
+//! always normalized to UTC; the original zone is recorded in the Parquet
+//! metadata (see [`crate::metadata::TIME_ZONES_METADATA_KEY`]).
+//!
+//! Only fixed offsets are supported: named zones such as `America/Sao_Paulo`
+//! are rejected rather than silently treated as UTC.
+//!
+//! ## Selecting by Date
+//!
+//! [`iso_times_to_values`] converts ISO 8601 times into offsets along a time
+//! axis, so that stride filter `step_dates` and `--time-range` can be given as
+//! dates rather than raw values.
+
+use crate::postprocess::{DateTimeConverter, PostProcessResult, PostProcessor, TimeUnit};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use polars::prelude::*;
+use std::collections::HashMap;
+
+/// Calendars whose dates can be decoded with the proleptic Gregorian calendar
+const SUPPORTED_CALENDARS: [&str; 3] = ["standard", "gregorian", "proleptic_gregorian"];
+
+/// Reference of a CF time axis: offsets are counted in `unit` from `base`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeReference {
+    pub unit: TimeUnit,
+    /// Reference instant, normalized to UTC
+    pub base: DateTime<Utc>,
+    /// Offset the reference was expressed in, when the file specified one
+    pub time_zone: Option<FixedOffset>,
+}
+
+impl TimeReference {
+    /// Whether the file specified a zone other than UTC
+    pub fn is_non_utc(&self) -> bool {
+        self.time_zone
+            .is_some_and(|zone| zone.local_minus_utc() != 0)
+    }
+
+    /// Value of this axis at `datetime`, in `unit` since `base`
+    pub fn value_at(&self, datetime: DateTime<Utc>) -> f64 {
+        let delta = datetime - self.base;
+        let seconds = delta.num_seconds() as f64 + f64::from(delta.subsec_nanos()) * 1e-9;
+        seconds / self.unit.to_seconds_multiplier()
+    }
+
+    /// One millisecond in `unit`, which absorbs floating-point rounding of the
+    /// stored values when matching them against converted times
+    pub fn resolution(&self) -> f64 {
+        1e-3 / self.unit.to_seconds_multiplier()
+    }
+}
+
+/// Parses CF time units such as `"days since 1970-01-01"` or
+/// `"hours since 2000-01-01 00:00:00 +05:00"`.
+pub fn parse_time_units(units: &str) -> Result<TimeReference, Box<dyn std::error::Error>> {
+    let position = units.to_ascii_lowercase().find(" since ").ok_or_else(|| {
+        format!(
+            "'{}' is not a CF time unit (expected '<unit> since <reference>')",
+            units
+        )
+    })?;
+    let unit = parse_time_unit(&units[..position])?;
+    let (base, time_zone) = parse_reference_datetime(&units[position + " since ".len()..])?;
+
+    Ok(TimeReference {
+        unit,
+        base,
+        time_zone,
+    })
+}
+
+fn parse_time_unit(unit: &str) -> Result<TimeUnit, Box<dyn std::error::Error>> {
+    match unit.trim().to_ascii_lowercase().as_str() {
+        "days" | "day" | "d" => Ok(TimeUnit::Days),
+        "hours" | "hour" | "hrs" | "hr" | "h" => Ok(TimeUnit::Hours),
+        "minutes" | "minute" | "mins" | "min" => Ok(TimeUnit::Minutes),
+        "seconds" | "second" | "secs" | "sec" | "s" => Ok(TimeUnit::Seconds),
+        "milliseconds" | "millisecond" | "msecs" | "msec" | "ms" => Ok(TimeUnit::Milliseconds),
+        "microseconds" | "microsecond" | "usecs" | "usec" | "us" => Ok(TimeUnit::Microseconds),
+        "nanoseconds" | "nanosecond" | "ns" => Ok(TimeUnit::Nanoseconds),
+        other => Err(format!("Unsupported time unit '{}'", other).into()),
+    }
+}
+
+/// Parses a reference time, returning it in UTC along with the zone it was written in.
+///
+/// Accepts RFC 3339 (`2000-01-01T00:00:00+05:00`) as well as the looser CF form
+/// `<date> [<time>] [<offset>]`, e.g. `2000-1-1`, `2000-01-01 06:00` or
+/// `2000-01-01 00:00:00 -03:00`. Without an offset the time is taken as UTC and
+/// no zone is returned.
+pub fn parse_reference_datetime(
+    text: &str,
+) -> Result<(DateTime<Utc>, Option<FixedOffset>), Box<dyn std::error::Error>> {
+    let text = text.trim();
+    if let Ok(datetime) = DateTime::parse_from_rfc3339(text) {
+        return Ok((datetime.with_timezone(&Utc), Some(*datetime.offset())));
+    }
+
+    let invalid = || format!("Invalid reference time '{}'", text);
+
+    let mut tokens = text.split_whitespace();
+    let first = tokens.next().ok_or_else(invalid)?;
+    // "2000-01-01T00:00:00" carries the date and the time in a single token
+    let (date, mut time) = match first.split_once('T') {
+        Some((date, time)) => (date, Some(time)),
+        None => (first, None),
+    };
+    let mut zone = None;
+    for token in tokens {
+        if time.is_none() && zone.is_none() && parse_utc_offset(token).is_none() {
+            time = Some(token);
+        } else if zone.is_none() {
+            zone = Some(token);
+        } else {
+            return Err(invalid().into());
+        }
+    }
+
+    let mut time_zone = match zone {
+        Some(zone) => Some(parse_utc_offset(zone).ok_or_else(|| {
+            format!(
+                "Unsupported time zone '{}' in reference time '{}'",
+                zone, text
+            )
+        })?),
+        None => None,
+    };
+    if time_zone.is_none()
+        && let Some(stripped) = time.and_then(|time| time.strip_suffix('Z'))
+    {
+        time = Some(stripped);
+        time_zone = FixedOffset::east_opt(0);
+    }
+
+    let date = NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| invalid())?;
+    let time = match time {
+        Some(time) => NaiveTime::parse_from_str(time, "%H:%M:%S%.f")
+            .or_else(|_| NaiveTime::parse_from_str(time, "%H:%M"))
+            .map_err(|_| invalid())?,
+        None => NaiveTime::MIN,
+    };
+
+    let base = to_utc(NaiveDateTime::new(date, time), time_zone).ok_or_else(invalid)?;
+    Ok((base, time_zone))
+}
+
+/// Parses a fixed UTC offset such as `Z`, `UTC`, `+05:00`, `-0330` or `+5`.
+pub fn parse_utc_offset(text: &str) -> Option<FixedOffset> {
+    let text = text.trim();
+    if matches!(text.to_ascii_uppercase().as_str(), "Z" | "UTC" | "GMT") {
+        return FixedOffset::east_opt(0);
+    }
+
+    let (sign, rest) = match text.as_bytes().first()? {
+        b'+' => (1, &text[1..]),
+        b'-' => (-1, &text[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some(parts) => parts,
+        None if rest.len() == 4 => rest.split_at(2),
+        None => (rest, "0"),
+    };
+    let hours: i32 = hours.parse().ok()?;
+    let minutes: i32 = minutes.parse().ok()?;
+    if hours > 14 || minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+fn to_utc(local: NaiveDateTime, time_zone: Option<FixedOffset>) -> Option<DateTime<Utc>> {
+    let zone = time_zone.or_else(|| FixedOffset::east_opt(0))?;
+    zone.from_local_datetime(&local)
+        .single()
+        .map(|datetime| datetime.with_timezone(&Utc))
+}
+
+/// Reads the CF time reference of the variable `name`, if it is a time axis.
+///
+/// Returns `Ok(None)` when the variable does not exist or its `units` attribute
+/// is not of the form `<unit> since <reference>`. A `time_zone` attribute is
+/// applied to references that do not carry their own offset.
+///
+/// # Errors
+///
+/// Returns an error for malformed reference times, unsupported time zones, and
+/// calendars other than `standard`, `gregorian` and `proleptic_gregorian`.
+pub fn read_time_reference(
+    file: &netcdf::File,
+    name: &str,
+) -> Result<Option<TimeReference>, Box<dyn std::error::Error>> {
+    let Some(var) = file.variable(name) else {
+        return Ok(None);
+    };
+    let Some(units) = read_string_attribute(&var, "units")? else {
+        return Ok(None);
+    };
+    if !units.to_ascii_lowercase().contains(" since ") {
+        return Ok(None);
+    }
+
+    if let Some(calendar) = read_string_attribute(&var, "calendar")?
+        && !SUPPORTED_CALENDARS.contains(&calendar.to_ascii_lowercase().as_str())
+    {
+        return Err(format!(
+            "Calendar '{}' of time variable '{}' is not supported",
+            calendar, name
+        )
+        .into());
+    }
+
+    let mut reference = parse_time_units(&units)?;
+    if reference.time_zone.is_none()
+        && let Some(zone_name) = read_string_attribute(&var, "time_zone")?
+    {
+        let zone = parse_utc_offset(&zone_name).ok_or_else(|| {
+            format!(
+                "Unsupported time_zone '{}' on variable '{}' (only fixed offsets such as '+05:00' are supported)",
+                zone_name, name
+            )
+        })?;
+        reference.base = to_utc(reference.base.naive_utc(), Some(zone))
+            .ok_or_else(|| format!("Invalid reference time '{}'", units))?;
+        reference.time_zone = Some(zone);
+    }
+
+    Ok(Some(reference))
+}
+
+/// Reads the CF time reference of the time coordinate `time_name` like
+/// [`read_time_reference`], failing when it has no `units` attribute of the form
+/// `<unit> since <reference>`
+pub fn require_time_reference(
+    file: &netcdf::File,
+    time_name: &str,
+) -> Result<TimeReference, Box<dyn std::error::Error>> {
+    read_time_reference(file, time_name)?.ok_or_else(|| {
+        format!(
+            "Time variable '{}' needs CF time units ('<unit> since <reference>')",
+            time_name
+        )
+        .into()
+    })
+}
+
+/// Converts ISO 8601 times into values of the time coordinate `time_name`.
+///
+/// The values are offsets in the axis unit since its reference (see
+/// [`require_time_reference`]). Times without a UTC offset are taken as UTC, and
+/// a date alone means midnight. The reference is returned along with the values.
+pub fn iso_times_to_values(
+    file: &netcdf::File,
+    time_name: &str,
+    times: &[String],
+) -> Result<(Vec<f64>, TimeReference), Box<dyn std::error::Error>> {
+    let reference = require_time_reference(file, time_name)?;
+    let values = times
+        .iter()
+        .map(|time| {
+            let (datetime, _) = parse_reference_datetime(time)
+                .map_err(|_| format!("Invalid time '{}' (expected ISO 8601)", time))?;
+            Ok(reference.value_at(datetime))
+        })
+        .collect::<Result<Vec<f64>, String>>()?;
+    Ok((values, reference))
+}
+
+/// Converts the ISO 8601 bounds of a time range into values of the time
+/// coordinate `time_name` (see [`iso_times_to_values`]), widened by the axis
+/// [`TimeReference::resolution`] so that steps exactly at the bounds are kept.
+pub fn time_range_to_values(
+    file: &netcdf::File,
+    time_name: &str,
+    start: &str,
+    end: &str,
+) -> Result<(f64, f64), Box<dyn std::error::Error>> {
+    let (values, reference) =
+        iso_times_to_values(file, time_name, &[start.to_string(), end.to_string()])?;
+    if values[0] > values[1] {
+        return Err(format!("Time range start {} is after its end {}", start, end).into());
+    }
+    let resolution = reference.resolution();
+    Ok((values[0] - resolution, values[1] + resolution))
+}
+
+/// Reads the time references of every dimension in `dimensions` that is a CF time axis
+pub fn read_time_references(
+    file: &netcdf::File,
+    dimensions: &[String],
+) -> Result<HashMap<String, TimeReference>, Box<dyn std::error::Error>> {
+    let mut references = HashMap::new();
+    for dimension in dimensions {
+        if let Some(reference) = read_time_reference(file, dimension)? {
+            references.insert(dimension.clone(), reference);
+        }
+    }
+    Ok(references)
+}
+
+/// Converts the numeric time columns of `df` to UTC datetimes
+pub fn decode_time_columns(
+    mut df: DataFrame,
+    references: &HashMap<String, TimeReference>,
+) -> PostProcessResult<DataFrame> {
+    for (column, reference) in references {
+        if df.schema().contains(column) {
+            df = DateTimeConverter::new(column.clone(), reference.base, reference.unit.clone())
+                .process(df)?;
+        }
+    }
+    Ok(df)
+}
+
+fn read_string_attribute(
+    var: &netcdf::Variable,
+    name: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error>> {
+    match var.attribute_value(name).transpose()? {
+        Some(value) => Ok(String::try_from(value).ok()),
+        None => Ok(None),
+    }
+}
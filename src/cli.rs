@@ -9,7 +9,15 @@
 //! - Progress reporting and logging
 //! - Filter DSL parsing for command line and environment variables
 
+use crate::cftime::parse_reference_datetime;
+use crate::extract::{FilterCombine, NanPolicy};
 use crate::input::{FilterConfig, JobConfig};
+use crate::logging::LogFormat;
+use crate::metrics::MetricsFormat;
+use crate::output::CompressionCodec;
+use crate::peek::CellSpec;
+use crate::postprocess::{AggregationOp, PackedType};
+use crate::storage::DEFAULT_S3_CONCURRENCY;
 use clap::{Parser, Subcommand, ValueEnum};
 use clap_complete::Shell;
 use serde::{Deserialize, Serialize};
@@ -77,6 +85,66 @@ pub struct Cli {
     #[arg(short, long, global = true, env = "NC2PARQUET_CONFIG")]
     pub config: Option<PathBuf>,
 
+    /// Maximum number of S3 requests in flight at once, across all inputs and outputs
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        env = "NC2PARQUET_S3_CONCURRENCY",
+        default_value_t = DEFAULT_S3_CONCURRENCY
+    )]
+    pub s3_concurrency: usize,
+
+    /// Number of threads for Polars and parallel extraction (0 = one per core)
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        env = "NC2PARQUET_THREADS",
+        default_value_t = 0
+    )]
+    pub threads: usize,
+
+    /// Directory for temporary copies of S3 and compressed inputs (default: the
+    /// system temporary directory)
+    #[arg(long, global = true, value_name = "DIR", env = "NC2PARQUET_TMPDIR")]
+    pub temp_dir: Option<PathBuf>,
+
+    /// Keep local copies of S3 inputs in this directory and reuse them while the
+    /// object's ETag is unchanged (default: no cache)
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        env = "NC2PARQUET_S3_CACHE_DIR"
+    )]
+    pub s3_cache_dir: Option<PathBuf>,
+
+    /// AWS region of S3 inputs and outputs (default: resolved from the ambient
+    /// AWS configuration)
+    #[arg(
+        long,
+        global = true,
+        value_name = "REGION",
+        env = "NC2PARQUET_AWS_REGION"
+    )]
+    pub aws_region: Option<String>,
+
+    /// Also append log messages to this file, at the level set by -v/-q
+    #[arg(long, global = true, value_name = "PATH", env = "NC2PARQUET_LOG_FILE")]
+    pub log_file: Option<PathBuf>,
+
+    /// Line format of --log-file: plain text, or one JSON object per line
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        default_value_t = LogFormat::Text,
+        requires = "log_file",
+        env = "NC2PARQUET_LOG_FORMAT"
+    )]
+    pub log_format: LogFormat,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -106,6 +174,21 @@ EXAMPLES:
   # Dry run for validation
   nc2parquet convert input.nc output.parquet -n temp --dry-run
 
+  # Preview the first 100 rows (an arbitrary, unsorted subset)
+  nc2parquet convert input.nc preview.parquet -n temp --limit 100
+
+  # Spot-check a reproducible random sample of 1000 rows
+  nc2parquet convert input.nc sample.parquet -n temp --sample 1000 --sample-seed 42
+
+  # Concatenate daily files into one monthly output
+  nc2parquet convert day01.nc month.parquet -n temp --concat day02.nc day03.nc
+
+  # Write a dataset partitioned by level, one file per level
+  nc2parquet convert input.nc dataset/ -n temp --partition-by level
+
+  # Re-run an incremental job, leaving identical outputs untouched
+  nc2parquet convert input.nc s3://results/output.parquet -n temp --skip-unchanged
+
   # Using config file with overrides
   nc2parquet convert --config base.json \\
     --input-override new_input.nc --output-override new_output.parquet
@@ -119,10 +202,63 @@ EXAMPLES:
         #[arg(value_name = "OUTPUT", env = "NC2PARQUET_OUTPUT")]
         output: Option<String>,
 
-        /// NetCDF variable name to extract
+        /// NetCDF variable name to extract (group path for grouped variables, e.g. /diagnostics/temp)
         #[arg(short = 'n', long, env = "NC2PARQUET_VARIABLE")]
         variable: Option<String>,
 
+        /// Variables written by --split-variables: var1,var2,...
+        #[arg(
+            long,
+            value_name = "VARIABLES",
+            value_delimiter = ',',
+            requires = "split_variables",
+            env = "NC2PARQUET_VARIABLES"
+        )]
+        variables: Vec<String>,
+
+        /// Write one output per variable (those of --variables, or every data variable),
+        /// named after OUTPUT, e.g. out.parquet to out_temperature.parquet
+        #[arg(
+            long,
+            env = "NC2PARQUET_SPLIT_VARIABLES",
+            conflicts_with_all = ["variable", "skip_unchanged", "append"]
+        )]
+        split_variables: bool,
+
+        /// More inputs to concatenate after INPUT, with the same variable and filters,
+        /// into a single OUTPUT (e.g. the daily files of a month). Their columns and
+        /// types must match
+        #[arg(
+            long,
+            value_name = "INPUT",
+            num_args = 1..,
+            value_delimiter = ',',
+            conflicts_with_all = ["split_variables", "skip_unchanged", "append", "chunk_size"]
+        )]
+        concat: Vec<String>,
+
+        /// Write a partitioned dataset instead of one file: OUTPUT becomes a directory
+        /// (or S3 prefix) with one file per distinct value of these columns, at
+        /// OUTPUT/col=value/part-0.parquet
+        #[arg(
+            long,
+            value_name = "COLUMNS",
+            value_delimiter = ',',
+            env = "NC2PARQUET_PARTITION_BY",
+            conflicts_with_all = [
+                "split_variables",
+                "skip_unchanged",
+                "append",
+                "cell_index",
+                "write_manifest",
+                "chunk_size",
+                "report",
+                "metrics_file",
+                "concat",
+            ]
+        )]
+        partition_by: Vec<String>,
+
         /// Override input path from config
         #[arg(long, env = "NC2PARQUET_INPUT_OVERRIDE")]
         input_override: Option<String>,
@@ -147,7 +283,56 @@ EXAMPLES:
         #[arg(long = "point3d", value_parser = parse_point3d_filter)]
         point3d_filters: Vec<Point3DFilterArg>,
 
-        /// Force overwrite existing output files
+        /// Select the time steps between two ISO 8601 times (inclusive), e.g.
+        /// 2020-01-01..2020-01-31T18:00. The time variable must have CF time units
+        #[arg(
+            long,
+            value_name = "START..END",
+            value_parser = parse_time_range,
+            env = "NC2PARQUET_TIME_RANGE"
+        )]
+        time_range: Option<TimeRangeArg>,
+
+        /// Time dimension selected by --time-range
+        #[arg(
+            long,
+            value_name = "DIM",
+            default_value = "time",
+            env = "NC2PARQUET_TIME_DIMENSION"
+        )]
+        time_dimension: String,
+
+        /// How CLI filters combine with the environment filters (NC2PARQUET_*_FILTERS)
+        /// of the same type: replace them, or append to them without duplicates
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = FilterMergeMode::Replace,
+            env = "NC2PARQUET_FILTER_MERGE"
+        )]
+        filter_merge: FilterMergeMode,
+
+        /// How filters on the same dimension combine: keep the indices selected by
+        /// all of them (and) or by any of them (or). Filters on different
+        /// dimensions always intersect
+        #[arg(long, value_enum, env = "NC2PARQUET_COMBINE")]
+        combine: Option<FilterCombine>,
+
+        /// Fail instead of writing an empty output when the filters select no rows,
+        /// naming the filters that match nothing
+        #[arg(long, env = "NC2PARQUET_FAIL_ON_EMPTY")]
+        fail_on_empty: bool,
+
+        /// What to do when the output file already exists
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = OverwritePolicy::Error,
+            env = "NC2PARQUET_OVERWRITE_POLICY"
+        )]
+        overwrite_policy: OverwritePolicy,
+
+        /// Force overwrite existing output files (same as --overwrite-policy overwrite)
         #[arg(long, env = "NC2PARQUET_FORCE")]
         force: bool,
 
@@ -155,6 +340,21 @@ EXAMPLES:
         #[arg(long, env = "NC2PARQUET_DRY_RUN")]
         dry_run: bool,
 
+        /// Write and delete an empty object next to the output before extracting,
+        /// so missing write permissions (e.g. on S3) fail early
+        #[arg(long, env = "NC2PARQUET_CHECK_WRITE")]
+        check_write: bool,
+
+        /// Skip writing when the output's content hash matches its .sha256 sidecar.
+        /// Existing outputs may be replaced without --force
+        #[arg(long, env = "NC2PARQUET_SKIP_UNCHANGED")]
+        skip_unchanged: bool,
+
+        /// Append the rows to an existing output instead of replacing it. This is not
+        /// an O(1) append: the file is read and rewritten, and its schema must match
+        #[arg(long, env = "NC2PARQUET_APPEND", conflicts_with = "skip_unchanged")]
+        append: bool,
+
         /// Rename column: old_name:new_name (can be used multiple times)
         #[arg(long = "rename", value_parser = parse_rename_column)]
         rename_columns: Vec<RenameColumnArg>,
@@ -167,9 +367,185 @@ EXAMPLES:
         #[arg(long = "kelvin-to-celsius")]
         kelvin_to_celsius: Vec<String>,
 
+        /// Convert the value column to this unit when its units attribute measures the
+        /// quantity: quantity:unit, e.g. temperature:celsius (can be used multiple times)
+        #[arg(long = "auto-unit", value_parser = parse_auto_unit)]
+        auto_units: Vec<AutoUnitArg>,
+
         /// Apply mathematical formula: target_column:formula:source1,source2,...
         #[arg(long = "formula", value_parser = parse_formula)]
         formulas: Vec<FormulaArg>,
+
+        /// Aggregate a column after the other post-processing: column:operation, e.g.
+        /// temperature:mean (can be used multiple times). Without --group-by, the
+        /// whole output is reduced to a single row
+        #[arg(long = "aggregate", value_parser = parse_aggregate)]
+        aggregates: Vec<AggregateArg>,
+
+        /// Columns to group by when aggregating: col1,col2,...
+        #[arg(long = "group-by", value_delimiter = ',', requires = "aggregates")]
+        group_by: Vec<String>,
+
+        /// Pack a float column into integers before writing, the inverse of NetCDF
+        /// packing: column:scale_factor:add_offset:dtype, e.g. temperature:0.01:273.15:int16
+        /// (dtype is int8, int16, int32, uint8, uint16 or uint32). The values are stored
+        /// as round((value - add_offset) / scale_factor), and the parameters are kept in
+        /// the Parquet metadata (can be used multiple times)
+        #[arg(long = "pack-output", value_parser = parse_pack_output)]
+        pack_outputs: Vec<PackOutputArg>,
+
+        /// Write at most this many rows (applied after filtering and post-processing).
+        /// Rows are taken in output order, so this is not a random or sorted sample
+        #[arg(long, value_name = "N", env = "NC2PARQUET_LIMIT")]
+        limit: Option<usize>,
+
+        /// Extract a random sample of this many coordinate combinations (after filtering).
+        /// Unlike --limit, the rows are spread over the whole filtered selection
+        #[arg(long, value_name = "N", env = "NC2PARQUET_SAMPLE")]
+        sample: Option<usize>,
+
+        /// Seed for --sample, making the selected rows reproducible
+        #[arg(
+            long,
+            value_name = "SEED",
+            requires = "sample",
+            env = "NC2PARQUET_SAMPLE_SEED"
+        )]
+        sample_seed: Option<u64>,
+
+        /// Convert coordinates with CF time units ("<unit> since <reference>") to UTC
+        /// datetimes. References without a zone are assumed to be in UTC
+        #[arg(long, env = "NC2PARQUET_DECODE_TIMES")]
+        decode_times: bool,
+
+        /// Add an integer <DIMENSION>_idx column per dimension holding the NetCDF
+        /// index each row was read from, next to the coordinate columns
+        #[arg(long, env = "NC2PARQUET_INCLUDE_INDICES")]
+        include_indices: bool,
+
+        /// Remove the coordinate columns (and index columns) after post-processing,
+        /// keeping only the value columns, for consumers that rebuild the grid
+        #[arg(long, env = "NC2PARQUET_DROP_COORDINATES")]
+        drop_coordinates: bool,
+
+        /// Write the coordinate columns sorted by dimension name instead of in the
+        /// variable's dimension order, so files storing the same dimensions in a
+        /// different order produce the same schema
+        #[arg(long, env = "NC2PARQUET_SORT_COORDINATES")]
+        sort_coordinates: bool,
+
+        /// Dimensions whose coordinate columns come first, in this order: dim1,dim2,...
+        /// The other coordinate columns follow
+        #[arg(
+            long,
+            value_name = "DIMENSIONS",
+            value_delimiter = ',',
+            env = "NC2PARQUET_COORDINATE_ORDER"
+        )]
+        coordinate_order: Vec<String>,
+
+        /// Start extracting at this index along the unlimited (record) dimension, e.g.
+        /// the number of records processed by a previous run of a growing file
+        #[arg(long, value_name = "INDEX", env = "NC2PARQUET_START_RECORD")]
+        start_record: Option<usize>,
+
+        /// Name the value column after the variable's CF standard_name attribute,
+        /// falling back to long_name and then to the variable name
+        #[arg(long, env = "NC2PARQUET_RENAME_FROM_ATTRIBUTES")]
+        rename_from_attributes: bool,
+
+        /// What to do with NaN values of the variable: keep them, drop their rows
+        /// (before post-processing), or fail with a count
+        #[arg(long, value_enum, env = "NC2PARQUET_NAN_POLICY")]
+        nan_policy: Option<NanPolicy>,
+
+        /// Check the input against CF conventions first (coordinate variables
+        /// present and monotonic, coordinate units, _FillValue types) and fail
+        /// listing every violation
+        #[arg(long, env = "NC2PARQUET_STRICT_CF")]
+        strict_cf: bool,
+
+        /// Also write <OUTPUT>.cellidx, a binary index mapping coordinate index
+        /// tuples to row numbers in the output
+        #[arg(long, env = "NC2PARQUET_CELL_INDEX")]
+        cell_index: bool,
+
+        /// Also write <OUTPUT>.json, a manifest listing the output's columns and
+        /// types, row count, source file and variable, filters and post-processors
+        #[arg(long, env = "NC2PARQUET_WRITE_MANIFEST")]
+        write_manifest: bool,
+
+        /// Add a Parquet Bloom filter on these output columns (comma-separated),
+        /// to speed up point lookups by readers that use them
+        #[arg(
+            long = "bloom-filter-columns",
+            value_name = "COLUMNS",
+            value_delimiter = ',',
+            env = "NC2PARQUET_BLOOM_FILTER_COLUMNS"
+        )]
+        bloom_filter_columns: Vec<String>,
+
+        /// Compression codec of the Parquet output
+        #[arg(long, value_enum, env = "NC2PARQUET_COMPRESSION")]
+        compression: Option<CompressionCodec>,
+
+        /// Compression level: 1-22 for zstd, 0-9 for gzip, 0-11 for brotli
+        /// (the codec's default when omitted)
+        #[arg(long, value_name = "LEVEL", env = "NC2PARQUET_COMPRESSION_LEVEL")]
+        compression_level: Option<i32>,
+
+        /// Write min/max and null count statistics per column chunk (default: true).
+        /// Query engines use them to skip row groups, at the cost of a few bytes
+        /// per column; turning them off only pays for many tiny files
+        #[arg(long, value_name = "BOOL", env = "NC2PARQUET_WRITE_STATISTICS")]
+        write_statistics: Option<bool>,
+
+        /// Extract and write at most this many rows at a time (local files only)
+        #[arg(long, value_name = "ROWS", env = "NC2PARQUET_CHUNK_SIZE")]
+        chunk_size: Option<usize>,
+
+        /// Before converting, print an estimate of the peak memory of the extraction:
+        /// the selected rows times the bytes of their coordinate and value columns,
+        /// plus the coordinate combinations (local files only)
+        #[arg(long, env = "NC2PARQUET_ESTIMATE_MEMORY")]
+        estimate_memory: bool,
+
+        /// Memory the extraction may use, e.g. 512MiB or 4GB. When the estimate
+        /// exceeds it, the job streams in chunks that fit (if it can stream and no
+        /// --chunk-size is given) or is aborted
+        #[arg(
+            long,
+            value_name = "SIZE",
+            value_parser = parse_memory_size,
+            env = "NC2PARQUET_MEMORY_LIMIT"
+        )]
+        memory_limit: Option<u64>,
+
+        /// Local file listing completed outputs, one path per line. A job whose
+        /// output is listed there and still exists is skipped; a successful job
+        /// adds its output, so scripted batches can be resumed after a failure
+        #[arg(long, value_name = "PATH", env = "NC2PARQUET_STATE_FILE")]
+        state_file: Option<PathBuf>,
+
+        /// After processing, write a JSON run report (paths, filters, post-processors,
+        /// row count, output size and elapsed time) to this path (local or S3)
+        #[arg(long, value_name = "PATH", env = "NC2PARQUET_REPORT")]
+        report: Option<String>,
+
+        /// After the run, write metrics (rows processed, bytes written, duration and
+        /// failures) to this local file, e.g. for the node_exporter textfile collector.
+        /// Failed runs are recorded too
+        #[arg(long, value_name = "PATH", env = "NC2PARQUET_METRICS_FILE")]
+        metrics_file: Option<PathBuf>,
+
+        /// Format of the --metrics-file
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = MetricsFormat::Prometheus,
+            env = "NC2PARQUET_METRICS_FORMAT"
+        )]
+        metrics_format: MetricsFormat,
     },
 
     /// Validate configuration file or arguments
@@ -200,6 +576,11 @@ EXAMPLES:
         /// Show detailed validation report
         #[arg(long)]
         detailed: bool,
+
+        /// Write and delete an empty object next to the output to check that it
+        /// can be written
+        #[arg(long, env = "NC2PARQUET_CHECK_WRITE")]
+        check_write: bool,
     },
 
     /// Show information about NetCDF file
@@ -240,11 +621,183 @@ EXAMPLES:
         #[arg(short = 'n', long)]
         variable: Option<String>,
 
+        /// Only read the file header, skipping the storage layout of --detailed
+        #[arg(
+            long,
+            visible_alias = "no-coordinate-read",
+            env = "NC2PARQUET_HEADER_ONLY"
+        )]
+        header_only: bool,
+
         /// Output format for file information
         #[arg(long, value_enum)]
         format: Option<OutputFormat>,
     },
 
+    /// Compare the structure of two NetCDF files
+    #[command(long_about = "
+Compare the structure of two NetCDF files (local or S3).
+
+Reports the dimensions and variables that were added, removed or renamed,
+changed dimension lengths, variable types, dimensions and shapes, and added,
+removed or changed variable and global attributes. A dimension or variable
+that only changed its name is reported as renamed when its definition is
+otherwise identical and the match is unambiguous.
+
+EXAMPLES:
+  # Human-readable table of differences
+  nc2parquet diff old.nc new.nc
+
+  # JSON output for scripting
+  nc2parquet diff s3://bucket/v1/data.nc s3://bucket/v2/data.nc --format json
+")]
+    Diff {
+        /// Original NetCDF file path (local or S3)
+        old: String,
+
+        /// NetCDF file path to compare against it (local or S3)
+        new: String,
+
+        /// Output format for the differences
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Print the value of a variable at a single grid cell
+    #[command(long_about = "
+Print the value of a variable at a single grid cell, without writing a file.
+
+Each dimension of the variable is pinned either by coordinate value with
+--at (matched exactly) or by index with --index. Dimensions of length one may
+be left out. The resolved indices and coordinates are printed with the value,
+which is decoded (packing, missing values) exactly as in a conversion.
+
+EXAMPLES:
+  # Temperature at the first time step and level, at 30N 120W
+  nc2parquet peek pres_temp_4D.nc -n temperature \\
+    --index time:0 --index level:0 --at latitude:30 --at longitude:-120
+
+  # JSON output for scripting
+  nc2parquet peek s3://bucket/data.nc --index time:0 --index lat:10 --index lon:20 --format json
+")]
+    Peek {
+        /// NetCDF file path (local or S3)
+        file: String,
+
+        /// Variable to read (default: the main data variable)
+        #[arg(short = 'n', long)]
+        variable: Option<String>,
+
+        /// Pin a dimension by coordinate value: dimension:value
+        #[arg(long = "at", value_name = "DIM:VALUE", value_parser = parse_coordinate_spec)]
+        at: Vec<CellSpec>,
+
+        /// Pin a dimension by index: dimension:index
+        #[arg(long = "index", value_name = "DIM:INDEX", value_parser = parse_index_spec)]
+        index: Vec<CellSpec>,
+
+        /// Output format for the cell
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+
+    /// Print the first rows a conversion would write, without writing a file
+    #[command(long_about = "
+Print the first rows a conversion would write, without writing a file.
+
+The rows are built exactly as in a conversion: the filters and the
+post-processing of the configuration file (--config) are applied, along with the
+filter flags given here. When every post-processor is row-wise, extraction stops
+as soon as enough rows are produced, so sampling a large file stays fast.
+
+EXAMPLES:
+  # First 10 rows of the main variable
+  nc2parquet sample data.nc
+
+  # First 25 rows of a filtered variable
+  nc2parquet sample data.nc -n temperature --rows 25 --range latitude:30:60
+
+  # Rows of a configured job, as CSV
+  nc2parquet --config job.json sample --format csv
+")]
+    Sample {
+        /// Input NetCDF file path (local or S3)
+        #[arg(value_name = "INPUT", env = "NC2PARQUET_INPUT")]
+        input: Option<String>,
+
+        /// NetCDF variable name to extract (group path for grouped variables, e.g. /diagnostics/temp)
+        #[arg(short = 'n', long, env = "NC2PARQUET_VARIABLE")]
+        variable: Option<String>,
+
+        /// Number of rows to print
+        #[arg(long, default_value_t = 10)]
+        rows: usize,
+
+        /// Apply range filter: dimension:min:max
+        #[arg(long = "range", value_parser = parse_range_filter)]
+        range_filters: Vec<RangeFilterArg>,
+
+        /// Apply list filter: dimension:val1,val2,val3
+        #[arg(long = "list", value_parser = parse_list_filter)]
+        list_filters: Vec<ListFilterArg>,
+
+        /// Apply 2D point filter: lat_dim,lon_dim:lat,lon:tolerance
+        #[arg(long = "point2d", value_parser = parse_point2d_filter)]
+        point2d_filters: Vec<Point2DFilterArg>,
+
+        /// Apply 3D point filter: time_dim,lat_dim,lon_dim:time,lat,lon:tolerance
+        #[arg(long = "point3d", value_parser = parse_point3d_filter)]
+        point3d_filters: Vec<Point3DFilterArg>,
+
+        /// Select the time steps between two ISO 8601 times (inclusive), e.g.
+        /// 2020-01-01..2020-01-31T18:00. The time variable must have CF time units
+        #[arg(
+            long,
+            value_name = "START..END",
+            value_parser = parse_time_range,
+            env = "NC2PARQUET_TIME_RANGE"
+        )]
+        time_range: Option<TimeRangeArg>,
+
+        /// Time dimension selected by --time-range
+        #[arg(
+            long,
+            value_name = "DIM",
+            default_value = "time",
+            env = "NC2PARQUET_TIME_DIMENSION"
+        )]
+        time_dimension: String,
+
+        /// How CLI filters combine with the environment filters (NC2PARQUET_*_FILTERS)
+        /// of the same type: replace them, or append to them without duplicates
+        #[arg(
+            long,
+            value_enum,
+            default_value_t = FilterMergeMode::Replace,
+            env = "NC2PARQUET_FILTER_MERGE"
+        )]
+        filter_merge: FilterMergeMode,
+
+        /// How filters on the same dimension combine: keep the indices selected by
+        /// all of them (and) or by any of them (or)
+        #[arg(long, value_enum, env = "NC2PARQUET_COMBINE")]
+        combine: Option<FilterCombine>,
+
+        /// Convert coordinates with CF time units ("<unit> since <reference>") to UTC
+        /// datetimes. References without a zone are assumed to be in UTC
+        #[arg(long, env = "NC2PARQUET_DECODE_TIMES")]
+        decode_times: bool,
+
+        /// Add an integer <DIMENSION>_idx column per dimension holding the NetCDF
+        /// index each row was read from
+        #[arg(long, env = "NC2PARQUET_INCLUDE_INDICES")]
+        include_indices: bool,
+
+        /// Output format for the rows
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+
     /// Generate configuration templates
     #[command(long_about = "
 Generate configuration file templates for common use cases.
@@ -288,6 +841,32 @@ EXAMPLES:
         format: ConfigFormat,
     },
 
+    /// Check that a Parquet file matches a configuration's output schema
+    #[command(long_about = "
+Check that an existing Parquet file matches the schema a configuration would produce.
+
+The expected schema is computed from the configured NetCDF variable and the
+post-processing pipeline, then compared with the Parquet file's schema.
+Missing columns, unexpected columns, and type mismatches are reported, and
+the command fails if any are found.
+
+EXAMPLES:
+  # Check a Parquet file against a configuration
+  nc2parquet check-schema --config job.json --parquet output.parquet
+
+  # JSON report for scripting
+  nc2parquet check-schema --config job.yaml --parquet s3://bucket/data.parquet --format json
+")]
+    CheckSchema {
+        /// Parquet file to check (local or S3)
+        #[arg(long, value_name = "PARQUET")]
+        parquet: String,
+
+        /// Output format for the schema report
+        #[arg(long, value_enum)]
+        format: Option<OutputFormat>,
+    },
+
     /// Generate shell completions
     #[command(long_about = "
 Generate shell completion scripts for various shells.
@@ -366,6 +945,31 @@ pub enum ConfigFormat {
     Yaml,
 }
 
+/// How CLI filters combine with the environment filters of the same type
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterMergeMode {
+    /// CLI filters of a type replace all environment filters of that type
+    #[default]
+    Replace,
+    /// CLI filters come first, followed by the environment filters of that type
+    /// that are not identical to one already given
+    Append,
+}
+
+/// What to do when a conversion's output file already exists
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OverwritePolicy {
+    /// Fail without writing anything
+    #[default]
+    Error,
+    /// Replace the existing file
+    Overwrite,
+    /// Write to the first free name among `output.1.parquet`, `output.2.parquet`, ...
+    Version,
+}
+
 /// Range filter argument from command line
 #[derive(Clone, Debug, PartialEq)]
 pub struct RangeFilterArg {
@@ -382,7 +986,7 @@ pub struct ListFilterArg {
 }
 
 /// Command-line argument for 2D spatial point filtering  
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Point2DFilterArg {
     pub lat_dimension: String,
     pub lon_dimension: String,
@@ -392,7 +996,7 @@ pub struct Point2DFilterArg {
 }
 
 /// Command-line argument for 3D spatiotemporal point filtering
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct Point3DFilterArg {
     pub time_dimension: String,
     pub lat_dimension: String,
@@ -403,6 +1007,13 @@ pub struct Point3DFilterArg {
     pub tolerance: f64,
 }
 
+/// Command-line argument for selecting a time range by ISO 8601 times
+#[derive(Debug, Clone, PartialEq)]
+pub struct TimeRangeArg {
+    pub start: String,
+    pub end: String,
+}
+
 /// Post-processing argument structures
 #[derive(Debug, Clone)]
 pub struct RenameColumnArg {
@@ -417,6 +1028,26 @@ pub struct UnitConversionArg {
     pub to_unit: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct AutoUnitArg {
+    pub quantity: String,
+    pub unit: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct AggregateArg {
+    pub column: String,
+    pub op: AggregationOp,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PackOutputArg {
+    pub column: String,
+    pub scale_factor: f64,
+    pub add_offset: f64,
+    pub dtype: PackedType,
+}
+
 #[derive(Debug, Clone)]
 pub struct FormulaArg {
     pub target_column: String,
@@ -501,6 +1132,29 @@ fn parse_range_filter(s: &str) -> Result<RangeFilterArg, String> {
     })
 }
 
+/// Parse time range from command line argument
+/// Format: start..end, both ISO 8601 times
+fn parse_time_range(s: &str) -> Result<TimeRangeArg, String> {
+    let (start, end) = s
+        .split_once("..")
+        .ok_or("Time range must be in format 'start..end'")?;
+    let (start, end) = (start.trim(), end.trim());
+    let start_time = parse_reference_datetime(start)
+        .map_err(|_| format!("Invalid start time '{}' (expected ISO 8601)", start))?
+        .0;
+    let end_time = parse_reference_datetime(end)
+        .map_err(|_| format!("Invalid end time '{}' (expected ISO 8601)", end))?
+        .0;
+    if start_time > end_time {
+        return Err("Time range start must not be after its end".to_string());
+    }
+
+    Ok(TimeRangeArg {
+        start: start.to_string(),
+        end: end.to_string(),
+    })
+}
+
 /// Parse list filter from command line argument
 /// Format: dimension:val1,val2,val3
 fn parse_list_filter(s: &str) -> Result<ListFilterArg, String> {
@@ -636,6 +1290,71 @@ fn parse_rename_column(s: &str) -> Result<RenameColumnArg, String> {
     Ok(RenameColumnArg { old_name, new_name })
 }
 
+/// Parse automatic unit conversion argument: quantity:unit
+fn parse_auto_unit(s: &str) -> Result<AutoUnitArg, String> {
+    let parts: Vec<&str> = s.split(':').collect();
+    if parts.len() != 2 {
+        return Err("Automatic unit conversion must be in format 'quantity:unit'".to_string());
+    }
+
+    let quantity = parts[0].trim().to_string();
+    let unit = parts[1].trim().to_string();
+
+    if quantity.is_empty() || unit.is_empty() {
+        return Err("Quantity and unit cannot be empty".to_string());
+    }
+
+    Ok(AutoUnitArg { quantity, unit })
+}
+
+/// Parse a cell coordinate argument: dimension:value
+fn parse_coordinate_spec(s: &str) -> Result<CellSpec, String> {
+    let (dimension, value) = s
+        .split_once(':')
+        .ok_or("Coordinate must be in format 'dimension:value'")?;
+    if dimension.is_empty() {
+        return Err("Dimension cannot be empty".to_string());
+    }
+    let value = value
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| "Invalid coordinate value")?;
+
+    Ok(CellSpec::coordinate(dimension, value))
+}
+
+/// Parse a cell index argument: dimension:index
+fn parse_index_spec(s: &str) -> Result<CellSpec, String> {
+    let (dimension, index) = s
+        .split_once(':')
+        .ok_or("Index must be in format 'dimension:index'")?;
+    if dimension.is_empty() {
+        return Err("Dimension cannot be empty".to_string());
+    }
+    let index = index
+        .trim()
+        .parse::<usize>()
+        .map_err(|_| "Invalid index, expected a non-negative integer")?;
+
+    Ok(CellSpec::index(dimension, index))
+}
+
+/// Parse aggregation argument: column:operation
+fn parse_aggregate(s: &str) -> Result<AggregateArg, String> {
+    let parts: Vec<&str> = s.split(':').collect();
+    if parts.len() != 2 {
+        return Err("Aggregation must be in format 'column:operation'".to_string());
+    }
+
+    let column = parts[0].trim().to_string();
+    if column.is_empty() {
+        return Err("Aggregation column cannot be empty".to_string());
+    }
+    let op = parts[1].trim().parse::<AggregationOp>()?;
+
+    Ok(AggregateArg { column, op })
+}
+
 /// Parse unit conversion argument: column:from_unit:to_unit
 fn parse_unit_conversion(s: &str) -> Result<UnitConversionArg, String> {
     let parts: Vec<&str> = s.split(':').collect();
@@ -658,6 +1377,72 @@ fn parse_unit_conversion(s: &str) -> Result<UnitConversionArg, String> {
     })
 }
 
+/// Parse packing argument: column:scale_factor:add_offset:dtype
+fn parse_pack_output(s: &str) -> Result<PackOutputArg, String> {
+    let parts: Vec<&str> = s.split(':').collect();
+    if parts.len() != 4 {
+        return Err("Packing must be in format 'column:scale_factor:add_offset:dtype'".to_string());
+    }
+
+    let column = parts[0].trim().to_string();
+    if column.is_empty() {
+        return Err("Packed column cannot be empty".to_string());
+    }
+    let scale_factor = parts[1]
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid scale factor: {}", parts[1]))?;
+    let add_offset = parts[2]
+        .trim()
+        .parse::<f64>()
+        .map_err(|_| format!("Invalid offset: {}", parts[2]))?;
+    let dtype = parts[3].trim().parse::<PackedType>()?;
+
+    Ok(PackOutputArg {
+        column,
+        scale_factor,
+        add_offset,
+        dtype,
+    })
+}
+
+/// Parse a memory size: a number of bytes with an optional unit, decimal (KB,
+/// MB, GB, TB) or binary (KiB, MiB, GiB, TiB), e.g. `512MiB` or `1.5GB`
+fn parse_memory_size(s: &str) -> Result<u64, String> {
+    const UNITS: &[(&str, f64)] = &[
+        ("kib", 1024.0),
+        ("mib", 1024.0 * 1024.0),
+        ("gib", 1024.0 * 1024.0 * 1024.0),
+        ("tib", 1024.0 * 1024.0 * 1024.0 * 1024.0),
+        ("kb", 1e3),
+        ("mb", 1e6),
+        ("gb", 1e9),
+        ("tb", 1e12),
+        ("b", 1.0),
+    ];
+
+    let lower = s.trim().to_lowercase();
+    let (number, multiplier) = UNITS
+        .iter()
+        .find_map(|(unit, multiplier)| {
+            lower
+                .strip_suffix(unit)
+                .map(|number| (number.trim(), *multiplier))
+        })
+        .unwrap_or((lower.as_str(), 1.0));
+    let value = number
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value > 0.0)
+        .ok_or_else(|| {
+            format!(
+                "Invalid memory size '{}' (expected e.g. 512MiB, 4GB or a number of bytes)",
+                s
+            )
+        })?;
+    Ok((value * multiplier) as u64)
+}
+
 /// Parse formula argument: target_column:formula:source1,source2,...
 fn parse_formula(s: &str) -> Result<FormulaArg, String> {
     let parts: Vec<&str> = s.splitn(3, ':').collect();
@@ -686,6 +1471,52 @@ fn parse_formula(s: &str) -> Result<FormulaArg, String> {
     })
 }
 
+/// Kinds of filter accepted by [`parse_filter_dsl`]
+const FILTER_DSL_KINDS: [&str; 5] = ["range", "list", "point2d", "point3d", "time"];
+
+/// Parse a filter written in the compact syntax of the command line, used by the
+/// `filters_dsl` field of configuration files.
+/// Format: `<kind> <spec>`, where the spec of `range`, `list`, `point2d` and
+/// `point3d` is that of the `--range`, `--list`, `--point2d` and `--point3d`
+/// options, and that of `time` is `time_dim:start..end`
+pub fn parse_filter_dsl(s: &str) -> Result<FilterConfig, String> {
+    let s = s.trim();
+    let (kind, spec) = s
+        .split_once(char::is_whitespace)
+        .map(|(kind, spec)| (kind, spec.trim()))
+        .ok_or_else(|| {
+            format!(
+                "Filter must be in format '<kind> <spec>', with kind one of {}",
+                FILTER_DSL_KINDS.join(", ")
+            )
+        })?;
+    match kind {
+        "range" => parse_range_filter(spec).map(FilterConfig::from),
+        "list" => parse_list_filter(spec).map(FilterConfig::from),
+        "point2d" => parse_point2d_filter(spec).map(FilterConfig::from),
+        "point3d" => parse_point3d_filter(spec).map(FilterConfig::from),
+        "time" => {
+            let (dimension, range) = spec
+                .split_once(':')
+                .ok_or("Time filter must be in format 'time_dim:start..end'")?;
+            let time_range = parse_time_range(range)?;
+            Ok(FilterConfig::TimeRange {
+                params: crate::input::TimeRangeParams {
+                    time_dimension_name: dimension.trim().to_string(),
+                    start_date: time_range.start,
+                    end_date: time_range.end,
+                    enabled: true,
+                },
+            })
+        }
+        _ => Err(format!(
+            "Unknown filter kind '{}' (expected one of {})",
+            kind,
+            FILTER_DSL_KINDS.join(", ")
+        )),
+    }
+}
+
 impl From<RangeFilterArg> for FilterConfig {
     fn from(arg: RangeFilterArg) -> Self {
         FilterConfig::Range {
@@ -693,6 +1524,10 @@ impl From<RangeFilterArg> for FilterConfig {
                 dimension_name: arg.dimension,
                 min_value: arg.min_value,
                 max_value: arg.max_value,
+                normalize_longitude: false,
+                min_inclusive: true,
+                max_inclusive: true,
+                enabled: true,
             },
         }
     }
@@ -704,6 +1539,8 @@ impl From<ListFilterArg> for FilterConfig {
             params: crate::input::ListParams {
                 dimension_name: arg.dimension,
                 values: arg.values,
+                tolerance: None,
+                enabled: true,
             },
         }
     }
@@ -717,6 +1554,9 @@ impl From<Point2DFilterArg> for FilterConfig {
                 lon_dimension_name: arg.lon_dimension,
                 points: vec![(arg.lat, arg.lon)],
                 tolerance: arg.tolerance,
+                snap_nearest: false,
+                normalize_longitude: false,
+                enabled: true,
             },
         }
     }
@@ -730,8 +1570,11 @@ impl From<Point3DFilterArg> for FilterConfig {
                 lat_dimension_name: arg.lat_dimension,
                 lon_dimension_name: arg.lon_dimension,
                 steps: vec![arg.time],
+                step_dates: Vec::new(),
                 points: vec![(arg.lat, arg.lon)],
                 tolerance: arg.tolerance,
+                normalize_longitude: false,
+                enabled: true,
             },
         }
     }
@@ -850,39 +1693,44 @@ pub fn parse_filters_from_env() -> FilterResult {
     ))
 }
 
-/// Merge CLI filters with environment variable filters
-/// Priority: CLI arguments > Environment variables
+/// Merge CLI filters with environment variable filters, type by type.
+///
+/// With [`FilterMergeMode::Replace`], CLI filters of a type take the place of the
+/// environment filters of that type, which are only used when no CLI filter of
+/// the type is given. With [`FilterMergeMode::Append`], both are kept, CLI
+/// filters first, and identical filters are only kept once.
 pub fn merge_filters(
     cli_range: Vec<RangeFilterArg>,
     cli_list: Vec<ListFilterArg>,
     cli_point2d: Vec<Point2DFilterArg>,
     cli_point3d: Vec<Point3DFilterArg>,
+    mode: FilterMergeMode,
 ) -> FilterResult {
     let (env_range, env_list, env_point2d, env_point3d) = parse_filters_from_env()?;
 
-    // CLI arguments have priority, but we append env filters if CLI is empty
-    let merged_range = if cli_range.is_empty() {
-        env_range
-    } else {
-        cli_range
-    };
-    let merged_list = if cli_list.is_empty() {
-        env_list
-    } else {
-        cli_list
-    };
-    let merged_point2d = if cli_point2d.is_empty() {
-        env_point2d
-    } else {
-        cli_point2d
-    };
-    let merged_point3d = if cli_point3d.is_empty() {
-        env_point3d
-    } else {
-        cli_point3d
-    };
-
-    Ok((merged_range, merged_list, merged_point2d, merged_point3d))
+    Ok((
+        merge_filter_list(cli_range, env_range, mode),
+        merge_filter_list(cli_list, env_list, mode),
+        merge_filter_list(cli_point2d, env_point2d, mode),
+        merge_filter_list(cli_point3d, env_point3d, mode),
+    ))
+}
+
+/// Merges the CLI and environment filters of one type
+fn merge_filter_list<T: PartialEq>(cli: Vec<T>, env: Vec<T>, mode: FilterMergeMode) -> Vec<T> {
+    match mode {
+        FilterMergeMode::Replace if cli.is_empty() => env,
+        FilterMergeMode::Replace => cli,
+        FilterMergeMode::Append => {
+            let mut merged = Vec::with_capacity(cli.len() + env.len());
+            for filter in cli.into_iter().chain(env) {
+                if !merged.contains(&filter) {
+                    merged.push(filter);
+                }
+            }
+            merged
+        }
+    }
 }
 
 #[cfg(test)]
@@ -907,6 +1755,22 @@ mod tests {
         assert!(parse_range_filter("latitude:60.0:30.0").is_err()); // min > max
     }
 
+    #[test]
+    fn test_parse_time_range() {
+        let result = parse_time_range("2020-01-01..2020-01-31T18:00:00Z").unwrap();
+        assert_eq!(result.start, "2020-01-01");
+        assert_eq!(result.end, "2020-01-31T18:00:00Z");
+
+        // A single instant is a valid range
+        assert!(parse_time_range("2020-01-01 06:00..2020-01-01 06:00").is_ok());
+
+        // Test invalid formats
+        assert!(parse_time_range("2020-01-01").is_err());
+        assert!(parse_time_range("2020-01-01..").is_err());
+        assert!(parse_time_range("yesterday..2020-01-01").is_err());
+        assert!(parse_time_range("2020-02-01..2020-01-01").is_err()); // start > end
+    }
+
     #[test]
     fn test_parse_list_filter() {
         let result = parse_list_filter("pressure:850.0,500.0,200.0").unwrap();
@@ -958,6 +1822,106 @@ mod tests {
         assert!(parse_point2d_filter("latitude,longitude:30.5,-120.2:-0.1").is_err()); // negative tolerance
     }
 
+    #[test]
+    fn test_parse_auto_unit() {
+        let result = parse_auto_unit("temperature:celsius").unwrap();
+        assert_eq!(result.quantity, "temperature");
+        assert_eq!(result.unit, "celsius");
+
+        assert!(parse_auto_unit("temperature").is_err());
+        assert!(parse_auto_unit("temperature:").is_err());
+        assert!(parse_auto_unit("a:b:c").is_err());
+    }
+
+    #[test]
+    fn test_parse_cell_specs() {
+        assert_eq!(
+            parse_coordinate_spec("longitude:-120.5").unwrap(),
+            CellSpec::coordinate("longitude", -120.5)
+        );
+        assert_eq!(
+            parse_index_spec("level:0").unwrap(),
+            CellSpec::index("level", 0)
+        );
+
+        assert!(parse_coordinate_spec("longitude").is_err());
+        assert!(parse_coordinate_spec(":1.0").is_err());
+        assert!(parse_coordinate_spec("longitude:west").is_err());
+        assert!(parse_index_spec("level:-1").is_err());
+        assert!(parse_index_spec("level:0.5").is_err());
+    }
+
+    #[test]
+    fn test_parse_aggregate() {
+        let result = parse_aggregate("temperature:mean").unwrap();
+        assert_eq!(result.column, "temperature");
+        assert_eq!(result.op, AggregationOp::Mean);
+        assert_eq!(
+            parse_aggregate("station:nunique").unwrap().op,
+            AggregationOp::NUnique
+        );
+
+        assert!(parse_aggregate("temperature").is_err());
+        assert!(parse_aggregate(":mean").is_err());
+        let error = parse_aggregate("temperature:average").unwrap_err();
+        assert!(error.starts_with("Unknown aggregation 'average' (known: mean, sum"));
+    }
+
+    #[test]
+    fn test_parse_pack_output() {
+        assert_eq!(
+            parse_pack_output("temperature:0.01:-273.15:int16").unwrap(),
+            PackOutputArg {
+                column: "temperature".to_string(),
+                scale_factor: 0.01,
+                add_offset: -273.15,
+                dtype: PackedType::Int16,
+            }
+        );
+
+        assert!(parse_pack_output("temperature:0.01:int16").is_err());
+        assert!(parse_pack_output("temperature:small:0:int16").is_err());
+        let error = parse_pack_output("temperature:0.01:0:int64").unwrap_err();
+        assert!(error.starts_with("Unknown packed type 'int64' (known: int8, int16"));
+    }
+
+    #[test]
+    fn test_parse_filter_dsl() {
+        let FilterConfig::Range { params } = parse_filter_dsl("range  latitude:30:60").unwrap()
+        else {
+            panic!("Expected a range filter");
+        };
+        assert_eq!(params.dimension_name, "latitude");
+        assert_eq!((params.min_value, params.max_value), (30.0, 60.0));
+
+        let filter = parse_filter_dsl("point2d lat,lon:40.7,-74.0:0.5").unwrap();
+        assert_eq!(filter.kind(), "2d_point");
+
+        // The errors of the option parsers are kept
+        assert_eq!(
+            parse_filter_dsl("list level").unwrap_err(),
+            "List filter must be in format 'dimension:val1,val2,val3'"
+        );
+        assert!(parse_filter_dsl("time time:2020-02-01..2020-01-01").is_err());
+        assert!(parse_filter_dsl("range").is_err());
+        let error = parse_filter_dsl("stride lon:2").unwrap_err();
+        assert!(error.starts_with("Unknown filter kind 'stride'"));
+    }
+
+    #[test]
+    fn test_parse_memory_size() {
+        assert_eq!(parse_memory_size("1048576"), Ok(1_048_576));
+        assert_eq!(parse_memory_size("512MiB"), Ok(512 * 1024 * 1024));
+        assert_eq!(parse_memory_size("1.5 GB"), Ok(1_500_000_000));
+        assert_eq!(parse_memory_size("4gib"), Ok(4 * 1024 * 1024 * 1024));
+        assert_eq!(parse_memory_size("100b"), Ok(100));
+
+        assert!(parse_memory_size("").is_err());
+        assert!(parse_memory_size("0MB").is_err());
+        assert!(parse_memory_size("-1GB").is_err());
+        assert!(parse_memory_size("lots").is_err());
+    }
+
     #[test]
     fn test_parse_point3d_filter() {
         let result = parse_point3d_filter("time,latitude,longitude:0.0,30.5,-120.2:0.1").unwrap();
@@ -1164,6 +2128,7 @@ mod tests {
             cli_list.clone(),
             cli_point2d,
             cli_point3d,
+            FilterMergeMode::Replace,
         )
         .unwrap();
 
@@ -1197,4 +2162,82 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_filter_merging_append() {
+        // Acquire mutex to ensure exclusive access to environment variables
+        let _guard = ENV_TEST_MUTEX.lock().unwrap();
+
+        use std::env;
+
+        // Save existing environment state
+        let original_range = env::var("NC2PARQUET_RANGE_FILTERS").ok();
+        let original_list = env::var("NC2PARQUET_LIST_FILTERS").ok();
+        let original_point2d = env::var("NC2PARQUET_POINT2D_FILTERS").ok();
+        let original_point3d = env::var("NC2PARQUET_POINT3D_FILTERS").ok();
+
+        unsafe {
+            env::set_var("NC2PARQUET_RANGE_FILTERS", "lat:0:90,lon:-180:180");
+            env::set_var("NC2PARQUET_LIST_FILTERS", "pressure:1000,850");
+            env::set_var("NC2PARQUET_POINT2D_FILTERS", "lat,lon:30.0,-120.0:0.1");
+            env::remove_var("NC2PARQUET_POINT3D_FILTERS");
+        }
+
+        // One CLI range filter repeats a standing environment filter
+        let cli_range = vec![
+            parse_range_filter("lon:-180:180").unwrap(),
+            parse_range_filter("depth:0:10").unwrap(),
+        ];
+        let cli_point2d = vec![parse_point2d_filter("lat,lon:30.0,-120.0:0.1").unwrap()];
+
+        let (range, list, point2d, point3d) = merge_filters(
+            cli_range.clone(),
+            vec![],
+            cli_point2d.clone(),
+            vec![],
+            FilterMergeMode::Append,
+        )
+        .unwrap();
+
+        // CLI filters first, then the environment filters not already given
+        let dimensions: Vec<&str> = range.iter().map(|f| f.dimension.as_str()).collect();
+        assert_eq!(dimensions, vec!["lon", "depth", "lat"]);
+        assert_eq!(list.len(), 1);
+        assert_eq!(point2d, cli_point2d);
+        assert!(point3d.is_empty());
+
+        // Replacing keeps only the CLI filters of types given on the command line
+        let (range, list, point2d, _) = merge_filters(
+            cli_range.clone(),
+            vec![],
+            cli_point2d.clone(),
+            vec![],
+            FilterMergeMode::Replace,
+        )
+        .unwrap();
+        assert_eq!(range, cli_range);
+        assert_eq!(list.len(), 1);
+        assert_eq!(point2d.len(), 1);
+
+        // Cleanup and restore original state
+        unsafe {
+            env::remove_var("NC2PARQUET_RANGE_FILTERS");
+            env::remove_var("NC2PARQUET_LIST_FILTERS");
+            env::remove_var("NC2PARQUET_POINT2D_FILTERS");
+            env::remove_var("NC2PARQUET_POINT3D_FILTERS");
+
+            if let Some(ref val) = original_range {
+                env::set_var("NC2PARQUET_RANGE_FILTERS", val);
+            }
+            if let Some(ref val) = original_list {
+                env::set_var("NC2PARQUET_LIST_FILTERS", val);
+            }
+            if let Some(ref val) = original_point2d {
+                env::set_var("NC2PARQUET_POINT2D_FILTERS", val);
+            }
+            if let Some(ref val) = original_point3d {
+                env::set_var("NC2PARQUET_POINT3D_FILTERS", val);
+            }
+        }
+    }
 }
@@ -0,0 +1,102 @@
+//! # Error Types
+//!
+//! This module defines [`Nc2ParquetError`], the error returned by the processing
+//! functions at the crate root (`process_netcdf_job`, `build_dataframe`, ...).
+//!
+//! Each variant names the step that failed, so library consumers can react to a
+//! missing variable differently from, say, a failed write. Errors from the
+//! post-processing and storage layers are kept as their own types.
+//!
+//! ## Example
+//! ```rust,no_run
+//! use nc2parquet::error::Nc2ParquetError;
+//! use nc2parquet::input::JobConfig;
+//! use nc2parquet::process_netcdf_job;
+//!
+//! let config = JobConfig::from_file("job.json").unwrap();
+//! match process_netcdf_job(&config) {
+//!     Ok(()) => println!("Done"),
+//!     Err(Nc2ParquetError::VariableNotFound(message)) => eprintln!("Check the job: {}", message),
+//!     Err(e) => eprintln!("Conversion failed: {}", e),
+//! }
+//! ```
+
+use crate::postprocess::PostProcessError;
+use crate::storage::StorageError;
+use thiserror::Error;
+
+/// Errors that can occur while processing a NetCDF job
+#[derive(Error, Debug)]
+pub enum Nc2ParquetError {
+    #[error("Failed to open NetCDF file {path}: {message}")]
+    FileOpen { path: String, message: String },
+
+    #[error("Variable not found: {0}")]
+    VariableNotFound(String),
+
+    #[error("Filter error: {0}")]
+    FilterError(String),
+
+    #[error("Extraction error: {0}")]
+    ExtractionError(String),
+
+    #[error("Post-processing error: {0}")]
+    PostProcessError(#[from] PostProcessError),
+
+    #[error("Write error: {0}")]
+    WriteError(String),
+
+    #[error("Invalid job configuration: {0}")]
+    InvalidConfig(String),
+
+    /// Boxed, since the S3 errors it wraps are large
+    #[error("Storage error: {0}")]
+    Storage(Box<StorageError>),
+
+    #[error("NetCDF error: {0}")]
+    NetCdf(#[from] netcdf::Error),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("{0}")]
+    Other(String),
+}
+
+/// Result type for the processing functions
+pub type Nc2ParquetResult<T> = Result<T, Nc2ParquetError>;
+
+/// Keeps `?` working on the boxed errors of the lower-level modules; they are
+/// reported as [`Nc2ParquetError::Other`]
+impl From<Box<dyn std::error::Error>> for Nc2ParquetError {
+    fn from(error: Box<dyn std::error::Error>) -> Self {
+        Nc2ParquetError::Other(error.to_string())
+    }
+}
+
+impl From<StorageError> for Nc2ParquetError {
+    fn from(error: StorageError) -> Self {
+        Nc2ParquetError::Storage(Box::new(error))
+    }
+}
+
+impl Nc2ParquetError {
+    pub(crate) fn file_open(path: &str, error: impl std::fmt::Display) -> Self {
+        Nc2ParquetError::FileOpen {
+            path: path.to_string(),
+            message: error.to_string(),
+        }
+    }
+
+    pub(crate) fn filter(error: impl std::fmt::Display) -> Self {
+        Nc2ParquetError::FilterError(error.to_string())
+    }
+
+    pub(crate) fn extraction(error: impl std::fmt::Display) -> Self {
+        Nc2ParquetError::ExtractionError(error.to_string())
+    }
+
+    pub(crate) fn write(error: impl std::fmt::Display) -> Self {
+        Nc2ParquetError::WriteError(error.to_string())
+    }
+}
@@ -7,11 +7,563 @@
 //!
 //! - [`DimensionIndexManager`]: Manages dimension indices and filter intersections
 //! - [`extract_data_to_dataframe`]: Main extraction function with filter application
+//! - [`extract_data_to_dataframe_chunked`]: Extraction in fixed-size row chunks for large variables
+//! - [`extract_data_to_dataframe_with_progress`]: Extraction reporting [`ExtractionProgress`] events
+//! - [`apply_filters`]: Filter evaluation, concurrent across independent dimensions
+//! - [`CoordTransform`]: Affine transform applied to coordinate values on output
+//! - [`SampleConfig`]: Reproducible random sample of coordinate combinations
+//! - [`ExtractOptions`]: Coordinate transforms and sampling applied during extraction
+//! - [`DataColumnType`]: Native Polars type used for the extracted variable column
+//! - [`ValueEncoding`]: CF packing and missing-value attributes, with [`VarOverride`]s applied
 
-use crate::filters::{FilterResult, NCFilter};
+use crate::cell_index::CellIndex;
+use crate::cftime::{decode_time_columns, read_time_references};
+use crate::filters::{CoordinateCache, FilterResult, NCFilter};
+use netcdf::NcTypeDescriptor;
+use netcdf::types::{EnumType, EnumTypeValues, FloatType, IntType, NcVariableType};
 use polars::prelude::*;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 
+/// Affine transform (`value * scale + offset`) applied to a coordinate column.
+///
+/// Transforms only affect the values written to the output; filters are always
+/// evaluated against the coordinates stored in the NetCDF file.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize, Serialize)]
+pub struct CoordTransform {
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+    #[serde(default)]
+    pub offset: f64,
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+impl CoordTransform {
+    pub fn new(scale: f64, offset: f64) -> Self {
+        CoordTransform { scale, offset }
+    }
+
+    pub fn apply(&self, value: f64) -> f64 {
+        value * self.scale + self.offset
+    }
+}
+
+/// Random sample of `n` coordinate combinations to extract instead of all of them.
+///
+/// Sampling happens after filtering and before any values are read. The same
+/// `seed` always selects the same combinations; without a seed the selection
+/// differs between runs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SampleConfig {
+    pub n: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+}
+
+/// Per-variable replacements for the CF attributes that describe stored values.
+///
+/// Each field that is set takes precedence over the corresponding attribute of the
+/// NetCDF variable, so files with wrong or missing attributes can still be decoded.
+/// `fill_value`, `valid_min` and `valid_max` are compared against the stored
+/// (packed) values, before `scale_factor` and `add_offset` are applied.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize, Serialize)]
+pub struct VarOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fill_value: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_min: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_max: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scale_factor: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub add_offset: Option<f64>,
+}
+
+/// What to do with NaN values of the extracted variable.
+///
+/// Nulls (e.g. masked fill values) are not NaN and are never affected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum NanPolicy {
+    /// Write NaN values as they are
+    #[default]
+    Keep,
+    /// Drop the rows whose value is NaN, before any post-processing
+    Drop,
+    /// Fail the extraction, reporting how many values are NaN
+    Error,
+}
+
+impl NanPolicy {
+    pub fn is_keep(&self) -> bool {
+        *self == NanPolicy::Keep
+    }
+}
+
+/// How filters on the same dimension are combined.
+///
+/// Filters on different dimensions, and point filters, always intersect.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterCombine {
+    /// Keep only the indices selected by every filter on the dimension
+    #[default]
+    And,
+    /// Keep the indices selected by any filter on the dimension
+    Or,
+}
+
+impl FilterCombine {
+    pub fn is_and(&self) -> bool {
+        *self == FilterCombine::And
+    }
+}
+
+/// Merges the [`FilterResult::Single`] results of the same dimension into one
+/// result holding the union of their indices, at the position of the first.
+///
+/// Other results are kept as they are, in order.
+pub fn union_single_results(results: Vec<FilterResult>) -> Vec<FilterResult> {
+    let mut merged: Vec<FilterResult> = Vec::with_capacity(results.len());
+    for result in results {
+        let FilterResult::Single { dimension, indices } = result else {
+            merged.push(result);
+            continue;
+        };
+        let existing = merged.iter_mut().find_map(|other| match other {
+            FilterResult::Single {
+                dimension: other_dimension,
+                indices: other_indices,
+            } if *other_dimension == dimension => Some(other_indices),
+            _ => None,
+        });
+        match existing {
+            Some(existing) => {
+                existing.extend(indices);
+                existing.sort_unstable();
+                existing.dedup();
+            }
+            None => merged.push(FilterResult::Single { dimension, indices }),
+        }
+    }
+    merged
+}
+
+/// Applies `filters` to `file`, returning the results of the single-dimension
+/// filters (see [`NCFilter::dimension`]) followed by those of the point filters,
+/// each in the order given.
+///
+/// Single-dimension filters on different dimensions read different coordinate
+/// variables, so they are evaluated concurrently on the rayon pool, one task per
+/// dimension. Point filters come last, since they fix the combinations of the
+/// other dimensions when applied; they are evaluated in turn, reusing the
+/// coordinates read by the single-dimension filters through `cache`.
+pub fn apply_filters(
+    file: &netcdf::File,
+    filters: &[Box<dyn NCFilter>],
+    cache: &mut CoordinateCache,
+) -> Result<Vec<FilterResult>, Box<dyn std::error::Error>> {
+    let mut by_dimension: Vec<(&str, Vec<(usize, &dyn NCFilter)>)> = Vec::new();
+    let mut point_filters = Vec::new();
+    for (position, filter) in filters.iter().enumerate() {
+        let Some(dimension) = filter.dimension() else {
+            point_filters.push(filter);
+            continue;
+        };
+        match by_dimension
+            .iter_mut()
+            .find(|(other, _)| *other == dimension)
+        {
+            Some((_, group)) => group.push((position, filter.as_ref())),
+            None => by_dimension.push((dimension, vec![(position, filter.as_ref())])),
+        }
+    }
+
+    // Errors are carried as strings, since boxed errors cannot leave the pool
+    let groups = by_dimension
+        .into_par_iter()
+        .map(|(_, group)| -> Result<_, String> {
+            let mut group_cache = CoordinateCache::new();
+            let results = group
+                .into_iter()
+                .map(|(position, filter)| -> Result<_, String> {
+                    let result = filter
+                        .apply_cached(file, &mut group_cache)
+                        .map_err(|e| e.to_string())?;
+                    Ok((position, result))
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+            Ok((results, group_cache))
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+
+    let mut single_results = Vec::with_capacity(filters.len());
+    for (results, group_cache) in groups {
+        single_results.extend(results);
+        cache.merge(group_cache);
+    }
+    single_results.sort_by_key(|(position, _)| *position);
+
+    let mut results: Vec<FilterResult> = single_results
+        .into_iter()
+        .map(|(_, result)| result)
+        .collect();
+    for filter in point_filters {
+        results.push(filter.apply_cached(file, cache)?);
+    }
+    Ok(results)
+}
+
+/// Options applied while extracting a variable, on top of its filters.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractOptions {
+    /// Affine transforms applied to coordinate columns, keyed by dimension name
+    pub coordinate_transforms: HashMap<String, CoordTransform>,
+    /// New names of coordinate columns, keyed by dimension name; applied last, so
+    /// the output carries these names from the start
+    pub coordinate_renames: HashMap<String, String>,
+    /// Random sample of coordinate combinations to read instead of all of them
+    pub sample: Option<SampleConfig>,
+    /// Read values one at a time instead of gathering them in parallel from a
+    /// preloaded block (see [`extract_data_to_dataframe_with_progress`])
+    pub serial: bool,
+    /// Convert coordinate columns with CF time units (`<unit> since <reference>`)
+    /// to UTC datetimes, after any coordinate transform
+    pub decode_times: bool,
+    /// First index to extract along the unlimited (record) dimension, so files
+    /// that keep growing can be ingested incrementally
+    pub start_record: Option<usize>,
+    /// Attribute overrides keyed by variable name (see [`VarOverride`])
+    pub variable_overrides: HashMap<String, VarOverride>,
+    /// Handling of NaN values in the variable column (see [`NanPolicy`])
+    pub nan_policy: NanPolicy,
+    /// How filters on the same dimension are combined (see [`FilterCombine`])
+    pub combine: FilterCombine,
+    /// Add a `<dimension>_idx` column per dimension (see [`index_column_name`])
+    /// with the NetCDF indices each row was read from, after the coordinate columns
+    pub include_indices: bool,
+    /// Order the coordinate columns by dimension name instead of following the
+    /// variable's dimensions (see [`coordinate_column_order`])
+    pub sort_coordinates: bool,
+    /// Dimensions whose coordinate columns come first, in this order
+    pub coordinate_order: Vec<String>,
+}
+
+/// Returns `n` distinct indices drawn uniformly from `0..total`, in ascending order.
+///
+/// The selection only depends on `seed`, so the same arguments always produce the
+/// same indices. When `n >= total`, every index is returned.
+pub fn sample_indices(total: usize, n: usize, seed: u64) -> Vec<usize> {
+    if n >= total {
+        return (0..total).collect();
+    }
+
+    // Floyd's algorithm: exactly n draws, no matter how large total is
+    let mut rng = SplitMix64(seed);
+    let mut selected = HashSet::with_capacity(n);
+    for j in (total - n)..total {
+        let candidate = rng.next_below(j + 1);
+        if !selected.insert(candidate) {
+            selected.insert(j);
+        }
+    }
+
+    let mut indices: Vec<usize> = selected.into_iter().collect();
+    indices.sort_unstable();
+    indices
+}
+
+/// Small deterministic generator, so samples are reproducible across platforms
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..bound`
+    fn next_below(&mut self, bound: usize) -> usize {
+        ((self.next_u64() as u128 * bound as u128) >> 64) as usize
+    }
+}
+
+fn random_seed() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Polars type of the extracted data column.
+///
+/// The variable is read in its native NetCDF type so integer data is not forced
+/// through a float conversion. Small integer types are widened to the nearest
+/// supported column type, and packed variables (with `scale_factor` or
+/// `add_offset` attributes) are unpacked into `Float64`. Signed integers flagged
+/// with `_Unsigned = "true"` are typed like the matching unsigned type (see
+/// [`ValueEncoding::unsigned`]). Values marked missing by the variable's
+/// [`ValueEncoding`] become nulls.
+///
+/// `char` variables are read as text, one string per value of their other
+/// dimensions (see [`row_dimensions`]), and enum variables as the name of each
+/// value's member, or null for values outside the member table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataColumnType {
+    Int16,
+    Int32,
+    Int64,
+    Float32,
+    Float64,
+    Utf8,
+}
+
+impl DataColumnType {
+    pub fn for_variable(var: &netcdf::Variable) -> Result<Self, Box<dyn std::error::Error>> {
+        Self::for_encoding(var, &ValueEncoding::for_variable(var, None)?)
+    }
+
+    /// Column type of `var` when its values are decoded with `encoding`
+    pub fn for_encoding(
+        var: &netcdf::Variable,
+        encoding: &ValueEncoding,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        if encoding.packing.is_some() {
+            return Ok(DataColumnType::Float64);
+        }
+
+        let vartype = match var.vartype() {
+            NcVariableType::Int(int_type) if encoding.unsigned => {
+                NcVariableType::Int(unsigned_counterpart(int_type))
+            }
+            vartype => vartype,
+        };
+        match vartype {
+            NcVariableType::Int(IntType::I8 | IntType::U8 | IntType::I16) => {
+                Ok(DataColumnType::Int16)
+            }
+            NcVariableType::Int(IntType::U16 | IntType::I32) => Ok(DataColumnType::Int32),
+            NcVariableType::Int(IntType::U32 | IntType::I64) => Ok(DataColumnType::Int64),
+            // u64 does not fit in any signed column type
+            NcVariableType::Int(IntType::U64) => Ok(DataColumnType::Float64),
+            NcVariableType::Float(FloatType::F32) => Ok(DataColumnType::Float32),
+            NcVariableType::Float(FloatType::F64) => Ok(DataColumnType::Float64),
+            NcVariableType::Char | NcVariableType::Enum(_) => Ok(DataColumnType::Utf8),
+            NcVariableType::Compound(compound_type) => Err(format!(
+                "Variable '{}' has compound type '{}', which cannot be extracted; only numeric, char and enum variables are supported",
+                var.name(),
+                compound_type.name
+            )
+            .into()),
+            other => Err(format!(
+                "Unsupported data type {:?} for variable '{}'",
+                other,
+                var.name()
+            )
+            .into()),
+        }
+    }
+
+    pub fn dtype(&self) -> DataType {
+        match self {
+            DataColumnType::Int16 => DataType::Int16,
+            DataColumnType::Int32 => DataType::Int32,
+            DataColumnType::Int64 => DataType::Int64,
+            DataColumnType::Float32 => DataType::Float32,
+            DataColumnType::Float64 => DataType::Float64,
+            DataColumnType::Utf8 => DataType::String,
+        }
+    }
+}
+
+/// Dimensions of `var` that become coordinate columns: all of them, except the
+/// last dimension of a `char` variable, which holds the characters of each string
+pub fn row_dimensions<'v>(var: &'v netcdf::Variable) -> &'v [netcdf::Dimension<'v>] {
+    let dimensions = var.dimensions();
+    match var.vartype() {
+        NcVariableType::Char if !dimensions.is_empty() => &dimensions[..dimensions.len() - 1],
+        _ => dimensions,
+    }
+}
+
+/// How the stored values of a variable are decoded: CF packing, signedness and
+/// the values that mark missing data.
+///
+/// Built from the variable's `scale_factor`, `add_offset`, `_Unsigned`,
+/// `_FillValue`, `valid_min` and `valid_max` attributes, with any [`VarOverride`]
+/// taking precedence.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ValueEncoding {
+    /// `(scale_factor, add_offset)` of packed variables
+    pub packing: Option<(f64, f64)>,
+    /// Whether the variable is a signed integer flagged `_Unsigned = "true"`,
+    /// the NetCDF classic convention for unsigned data. Its stored values (and
+    /// negative fill and valid-range attributes) are reinterpreted as unsigned,
+    /// so a byte stored as `-1` decodes as `255`.
+    pub unsigned: bool,
+    pub fill_value: Option<f64>,
+    pub valid_min: Option<f64>,
+    pub valid_max: Option<f64>,
+}
+
+impl ValueEncoding {
+    pub fn for_variable(
+        var: &netcdf::Variable,
+        overrides: Option<&VarOverride>,
+    ) -> Result<Self, Box<dyn std::error::Error>> {
+        // Text values are neither packed nor masked
+        if matches!(
+            var.vartype(),
+            NcVariableType::Char | NcVariableType::Enum(_)
+        ) {
+            return Ok(ValueEncoding::default());
+        }
+
+        let overrides = overrides.copied().unwrap_or_default();
+        let attribute = |value: Option<f64>, name: &str| match value {
+            Some(value) => Ok(Some(value)),
+            None => numeric_attribute(var, name),
+        };
+
+        let scale = attribute(overrides.scale_factor, "scale_factor")?;
+        let offset = attribute(overrides.add_offset, "add_offset")?;
+        let packing = if scale.is_none() && offset.is_none() {
+            None
+        } else {
+            Some((scale.unwrap_or(1.0), offset.unwrap_or(0.0)))
+        };
+
+        let unsigned_bits = match var.vartype() {
+            NcVariableType::Int(int_type) if is_flagged_unsigned(var)? => match int_type {
+                IntType::I8 => Some(8),
+                IntType::I16 => Some(16),
+                IntType::I32 => Some(32),
+                IntType::I64 => Some(64),
+                _ => None,
+            },
+            _ => None,
+        };
+        // Attributes share the variable's signed type, so e.g. a `_FillValue` of
+        // -1 on an unsigned byte variable stands for 255
+        let as_unsigned = |value: Option<f64>| match (value, unsigned_bits) {
+            (Some(value), Some(bits)) if value < 0.0 => Some(value + 2f64.powi(bits)),
+            _ => value,
+        };
+
+        Ok(ValueEncoding {
+            packing,
+            unsigned: unsigned_bits.is_some(),
+            fill_value: as_unsigned(attribute(overrides.fill_value, "_FillValue")?),
+            valid_min: as_unsigned(attribute(overrides.valid_min, "valid_min")?),
+            valid_max: as_unsigned(attribute(overrides.valid_max, "valid_max")?),
+        })
+    }
+
+    /// Whether any stored value can be marked missing
+    pub fn has_mask(&self) -> bool {
+        self.fill_value.is_some() || self.valid_min.is_some() || self.valid_max.is_some()
+    }
+
+    /// Whether a stored value is the fill value or lies outside the valid range
+    fn is_missing<T: StoredValue>(&self, value: T) -> bool {
+        self.fill_value.is_some_and(|fill| value.equals(fill))
+            || self.valid_min.is_some_and(|min| value.to_f64() < min)
+            || self.valid_max.is_some_and(|max| value.to_f64() > max)
+    }
+
+    /// Replaces missing values with `None`
+    fn mask<T: StoredValue>(&self, values: Vec<T>) -> Vec<Option<T>> {
+        values
+            .into_iter()
+            .map(|value| (!self.is_missing(value)).then_some(value))
+            .collect()
+    }
+}
+
+/// Reads a numeric scalar attribute of a variable as `f64`
+fn numeric_attribute(
+    var: &netcdf::Variable,
+    name: &str,
+) -> Result<Option<f64>, Box<dyn std::error::Error>> {
+    Ok(var
+        .attribute_value(name)
+        .transpose()?
+        .map(f64::try_from)
+        .transpose()?)
+}
+
+/// Whether `var` has an `_Unsigned` attribute set to `"true"`
+fn is_flagged_unsigned(var: &netcdf::Variable) -> Result<bool, Box<dyn std::error::Error>> {
+    Ok(match var.attribute_value("_Unsigned").transpose()? {
+        Some(value) => String::try_from(value).is_ok_and(|flag| flag.eq_ignore_ascii_case("true")),
+        None => false,
+    })
+}
+
+/// Unsigned type with the same width as `int_type`
+fn unsigned_counterpart(int_type: IntType) -> IntType {
+    match int_type {
+        IntType::I8 => IntType::U8,
+        IntType::I16 => IntType::U16,
+        IntType::I32 => IntType::U32,
+        IntType::I64 => IntType::U64,
+        unsigned => unsigned,
+    }
+}
+
+/// Native value type of a data column, as stored in the NetCDF file
+trait StoredValue: Copy {
+    fn to_f64(self) -> f64;
+
+    /// Converts an unsigned stored value; column types are chosen wide enough to
+    /// hold every value of the stored type
+    fn from_unsigned(value: u64) -> Self;
+
+    /// Compares with an `f64` attribute in the value's own precision, so a fill
+    /// value such as `-999.9` matches the `f32` stored for it
+    fn equals(self, target: f64) -> bool {
+        self.to_f64() == target
+    }
+}
+
+macro_rules! impl_stored_value {
+    ($($t:ty),*) => {
+        $(impl StoredValue for $t {
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+
+            fn from_unsigned(value: u64) -> Self {
+                value as $t
+            }
+        })*
+    };
+}
+
+impl_stored_value!(i16, i32, i64, f64);
+
+impl StoredValue for f32 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn from_unsigned(value: u64) -> Self {
+        value as f32
+    }
+
+    fn equals(self, target: f64) -> bool {
+        self == target as f32
+    }
+}
+
 /// Manages dimension indices and coordinate combinations during filtering operations.
 ///
 /// This struct maintains the state of valid indices for each dimension and handles
@@ -28,7 +580,7 @@ impl DimensionIndexManager {
         let mut dimension_indices = HashMap::new();
         let mut dimension_order = Vec::new();
 
-        for dim in var.dimensions() {
+        for dim in row_dimensions(var) {
             let dim_name = dim.name().to_string();
             let dim_size = dim.len();
 
@@ -207,6 +759,48 @@ impl DimensionIndexManager {
         }
     }
 
+    /// Keeps only the coordinate combinations whose index along `dimension` is at
+    /// least `start`, including combinations chosen explicitly by point filters.
+    pub fn apply_start_record(
+        &mut self,
+        dimension: &str,
+        start: usize,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let position = self
+            .dimension_order
+            .iter()
+            .position(|d| d == dimension)
+            .ok_or(format!("Dimension {} not found", dimension))?;
+
+        if let Some(indices) = self.dimension_indices.get_mut(dimension) {
+            indices.retain(|&idx| idx >= start);
+        }
+        if let Some(ref mut explicit) = self.explicit_combinations {
+            explicit.retain(|combination| combination[position] >= start);
+        }
+        Ok(())
+    }
+
+    /// Keeps only a random sample of the current coordinate combinations.
+    ///
+    /// The sampled combinations stay in their original relative order.
+    pub fn apply_sample(&mut self, sample: &SampleConfig) {
+        let combinations = self.get_all_coordinate_combinations();
+        let seed = sample.seed.unwrap_or_else(random_seed);
+        let selected: HashSet<usize> = sample_indices(combinations.len(), sample.n, seed)
+            .into_iter()
+            .collect();
+
+        self.explicit_combinations = Some(
+            combinations
+                .into_iter()
+                .enumerate()
+                .filter(|(idx, _)| selected.contains(idx))
+                .map(|(_, combination)| combination)
+                .collect(),
+        );
+    }
+
     pub fn get_dimension_indices(&self, dim_name: &str) -> Option<&HashSet<usize>> {
         self.dimension_indices.get(dim_name)
     }
@@ -215,6 +809,18 @@ impl DimensionIndexManager {
         &self.dimension_order
     }
 
+    /// Number of coordinate combinations, i.e. rows, without generating them
+    pub fn combination_count(&self) -> usize {
+        if let Some(ref explicit) = self.explicit_combinations {
+            explicit.len()
+        } else {
+            self.dimension_order
+                .iter()
+                .map(|dim_name| self.dimension_indices.get(dim_name).map_or(0, HashSet::len))
+                .product()
+        }
+    }
+
     pub fn get_all_coordinate_combinations(&self) -> Vec<Vec<usize>> {
         if let Some(ref explicit) = self.explicit_combinations {
             explicit.clone()
@@ -254,7 +860,8 @@ impl DimensionIndexManager {
 ///
 /// This is the main extraction function that:
 /// 1. Creates a dimension index manager for the variable
-/// 2. Applies all filters with intersection logic
+/// 2. Applies all filters with intersection logic (see [`FilterCombine`] for
+///    unions of filters on the same dimension)
 /// 3. Extracts data only for valid coordinate combinations
 /// 4. Returns a DataFrame with coordinate and variable data
 ///
@@ -275,33 +882,490 @@ pub fn extract_data_to_dataframe(
     var_name: &str,
     filters: &Vec<Box<dyn NCFilter>>,
 ) -> Result<DataFrame, Box<dyn std::error::Error>> {
-    let mut dim_manager = DimensionIndexManager::new(var)?;
-    for filter in filters.iter() {
-        let result = filter.apply(file)?;
-        dim_manager.apply_filter_result(&result)?;
+    extract_data_to_dataframe_with_transforms(file, var, var_name, filters, &HashMap::new())
+}
+
+/// Extracts NetCDF data like [`extract_data_to_dataframe`], applying the given
+/// coordinate transforms to the output coordinate columns.
+///
+/// Filters are applied first against the original coordinate values; the
+/// transforms keyed by dimension name are only applied to the values written
+/// to the DataFrame.
+pub fn extract_data_to_dataframe_with_transforms(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    var_name: &str,
+    filters: &Vec<Box<dyn NCFilter>>,
+    coordinate_transforms: &HashMap<String, CoordTransform>,
+) -> Result<DataFrame, Box<dyn std::error::Error>> {
+    let options = ExtractOptions {
+        coordinate_transforms: coordinate_transforms.clone(),
+        ..ExtractOptions::default()
+    };
+    extract_data_to_dataframe_with_progress(file, var, var_name, filters, &options, &mut |_| {})
+}
+
+/// Progress events reported while extracting a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtractionProgress {
+    /// Filtering is done; `total` coordinate combinations will be read
+    Started { total: usize },
+    /// `rows` more coordinate combinations have been read
+    Advanced { rows: usize },
+}
+
+/// Number of values read between two [`ExtractionProgress::Advanced`] events
+const PROGRESS_INTERVAL: usize = 4096;
+
+/// Largest block, as a multiple of the number of values requested, that is loaded
+/// to gather values in parallel. Sparser selections are read value by value.
+const MAX_BLOCK_OVERREAD: usize = 4;
+
+/// Extracts NetCDF data with the given [`ExtractOptions`], reporting progress
+/// through `on_progress`.
+///
+/// `on_progress` receives one [`ExtractionProgress::Started`] event once the
+/// filters have been applied, followed by [`ExtractionProgress::Advanced`] events
+/// whose row counts add up to the announced total.
+///
+/// NetCDF handles are not thread-safe, so values are never read from the file in
+/// parallel. Instead, the smallest block of the variable covering every selected
+/// coordinate combination is read with a single call, and values are gathered from
+/// that in-memory block on the rayon thread pool. When the block would be much
+/// larger than the selection (e.g. a few scattered points), or
+/// [`ExtractOptions::serial`] is set, values are read one at a time instead. Both
+/// paths produce identical DataFrames.
+pub fn extract_data_to_dataframe_with_progress(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    var_name: &str,
+    filters: &Vec<Box<dyn NCFilter>>,
+    options: &ExtractOptions,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Result<DataFrame, Box<dyn std::error::Error>> {
+    let (df, _) = extract_rows(file, var, var_name, filters, options, on_progress)?;
+    Ok(df)
+}
+
+/// Extracts NetCDF data like [`extract_data_to_dataframe_with_progress`], also
+/// returning the [`CellIndex`] that maps each coordinate index tuple to its row.
+pub fn extract_data_to_dataframe_with_cell_index(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    var_name: &str,
+    filters: &Vec<Box<dyn NCFilter>>,
+    options: &ExtractOptions,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Result<(DataFrame, CellIndex), Box<dyn std::error::Error>> {
+    if options.nan_policy == NanPolicy::Drop {
+        return Err("A cell index cannot be built when dropping NaN rows".into());
     }
-    extract_data_with_dimension_manager(file, var, var_name, &dim_manager)
+    let (df, dim_manager) = extract_rows(file, var, var_name, filters, options, on_progress)?;
+    let cell_index = CellIndex::from_combinations(
+        dim_manager.get_dimension_order().to_vec(),
+        &dim_manager.get_all_coordinate_combinations(),
+    )?;
+    Ok((df, cell_index))
 }
 
-fn extract_data_with_dimension_manager(
+/// Extracts the selected rows, also returning the dimension manager that chose them
+fn extract_rows(
     file: &netcdf::File,
     var: &netcdf::Variable,
     var_name: &str,
-    dim_manager: &DimensionIndexManager,
+    filters: &Vec<Box<dyn NCFilter>>,
+    options: &ExtractOptions,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Result<(DataFrame, DimensionIndexManager), Box<dyn std::error::Error>> {
+    let mut coordinate_cache = CoordinateCache::new();
+    validate_coordinate_lengths(file, var, &mut coordinate_cache)?;
+    let dim_manager = build_dimension_manager(file, var, filters, options, &mut coordinate_cache)?;
+    let dimension_order = dim_manager.get_dimension_order();
+    let column_order = coordinate_column_order(dimension_order, options)?;
+    let coordinate_vars = get_coordinate_variables(
+        file,
+        dimension_order,
+        &options.coordinate_transforms,
+        &mut coordinate_cache,
+    )?;
+    let time_references = if options.decode_times {
+        read_time_references(file, dimension_order)?
+    } else {
+        HashMap::new()
+    };
+    let encoding = ValueEncoding::for_variable(var, options.variable_overrides.get(&var.name()))?;
+    let combinations = dim_manager.get_all_coordinate_combinations();
+
+    on_progress(ExtractionProgress::Started {
+        total: combinations.len(),
+    });
+    let mut df = build_dataframe(
+        var,
+        var_name,
+        &encoding,
+        dimension_order,
+        &column_order,
+        &coordinate_vars,
+        &combinations,
+        options.serial,
+        &mut |rows| on_progress(ExtractionProgress::Advanced { rows }),
+    )?;
+    if options.include_indices {
+        insert_index_columns(&mut df, dimension_order, &column_order, &combinations)?;
+    }
+    let df = apply_nan_policy(df, var_name, options.nan_policy)?;
+    let df = decode_time_columns(df, &time_references)?;
+    Ok((
+        rename_coordinate_columns(df, dimension_order, &options.coordinate_renames)?,
+        dim_manager,
+    ))
+}
+
+/// Renames the coordinate columns (and their index columns, if present) of `df`
+/// as set by `renames`, keyed by dimension name
+fn rename_coordinate_columns(
+    mut df: DataFrame,
+    dimension_order: &[String],
+    renames: &HashMap<String, String>,
+) -> Result<DataFrame, Box<dyn std::error::Error>> {
+    for (dim_name, new_name) in renames {
+        if !dimension_order.contains(dim_name) {
+            return Err(format!(
+                "Coordinate rename references unknown dimension '{}'",
+                dim_name
+            )
+            .into());
+        }
+        df.rename(dim_name, new_name.as_str().into())?;
+        let index_name = index_column_name(dim_name);
+        if df.get_column_index(&index_name).is_some() {
+            df.rename(&index_name, index_column_name(new_name).into())?;
+        }
+    }
+    Ok(df)
+}
+
+/// Drops or rejects the rows whose `var_name` value is NaN, as set by `policy`
+fn apply_nan_policy(
+    df: DataFrame,
+    var_name: &str,
+    policy: NanPolicy,
 ) -> Result<DataFrame, Box<dyn std::error::Error>> {
+    let column = df.column(var_name)?;
+    // Only float columns can hold NaN
+    if policy.is_keep() || !column.dtype().is_float() {
+        return Ok(df);
+    }
+
+    let is_nan = column
+        .as_materialized_series()
+        .is_nan()?
+        .fill_null_with_values(false)?;
+    let nan_count = is_nan.num_trues();
+    if nan_count == 0 {
+        return Ok(df);
+    }
+
+    match policy {
+        NanPolicy::Keep => Ok(df),
+        NanPolicy::Drop => Ok(df.filter(&!is_nan)?),
+        NanPolicy::Error => Err(format!(
+            "Variable '{}' has {} NaN value(s) in the selected rows (nan_policy is \"error\")",
+            var_name, nan_count
+        )
+        .into()),
+    }
+}
+
+/// Extracts NetCDF data in chunks of at most `chunk_size` rows.
+///
+/// Filters are applied exactly as in [`extract_data_to_dataframe`], but instead
+/// of building one DataFrame the matching coordinate combinations are split into
+/// consecutive batches. The variable is read batch by batch and each resulting
+/// DataFrame is handed to `on_chunk`, so only one chunk is held in memory at a time.
+/// Concatenating all chunks yields the same rows, in the same order, as the
+/// non-chunked extraction.
+///
+/// `on_chunk` is called at least once: when no rows match, it receives a single
+/// empty DataFrame carrying the output schema.
+///
+/// # Returns
+///
+/// Returns the total number of rows extracted, or an error if `chunk_size` is
+/// zero, extraction fails, or `on_chunk` returns an error.
+pub fn extract_data_to_dataframe_chunked<F>(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    var_name: &str,
+    filters: &Vec<Box<dyn NCFilter>>,
+    options: &ExtractOptions,
+    chunk_size: usize,
+    mut on_chunk: F,
+) -> Result<usize, Box<dyn std::error::Error>>
+where
+    F: FnMut(DataFrame) -> Result<(), Box<dyn std::error::Error>>,
+{
+    if chunk_size == 0 {
+        return Err("Chunk size must be at least 1".into());
+    }
+
+    let mut coordinate_cache = CoordinateCache::new();
+    validate_coordinate_lengths(file, var, &mut coordinate_cache)?;
+    let dim_manager = build_dimension_manager(file, var, filters, options, &mut coordinate_cache)?;
     let dimension_order = dim_manager.get_dimension_order();
-    let coordinate_vars: HashMap<String, Vec<f64>> =
-        get_coordinate_variables(file, dimension_order)?;
+    let column_order = coordinate_column_order(dimension_order, options)?;
+    let coordinate_vars = get_coordinate_variables(
+        file,
+        dimension_order,
+        &options.coordinate_transforms,
+        &mut coordinate_cache,
+    )?;
+    let time_references = if options.decode_times {
+        read_time_references(file, dimension_order)?
+    } else {
+        HashMap::new()
+    };
+    let encoding = ValueEncoding::for_variable(var, options.variable_overrides.get(&var.name()))?;
     let combinations = dim_manager.get_all_coordinate_combinations();
 
+    if combinations.is_empty() {
+        let mut df = build_dataframe(
+            var,
+            var_name,
+            &encoding,
+            dimension_order,
+            &column_order,
+            &coordinate_vars,
+            &[],
+            options.serial,
+            &mut |_| {},
+        )?;
+        if options.include_indices {
+            insert_index_columns(&mut df, dimension_order, &column_order, &[])?;
+        }
+        let df = decode_time_columns(df, &time_references)?;
+        on_chunk(rename_coordinate_columns(
+            df,
+            dimension_order,
+            &options.coordinate_renames,
+        )?)?;
+        return Ok(0);
+    }
+
+    let mut rows = 0;
+    for batch in combinations.chunks(chunk_size) {
+        let mut df = build_dataframe(
+            var,
+            var_name,
+            &encoding,
+            dimension_order,
+            &column_order,
+            &coordinate_vars,
+            batch,
+            options.serial,
+            &mut |_| {},
+        )?;
+        if options.include_indices {
+            insert_index_columns(&mut df, dimension_order, &column_order, batch)?;
+        }
+        let df = apply_nan_policy(df, var_name, options.nan_policy)?;
+        rows += df.height();
+        let df = decode_time_columns(df, &time_references)?;
+        on_chunk(rename_coordinate_columns(
+            df,
+            dimension_order,
+            &options.coordinate_renames,
+        )?)?;
+    }
+
+    Ok(rows)
+}
+
+/// Returns the schema of the DataFrame produced by extracting `var`.
+///
+/// Every row dimension (see [`row_dimensions`]) becomes a `Float64` coordinate
+/// column, in dimension order,
+/// followed by the variable itself as a column named `var_name` whose type is
+/// given by [`DataColumnType::for_encoding`], taking `overrides` into account.
+pub fn extraction_schema(
+    var: &netcdf::Variable,
+    var_name: &str,
+    overrides: Option<&VarOverride>,
+) -> Result<Schema, Box<dyn std::error::Error>> {
+    let mut fields: Vec<Field> = row_dimensions(var)
+        .iter()
+        .map(|dim| Field::new(dim.name().into(), DataType::Float64))
+        .collect();
+    fields.push(Field::new(
+        var_name.into(),
+        DataColumnType::for_encoding(var, &ValueEncoding::for_variable(var, overrides)?)?.dtype(),
+    ));
+    Ok(Schema::from_iter(fields))
+}
+
+/// Estimated memory of an extraction, before any data is read (see [`estimate_memory`]).
+///
+/// Extraction holds every coordinate combination for the whole run, plus the
+/// columns of the rows being built: all of them at once, or one chunk at a time
+/// when streaming. Post-processing and writing may need more, so the estimate is
+/// a lower bound of the peak rather than an exact figure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MemoryEstimate {
+    /// Rows selected by the filters
+    pub rows: usize,
+    /// Bytes of one row across the coordinate, index and value columns
+    pub row_bytes: usize,
+    /// Bytes of the coordinate combinations, held for the whole extraction
+    pub combination_bytes: u64,
+}
+
+impl MemoryEstimate {
+    /// Estimated peak when the whole DataFrame is built at once
+    pub fn peak_bytes(&self) -> u64 {
+        self.chunked_peak_bytes(self.rows)
+    }
+
+    /// Estimated peak when extracting `chunk_size` rows at a time
+    pub fn chunked_peak_bytes(&self, chunk_size: usize) -> u64 {
+        self.combination_bytes + (chunk_size.min(self.rows) as u64) * self.row_bytes as u64
+    }
+
+    /// Largest chunk size whose estimated peak stays within `limit` bytes, or
+    /// `None` when even the coordinate combinations and a single row exceed it
+    pub fn chunk_size_within(&self, limit: u64) -> Option<usize> {
+        let available = limit.checked_sub(self.combination_bytes)?;
+        let rows = available / self.row_bytes.max(1) as u64;
+        (rows > 0).then(|| rows.min(self.rows.max(1) as u64) as usize)
+    }
+}
+
+/// Estimates the memory of extracting `var` with `filters` and `options`.
+///
+/// Only the filters are applied, which reads coordinate variables but no values.
+/// Coordinate and index columns take 8 bytes per row each; the value column takes
+/// the size of its [`DataColumnType`], and text columns an approximation of
+/// their string storage.
+pub fn estimate_memory(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    filters: &Vec<Box<dyn NCFilter>>,
+    options: &ExtractOptions,
+) -> Result<MemoryEstimate, Box<dyn std::error::Error>> {
+    let mut coordinate_cache = CoordinateCache::new();
+    let dim_manager = build_dimension_manager(file, var, filters, options, &mut coordinate_cache)?;
+    let rows = dim_manager.combination_count();
+    let dimensions = dim_manager.get_dimension_order().len();
+
+    let encoding = ValueEncoding::for_variable(var, options.variable_overrides.get(&var.name()))?;
+    let value_bytes = match DataColumnType::for_encoding(var, &encoding)? {
+        DataColumnType::Int16 => 2,
+        DataColumnType::Int32 | DataColumnType::Float32 => 4,
+        DataColumnType::Int64 | DataColumnType::Float64 => 8,
+        // A string view, plus the characters of strings too long to be inlined
+        DataColumnType::Utf8 => {
+            16 + match var.vartype() {
+                NcVariableType::Char => var.dimensions().last().map_or(0, |dim| dim.len()),
+                _ => 16,
+            }
+        }
+    };
+    let coordinate_columns = if options.include_indices {
+        2 * dimensions
+    } else {
+        dimensions
+    };
+
+    Ok(MemoryEstimate {
+        rows,
+        row_bytes: 8 * coordinate_columns + value_bytes,
+        combination_bytes: rows as u64
+            * (std::mem::size_of::<Vec<usize>>() + dimensions * std::mem::size_of::<usize>())
+                as u64,
+    })
+}
+
+/// Checks that every coordinate variable of `var` has one value per index of its
+/// dimension.
+///
+/// In a malformed file, a coordinate variable named after a dimension may be
+/// defined over a different dimension; its values would then be misaligned with
+/// (or index past the end of) the data, so extraction is refused instead.
+fn validate_coordinate_lengths(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    coordinate_cache: &mut CoordinateCache,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for dim in row_dimensions(var) {
+        let dim_name = dim.name();
+        if let Ok(Some(coords)) = coordinate_cache.get(file, &dim_name)
+            && coords.len() != dim.len()
+        {
+            return Err(format!(
+                "Coordinate variable '{}' has {} values but dimension '{}' of variable '{}' has length {}",
+                dim_name,
+                coords.len(),
+                dim_name,
+                var.name(),
+                dim.len()
+            )
+            .into());
+        }
+    }
+    Ok(())
+}
+
+fn build_dimension_manager(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    filters: &Vec<Box<dyn NCFilter>>,
+    options: &ExtractOptions,
+    coordinate_cache: &mut CoordinateCache,
+) -> Result<DimensionIndexManager, Box<dyn std::error::Error>> {
+    let mut dim_manager = DimensionIndexManager::new(var)?;
+    let mut results = apply_filters(file, filters, coordinate_cache)?;
+    if options.combine == FilterCombine::Or {
+        results = union_single_results(results);
+    }
+    for result in &results {
+        dim_manager.apply_filter_result(result)?;
+    }
+    if let Some(start_record) = options.start_record {
+        let record_dim = var
+            .dimensions()
+            .iter()
+            .find(|dim| dim.is_unlimited())
+            .ok_or(format!(
+                "A start record was given, but variable '{}' has no unlimited dimension",
+                var.name()
+            ))?;
+        dim_manager.apply_start_record(&record_dim.name(), start_record)?;
+    }
+    if let Some(ref sample) = options.sample {
+        dim_manager.apply_sample(sample);
+    }
+    Ok(dim_manager)
+}
+
+/// Builds the coordinate columns, in `column_order` (see
+/// [`coordinate_column_order`]), followed by the variable's column
+fn build_dataframe(
+    var: &netcdf::Variable,
+    var_name: &str,
+    encoding: &ValueEncoding,
+    dimension_order: &[String],
+    column_order: &[usize],
+    coordinate_vars: &HashMap<String, Vec<f64>>,
+    combinations: &[Vec<usize>],
+    serial: bool,
+    on_rows_read: &mut dyn FnMut(usize),
+) -> Result<DataFrame, Box<dyn std::error::Error>> {
     let mut data_columns: HashMap<String, Vec<f64>> = HashMap::new();
-    let mut variable_values = Vec::new();
 
     for dim_name in dimension_order {
-        data_columns.insert(dim_name.clone(), Vec::new());
+        data_columns.insert(dim_name.clone(), Vec::with_capacity(combinations.len()));
     }
 
-    for combination in &combinations {
+    for combination in combinations {
         for (i, dim_name) in dimension_order.iter().enumerate() {
             let idx = combination[i];
 
@@ -311,63 +1375,555 @@ fn extract_data_with_dimension_manager(
                 .unwrap_or(idx as f64);
             data_columns.get_mut(dim_name).unwrap().push(coord_value);
         }
-
-        let indices: Vec<usize> = combination.clone();
-        let value = extract_variable_value(var, &indices)?;
-        variable_values.push(value);
     }
 
     let mut columns = Vec::new();
 
-    for dim_name in dimension_order {
+    for &position in column_order {
+        let dim_name = &dimension_order[position];
         let values = data_columns.remove(dim_name).unwrap();
         columns.push(Series::new(dim_name.as_str().into(), values).into());
     }
 
-    columns.push(Series::new(var_name.into(), variable_values).into());
+    columns.push(
+        read_data_column(var, var_name, encoding, combinations, serial, on_rows_read)?.into(),
+    );
 
     let df = DataFrame::new(columns)?;
     Ok(df)
 }
 
+/// Name of the column holding each row's index along `dimension` (see
+/// [`ExtractOptions::include_indices`]), e.g. `time_idx`
+pub fn index_column_name(dimension: &str) -> String {
+    format!("{}_idx", dimension)
+}
+
+/// Positions in `dimension_order` of the coordinate columns, in output order.
+///
+/// The dimensions of [`ExtractOptions::coordinate_order`] come first, in that
+/// order, followed by the others in dimension order, or sorted by name when
+/// [`ExtractOptions::sort_coordinates`] is set. Output schemas then stay the same
+/// across files that store the same dimensions in a different order.
+pub fn coordinate_column_order(
+    dimension_order: &[String],
+    options: &ExtractOptions,
+) -> Result<Vec<usize>, Box<dyn std::error::Error>> {
+    let mut order = Vec::with_capacity(dimension_order.len());
+    for dim_name in &options.coordinate_order {
+        let position = dimension_order
+            .iter()
+            .position(|other| other == dim_name)
+            .ok_or_else(|| {
+                format!(
+                    "Coordinate order references unknown dimension '{}'",
+                    dim_name
+                )
+            })?;
+        if order.contains(&position) {
+            return Err(format!("Coordinate order lists dimension '{}' twice", dim_name).into());
+        }
+        order.push(position);
+    }
+
+    let mut rest: Vec<usize> = (0..dimension_order.len())
+        .filter(|position| !order.contains(position))
+        .collect();
+    if options.sort_coordinates {
+        rest.sort_by_key(|&position| &dimension_order[position]);
+    }
+    order.extend(rest);
+    Ok(order)
+}
+
+/// Inserts one `UInt64` index column per dimension right after the coordinate
+/// columns, in `column_order`, holding the coordinate combination each row was
+/// read from
+fn insert_index_columns(
+    df: &mut DataFrame,
+    dimension_order: &[String],
+    column_order: &[usize],
+    combinations: &[Vec<usize>],
+) -> Result<(), Box<dyn std::error::Error>> {
+    for (i, &position) in column_order.iter().enumerate() {
+        let indices: Vec<u64> = combinations
+            .iter()
+            .map(|combination| combination[position] as u64)
+            .collect();
+        df.insert_column(
+            dimension_order.len() + i,
+            Series::new(
+                index_column_name(&dimension_order[position]).into(),
+                indices,
+            ),
+        )?;
+    }
+    Ok(())
+}
+
+/// Reads the variable at each coordinate combination into a natively typed Series,
+/// decoded with `encoding`
+fn read_data_column(
+    var: &netcdf::Variable,
+    var_name: &str,
+    encoding: &ValueEncoding,
+    combinations: &[Vec<usize>],
+    serial: bool,
+    on_rows_read: &mut dyn FnMut(usize),
+) -> Result<Series, Box<dyn std::error::Error>> {
+    let name = var_name.into();
+    let series = match DataColumnType::for_encoding(var, encoding)? {
+        DataColumnType::Int16 => masked_series(
+            name,
+            read_column_values::<i16>(var, encoding, combinations, serial, on_rows_read)?,
+            encoding,
+        ),
+        DataColumnType::Int32 => masked_series(
+            name,
+            read_column_values::<i32>(var, encoding, combinations, serial, on_rows_read)?,
+            encoding,
+        ),
+        DataColumnType::Int64 => masked_series(
+            name,
+            read_column_values::<i64>(var, encoding, combinations, serial, on_rows_read)?,
+            encoding,
+        ),
+        DataColumnType::Float32 => masked_series(
+            name,
+            read_column_values::<f32>(var, encoding, combinations, serial, on_rows_read)?,
+            encoding,
+        ),
+        DataColumnType::Float64 => {
+            let values =
+                read_column_values::<f64>(var, encoding, combinations, serial, on_rows_read)?;
+            let Some((scale, offset)) = encoding.packing else {
+                return Ok(masked_series(name, values, encoding));
+            };
+            // Missing values are detected on the packed values, then the rest unpacked
+            let unpack = |value: f64| value * scale + offset;
+            if encoding.has_mask() {
+                let values: Vec<Option<f64>> = encoding
+                    .mask(values)
+                    .into_iter()
+                    .map(|value| value.map(unpack))
+                    .collect();
+                Series::new(name, values)
+            } else {
+                Series::new(name, values.into_iter().map(unpack).collect::<Vec<_>>())
+            }
+        }
+        DataColumnType::Utf8 => Series::new(
+            name,
+            read_text_values(var, combinations, serial, on_rows_read)?,
+        ),
+    };
+    Ok(series)
+}
+
+/// A NetCDF `char`, read as its byte
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+struct NcChar(u8);
+
+// SAFETY: `NcChar` has the size and layout of a NetCDF `char`
+unsafe impl NcTypeDescriptor for NcChar {
+    fn type_descriptor() -> NcVariableType {
+        NcVariableType::Char
+    }
+}
+
+/// Reads the values of a `char` or enum variable at each coordinate combination
+/// as text (see [`DataColumnType::Utf8`])
+fn read_text_values(
+    var: &netcdf::Variable,
+    combinations: &[Vec<usize>],
+    serial: bool,
+    on_rows_read: &mut dyn FnMut(usize),
+) -> Result<Vec<Option<String>>, Box<dyn std::error::Error>> {
+    let values = match var.vartype() {
+        NcVariableType::Char => read_char_strings(var, combinations, serial)?
+            .into_iter()
+            .map(Some)
+            .collect(),
+        NcVariableType::Enum(enum_type) => read_enum_names(var, &enum_type, combinations, serial)?,
+        other => {
+            return Err(format!(
+                "Variable '{}' of type {:?} cannot be read as text",
+                var.name(),
+                other
+            )
+            .into());
+        }
+    };
+    on_rows_read(combinations.len());
+    Ok(values)
+}
+
+/// Reads the string at each combination of the row dimensions of a `char`
+/// variable, joining the characters along its last dimension up to the first NUL
+fn read_char_strings(
+    var: &netcdf::Variable,
+    combinations: &[Vec<usize>],
+    serial: bool,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let string_length = var.dimensions().last().map_or(0, |dim| dim.len());
+    if string_length == 0 {
+        return Ok(vec![String::new(); combinations.len()]);
+    }
+
+    let char_combinations: Vec<Vec<usize>> = combinations
+        .iter()
+        .flat_map(|combination| {
+            (0..string_length).map(move |i| {
+                let mut char_combination = combination.clone();
+                char_combination.push(i);
+                char_combination
+            })
+        })
+        .collect();
+    let chars = read_values::<NcChar>(var, &char_combinations, serial, &mut |_| {})?;
+    Ok(chars
+        .chunks(string_length)
+        .map(|chars| {
+            let bytes: Vec<u8> = chars
+                .iter()
+                .map(|c| c.0)
+                .take_while(|&byte| byte != 0)
+                .collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        })
+        .collect())
+}
+
+/// Reads the name of the member of each enum value, or `None` for values that
+/// are not in the member table (such as an unset fill value)
+fn read_enum_names(
+    var: &netcdf::Variable,
+    enum_type: &EnumType,
+    combinations: &[Vec<usize>],
+    serial: bool,
+) -> Result<Vec<Option<String>>, Box<dyn std::error::Error>> {
+    let names: HashMap<i64, &str> = enum_codes(&enum_type.fieldvalues)
+        .into_iter()
+        .zip(enum_type.fieldnames.iter().map(String::as_str))
+        .collect();
+    let width = enum_width(&enum_type.fieldvalues);
+    let bytes = read_raw_values(var, combinations, width, serial)?;
+    Ok(bytes
+        .chunks(width)
+        .map(|value| {
+            let code = decode_enum_code(value, &enum_type.fieldvalues);
+            names.get(&code).map(|name| name.to_string())
+        })
+        .collect())
+}
+
+/// Size in bytes of the values of an enum type
+fn enum_width(values: &EnumTypeValues) -> usize {
+    match values {
+        EnumTypeValues::U8(_) | EnumTypeValues::I8(_) => 1,
+        EnumTypeValues::U16(_) | EnumTypeValues::I16(_) => 2,
+        EnumTypeValues::U32(_) | EnumTypeValues::I32(_) => 4,
+        EnumTypeValues::U64(_) | EnumTypeValues::I64(_) => 8,
+    }
+}
+
+/// Values of the members of an enum type, as `i64` (`u64` values are reinterpreted)
+fn enum_codes(values: &EnumTypeValues) -> Vec<i64> {
+    match values {
+        EnumTypeValues::U8(values) => values.iter().map(|&v| v as i64).collect(),
+        EnumTypeValues::U16(values) => values.iter().map(|&v| v as i64).collect(),
+        EnumTypeValues::U32(values) => values.iter().map(|&v| v as i64).collect(),
+        EnumTypeValues::U64(values) => values.iter().map(|&v| v as i64).collect(),
+        EnumTypeValues::I8(values) => values.iter().map(|&v| v as i64).collect(),
+        EnumTypeValues::I16(values) => values.iter().map(|&v| v as i64).collect(),
+        EnumTypeValues::I32(values) => values.iter().map(|&v| v as i64).collect(),
+        EnumTypeValues::I64(values) => values.clone(),
+    }
+}
+
+/// Decodes one enum value stored in native byte order, like [`enum_codes`]
+fn decode_enum_code(bytes: &[u8], values: &EnumTypeValues) -> i64 {
+    // `bytes` holds exactly one value, as read by `read_raw_values`
+    match values {
+        EnumTypeValues::U8(_) => bytes[0] as i64,
+        EnumTypeValues::I8(_) => bytes[0] as i8 as i64,
+        EnumTypeValues::U16(_) => u16::from_ne_bytes(bytes.try_into().unwrap()) as i64,
+        EnumTypeValues::I16(_) => i16::from_ne_bytes(bytes.try_into().unwrap()) as i64,
+        EnumTypeValues::U32(_) => u32::from_ne_bytes(bytes.try_into().unwrap()) as i64,
+        EnumTypeValues::I32(_) => i32::from_ne_bytes(bytes.try_into().unwrap()) as i64,
+        EnumTypeValues::U64(_) => u64::from_ne_bytes(bytes.try_into().unwrap()) as i64,
+        EnumTypeValues::I64(_) => i64::from_ne_bytes(bytes.try_into().unwrap()),
+    }
+}
+
+/// Reads the `width` bytes of the value at each coordinate combination, without
+/// conversion, for types the netcdf crate cannot read directly. Like
+/// [`read_values`], dense selections are read as one block.
+fn read_raw_values(
+    var: &netcdf::Variable,
+    combinations: &[Vec<usize>],
+    width: usize,
+    serial: bool,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut values = Vec::with_capacity(combinations.len() * width);
+    if !serial && let Some((start, count)) = bounding_block(combinations) {
+        let block = var.get_raw_values((start.as_slice(), count.as_slice()))?;
+        let strides = row_major_strides(&count);
+        for combination in combinations {
+            let offset = block_offset(combination, &start, &strides) * width;
+            values.extend_from_slice(&block[offset..offset + width]);
+        }
+        return Ok(values);
+    }
+
+    for combination in combinations {
+        let count = vec![1; combination.len()];
+        values.extend(var.get_raw_values((combination.as_slice(), count.as_slice()))?);
+    }
+    Ok(values)
+}
+
+/// Builds a Series from stored values, with nulls in place of missing values
+fn masked_series<T: StoredValue>(
+    name: PlSmallStr,
+    values: Vec<T>,
+    encoding: &ValueEncoding,
+) -> Series
+where
+    Series: NamedFrom<Vec<T>, [T]> + NamedFrom<Vec<Option<T>>, [Option<T>]>,
+{
+    if encoding.has_mask() {
+        Series::new(name, encoding.mask(values))
+    } else {
+        Series::new(name, values)
+    }
+}
+
+/// Reads the values at each coordinate combination as `T`, reinterpreting the
+/// signed values of `_Unsigned` variables (see [`ValueEncoding::unsigned`])
+fn read_column_values<T: StoredValue + NcTypeDescriptor + Send + Sync>(
+    var: &netcdf::Variable,
+    encoding: &ValueEncoding,
+    combinations: &[Vec<usize>],
+    serial: bool,
+    on_rows_read: &mut dyn FnMut(usize),
+) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    if !encoding.unsigned {
+        return read_values::<T>(var, combinations, serial, on_rows_read);
+    }
+
+    // Read in the stored width so no conversion happens before reinterpreting
+    let values: Vec<u64> = match var.vartype() {
+        NcVariableType::Int(IntType::I8) => {
+            read_values::<i8>(var, combinations, serial, on_rows_read)?
+                .into_iter()
+                .map(|value| value as u8 as u64)
+                .collect()
+        }
+        NcVariableType::Int(IntType::I16) => {
+            read_values::<i16>(var, combinations, serial, on_rows_read)?
+                .into_iter()
+                .map(|value| value as u16 as u64)
+                .collect()
+        }
+        NcVariableType::Int(IntType::I32) => {
+            read_values::<i32>(var, combinations, serial, on_rows_read)?
+                .into_iter()
+                .map(|value| value as u32 as u64)
+                .collect()
+        }
+        NcVariableType::Int(IntType::I64) => {
+            read_values::<i64>(var, combinations, serial, on_rows_read)?
+                .into_iter()
+                .map(|value| value as u64)
+                .collect()
+        }
+        other => {
+            return Err(format!(
+                "Variable '{}' of type {:?} cannot be read as unsigned",
+                var.name(),
+                other
+            )
+            .into());
+        }
+    };
+    Ok(values.into_iter().map(T::from_unsigned).collect())
+}
+
+fn read_values<T: NcTypeDescriptor + Copy + Send + Sync>(
+    var: &netcdf::Variable,
+    combinations: &[Vec<usize>],
+    serial: bool,
+    on_rows_read: &mut dyn FnMut(usize),
+) -> Result<Vec<T>, Box<dyn std::error::Error>> {
+    let mut values = Vec::with_capacity(combinations.len());
+
+    let block = if serial {
+        None
+    } else {
+        ValueBlock::<T>::load(var, combinations)?
+    };
+    if let Some(block) = block {
+        for batch in combinations.chunks(PROGRESS_INTERVAL) {
+            values.par_extend(
+                batch
+                    .par_iter()
+                    .map(|combination| block.value_at(combination)),
+            );
+            on_rows_read(batch.len());
+        }
+        return Ok(values);
+    }
+
+    for batch in combinations.chunks(PROGRESS_INTERVAL) {
+        for combination in batch {
+            values.push(extract_variable_value::<T>(var, combination)?);
+        }
+        on_rows_read(batch.len());
+    }
+    Ok(values)
+}
+
+/// In-memory block of a variable covering a set of coordinate combinations.
+///
+/// Values are stored in row-major order, as returned by NetCDF, so the value at a
+/// combination is found with plain index math and can be looked up from any thread.
+struct ValueBlock<T> {
+    start: Vec<usize>,
+    strides: Vec<usize>,
+    values: Vec<T>,
+}
+
+impl<T: NcTypeDescriptor + Copy> ValueBlock<T> {
+    /// Reads the bounding block of `combinations`, or returns `None` when there is
+    /// nothing to gather or the block is too sparse to be worth loading.
+    fn load(
+        var: &netcdf::Variable,
+        combinations: &[Vec<usize>],
+    ) -> Result<Option<Self>, Box<dyn std::error::Error>> {
+        let Some((start, count)) = bounding_block(combinations) else {
+            return Ok(None);
+        };
+        let strides = row_major_strides(&count);
+
+        let values = var.get_values::<T, _>((start.as_slice(), count.as_slice()))?;
+        Ok(Some(ValueBlock {
+            start,
+            strides,
+            values,
+        }))
+    }
+
+    fn value_at(&self, combination: &[usize]) -> T {
+        self.values[block_offset(combination, &self.start, &self.strides)]
+    }
+}
+
+/// Start and count of the bounding block of `combinations`, or `None` when there
+/// is nothing to gather or the block is too sparse to be worth loading
+fn bounding_block(combinations: &[Vec<usize>]) -> Option<(Vec<usize>, Vec<usize>)> {
+    let first = combinations.first().filter(|first| !first.is_empty())?;
+
+    let mut start = first.clone();
+    let mut end = first.clone();
+    for combination in combinations {
+        for (dim, &idx) in combination.iter().enumerate() {
+            start[dim] = start[dim].min(idx);
+            end[dim] = end[dim].max(idx);
+        }
+    }
+
+    let count: Vec<usize> = start.iter().zip(&end).map(|(s, e)| e - s + 1).collect();
+    let block_len = count
+        .iter()
+        .try_fold(1usize, |len, &dim_len| len.checked_mul(dim_len));
+    match block_len {
+        Some(len) if len <= combinations.len().saturating_mul(MAX_BLOCK_OVERREAD) => {
+            Some((start, count))
+        }
+        _ => None,
+    }
+}
+
+/// Strides of a row-major block of shape `count`
+fn row_major_strides(count: &[usize]) -> Vec<usize> {
+    let mut strides = vec![1; count.len()];
+    for dim in (0..count.len().saturating_sub(1)).rev() {
+        strides[dim] = strides[dim + 1] * count[dim + 1];
+    }
+    strides
+}
+
+/// Position of `combination` in a row-major block starting at `start`
+fn block_offset(combination: &[usize], start: &[usize], strides: &[usize]) -> usize {
+    combination
+        .iter()
+        .zip(start)
+        .zip(strides)
+        .map(|((&idx, &start), &stride)| (idx - start) * stride)
+        .sum()
+}
+
 fn get_coordinate_variables(
     file: &netcdf::File,
     dimension_order: &[String],
+    coordinate_transforms: &HashMap<String, CoordTransform>,
+    coordinate_cache: &mut CoordinateCache,
 ) -> Result<HashMap<String, Vec<f64>>, Box<dyn std::error::Error>> {
     let mut coordinate_vars = HashMap::new();
 
+    // Coordinates already read by the filters come from the cache
     for dim_name in dimension_order {
-        if let Some(coord_var) = file.variable(dim_name)
-            && let Ok(coords_array) = coord_var.get::<f64, _>(..)
-        {
-            let coords_vec: Vec<f64> = coords_array.iter().cloned().collect();
-            coordinate_vars.insert(dim_name.clone(), coords_vec);
+        if let Ok(Some(coords)) = coordinate_cache.get(file, dim_name) {
+            coordinate_vars.insert(dim_name.clone(), coords.to_vec());
+        }
+    }
+
+    for dim_name in coordinate_transforms.keys() {
+        if !dimension_order.contains(dim_name) {
+            return Err(format!(
+                "Coordinate transform references unknown dimension '{}'",
+                dim_name
+            )
+            .into());
+        }
+    }
+
+    // Dimensions without a coordinate variable use their indices as coordinates,
+    // so materialize those before transforming.
+    for (dim_name, transform) in coordinate_transforms {
+        let coords = coordinate_vars.entry(dim_name.clone()).or_insert_with(|| {
+            let len = file.dimension(dim_name).map(|d| d.len()).unwrap_or(0);
+            (0..len).map(|idx| idx as f64).collect()
+        });
+        for value in coords.iter_mut() {
+            *value = transform.apply(*value);
         }
     }
 
     Ok(coordinate_vars)
 }
 
-fn extract_variable_value(
+fn extract_variable_value<T: NcTypeDescriptor + Copy>(
     var: &netcdf::Variable,
     indices: &[usize],
-) -> Result<f32, Box<dyn std::error::Error>> {
+) -> Result<T, Box<dyn std::error::Error>> {
     match indices.len() {
         1 => {
-            let value_array = var.get::<f32, _>(indices[0])?;
+            let value_array = var.get::<T, _>(indices[0])?;
             Ok(value_array[[]])
         }
         2 => {
-            let value_array = var.get::<f32, _>((indices[0], indices[1]))?;
+            let value_array = var.get::<T, _>((indices[0], indices[1]))?;
             Ok(value_array[[]])
         }
         3 => {
-            let value_array = var.get::<f32, _>((indices[0], indices[1], indices[2]))?;
+            let value_array = var.get::<T, _>((indices[0], indices[1], indices[2]))?;
             Ok(value_array[[]])
         }
         4 => {
-            let value_array =
-                var.get::<f32, _>((indices[0], indices[1], indices[2], indices[3]))?;
+            let value_array = var.get::<T, _>((indices[0], indices[1], indices[2], indices[3]))?;
             Ok(value_array[[]])
         }
         _ => Err(format!("Unsupported number of dimensions: {}", indices.len()).into()),
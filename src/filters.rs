@@ -6,13 +6,46 @@
 //!
 //! - **Range filters**: Filter dimension values within a numeric range
 //! - **List filters**: Filter dimension values that match specific values
-//! - **2D Point filters**: Filter spatial coordinates (lat/lon) within tolerance
-//! - **3D Point filters**: Filter spatio-temporal coordinates (time/lat/lon) within tolerance
+//! - **2D Point filters**: Filter spatial coordinates (lat/lon) within tolerance, on regular or curvilinear grids
+//! - **3D Point filters**: Filter spatio-temporal coordinates (time/lat/lon) within tolerance,
+//!   selecting time steps by raw value or by ISO 8601 date
+//! - **3D bounding box filters**: Filter the (time, lat, lon) cells inside a time window
+//!   (raw values or ISO 8601 dates) and a latitude/longitude box
+//! - **Stride filters**: Select every Nth index along a dimension
+//! - **Time range filters**: Select the steps of a CF time axis between two ISO 8601 times
+//! - **Nearest time filters**: Select the N steps of a time axis closest to a target
+//!   value or ISO 8601 time
+//!
+//! ## Range Bounds
+//!
+//! Range filters include both bounds by default. Either bound can be made
+//! exclusive (`min_inclusive` / `max_inclusive`), so adjacent half-open bands
+//! such as `[30, 45)` and `[45, 60)` select a boundary value only once.
+//!
+//! ## Longitude Conventions
+//!
+//! Files store longitudes either in -180..180 or in 0..360. Range and point filters
+//! accept a `normalize_longitude` flag that maps both the coordinate values and the
+//! requested bounds or points to -180..180 (see [`normalize_longitude`]) before
+//! comparing them. It only applies to the dimension designated as longitude: the
+//! range filter's dimension, or `lon_dimension_name` for point filters.
 //!
 //! ## Filter Results
 //!
 //! All filters return a [`FilterResult`] enum that preserves dimension information
 //! and coordinate relationships for proper intersection logic.
+//!
+//! ## Grid Spacing
+//!
+//! [`median_spacing`] and [`axis_spacing`] estimate the resolution of a coordinate
+//! axis, e.g. to warn when a point filter's tolerance is too small to reach any
+//! grid node.
+//!
+//! ## Coordinate Cache
+//!
+//! Filters read coordinate variables through a [`CoordinateCache`], so when several
+//! filters target the same axis (e.g. a range filter and a point filter on latitude)
+//! the variable is read from disk only once per extraction.
 
 /// Type alias for coordinate pair result tuple
 type PairResult<'a> = Option<(&'a String, &'a String, &'a Vec<(usize, usize)>)>;
@@ -25,7 +58,11 @@ type TripletResult<'a> = Option<(
     &'a Vec<(usize, usize, usize)>,
 )>;
 
+use crate::cftime::{iso_times_to_values, time_range_to_values};
+use log::warn;
 use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Result of applying a filter to NetCDF data.
 ///
@@ -100,8 +137,194 @@ impl FilterResult {
     }
 }
 
-pub trait NCFilter {
-    fn apply(&self, file: &netcdf::File) -> Result<FilterResult, Box<dyn std::error::Error>>;
+/// Coordinate variable values read from one NetCDF file, keyed by variable name.
+///
+/// Each variable is read with a single `get::<f64, _>(..)` call the first time it
+/// is requested; later requests share the cached values.
+#[derive(Debug, Default)]
+pub struct CoordinateCache {
+    values: HashMap<String, Arc<[f64]>>,
+    reads: usize,
+}
+
+impl CoordinateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the values of the variable `name`, reading them from `file` on first use.
+    ///
+    /// Returns `Ok(None)` when the file has no variable with that name.
+    pub fn get(
+        &mut self,
+        file: &netcdf::File,
+        name: &str,
+    ) -> Result<Option<Arc<[f64]>>, Box<dyn std::error::Error>> {
+        if let Some(values) = self.values.get(name) {
+            return Ok(Some(Arc::clone(values)));
+        }
+        let Some(var) = file.variable(name) else {
+            return Ok(None);
+        };
+        let values: Arc<[f64]> = var.get::<f64, _>(..)?.iter().copied().collect();
+        self.reads += 1;
+        self.values.insert(name.to_string(), Arc::clone(&values));
+        Ok(Some(values))
+    }
+
+    /// Number of variables read from disk so far
+    pub fn reads(&self) -> usize {
+        self.reads
+    }
+
+    /// Moves the variables read by `other` into this cache, keeping the values
+    /// already cached here; its reads are added to this cache's count.
+    pub fn merge(&mut self, other: CoordinateCache) {
+        for (name, values) in other.values {
+            self.values.entry(name).or_insert(values);
+        }
+        self.reads += other.reads;
+    }
+}
+
+pub trait NCFilter: Send + Sync {
+    fn apply(&self, file: &netcdf::File) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        self.apply_cached(file, &mut CoordinateCache::new())
+    }
+
+    /// Applies the filter, reading coordinate variables through `cache`.
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>>;
+
+    /// The dimension selected by a filter whose results are always
+    /// [`FilterResult::Single`], or `None` for filters over several dimensions.
+    ///
+    /// Filters on different dimensions are independent, which lets
+    /// [`crate::extract::apply_filters`] evaluate them concurrently.
+    fn dimension(&self) -> Option<&str> {
+        None
+    }
+
+    /// Returns a one-line, human-readable description of what the filter selects,
+    /// e.g. `Range on 'latitude' [30, 60]`.
+    fn describe(&self) -> String;
+}
+
+/// Maps a longitude in any convention (e.g. 0..360) to the -180..180 range.
+///
+/// The result lies in `[-180, 180)`, so `180` and `-180` both map to `-180`.
+pub fn normalize_longitude(longitude: f64) -> f64 {
+    (longitude + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// Whether `value` lies between `min` and `max`, including each bound as requested
+fn in_bounds(value: f64, min: f64, max: f64, min_inclusive: bool, max_inclusive: bool) -> bool {
+    above_min(value, min, min_inclusive) && below_max(value, max, max_inclusive)
+}
+
+fn above_min(value: f64, min: f64, inclusive: bool) -> bool {
+    if inclusive { value >= min } else { value > min }
+}
+
+fn below_max(value: f64, max: f64, inclusive: bool) -> bool {
+    if inclusive { value <= max } else { value < max }
+}
+
+/// Whether `longitude` lies between `min` and `max` once all three are normalized.
+///
+/// Ranges crossing the antimeridian (e.g. 170..190, which becomes 170..-170) wrap
+/// around, and ranges spanning 360 degrees or more match every longitude.
+fn longitude_in_range(
+    longitude: f64,
+    min: f64,
+    max: f64,
+    min_inclusive: bool,
+    max_inclusive: bool,
+) -> bool {
+    if max - min >= 360.0 {
+        return !longitude.is_nan();
+    }
+    let (longitude, min, max) = (
+        normalize_longitude(longitude),
+        normalize_longitude(min),
+        normalize_longitude(max),
+    );
+    if min <= max {
+        in_bounds(longitude, min, max, min_inclusive, max_inclusive)
+    } else {
+        above_min(longitude, min, min_inclusive) || below_max(longitude, max, max_inclusive)
+    }
+}
+
+/// Absolute difference between two longitudes, going around the globe when
+/// `normalize` is set (so 359 and -1 are 0 degrees apart)
+fn longitude_distance(longitude: f64, target: f64, normalize: bool) -> f64 {
+    if normalize {
+        normalize_longitude(longitude - target).abs()
+    } else {
+        (longitude - target).abs()
+    }
+}
+
+/// Estimates the grid spacing of a coordinate axis as the median absolute
+/// difference between consecutive values, ignoring NaNs.
+///
+/// Returns `None` when there are fewer than two valid values.
+pub fn median_spacing(values: &[f64]) -> Option<f64> {
+    let mut steps: Vec<f64> = values
+        .windows(2)
+        .map(|pair| (pair[1] - pair[0]).abs())
+        .filter(|step| !step.is_nan())
+        .collect();
+    if steps.is_empty() {
+        return None;
+    }
+    steps.sort_by(f64::total_cmp);
+    let mid = steps.len() / 2;
+    Some(if steps.len() % 2 == 0 {
+        (steps[mid - 1] + steps[mid]) / 2.0
+    } else {
+        steps[mid]
+    })
+}
+
+/// Median grid spacing (see [`median_spacing`]) of the 1-D coordinate variable `name`.
+///
+/// Returns `Ok(None)` when the file has no such variable or it is not 1-D, as the
+/// spacing of curvilinear (2-D) coordinates depends on the direction.
+pub fn axis_spacing(
+    file: &netcdf::File,
+    cache: &mut CoordinateCache,
+    name: &str,
+) -> Result<Option<f64>, Box<dyn std::error::Error>> {
+    if variable_dimensions(file, name).len() != 1 {
+        return Ok(None);
+    }
+    Ok(cache
+        .get(file, name)?
+        .and_then(|values| median_spacing(&values)))
+}
+
+/// Serde default for flags that are on unless disabled
+pub(crate) fn default_true() -> bool {
+    true
+}
+
+/// Formats a point list for [`NCFilter::describe`], eliding all but the first few points
+fn describe_points(points: &[(f64, f64)]) -> String {
+    const SHOWN: usize = 3;
+    let mut shown: Vec<String> = points
+        .iter()
+        .take(SHOWN)
+        .map(|(lat, lon)| format!("({}, {})", lat, lon))
+        .collect();
+    if points.len() > SHOWN {
+        shown.push(format!("... {} more", points.len() - SHOWN));
+    }
+    shown.join(", ")
 }
 
 #[derive(Deserialize)]
@@ -109,6 +332,15 @@ pub struct NCRangeFilter {
     pub dimension_name: String,
     pub min_value: f64,
     pub max_value: f64,
+    /// Treat the dimension as longitude and compare in the -180..180 convention
+    #[serde(default)]
+    pub normalize_longitude: bool,
+    /// Whether values equal to `min_value` are selected
+    #[serde(default = "default_true")]
+    pub min_inclusive: bool,
+    /// Whether values equal to `max_value` are selected
+    #[serde(default = "default_true")]
+    pub max_inclusive: bool,
 }
 
 impl NCRangeFilter {
@@ -117,9 +349,27 @@ impl NCRangeFilter {
             dimension_name: dimension_name.to_string(),
             min_value,
             max_value,
+            normalize_longitude: false,
+            min_inclusive: true,
+            max_inclusive: true,
         }
     }
 
+    /// Sets whether each bound is part of the range; both are by default.
+    ///
+    /// Half-open ranges such as `[30, 45)` tile an axis without overlap: a value
+    /// on the shared boundary belongs only to the band that includes it.
+    pub fn with_inclusive_bounds(mut self, min_inclusive: bool, max_inclusive: bool) -> Self {
+        self.min_inclusive = min_inclusive;
+        self.max_inclusive = max_inclusive;
+        self
+    }
+
+    pub fn with_normalize_longitude(mut self, normalize_longitude: bool) -> Self {
+        self.normalize_longitude = normalize_longitude;
+        self
+    }
+
     pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let f: NCRangeFilter = serde_json::from_str(json_str)?;
         Ok(f)
@@ -127,13 +377,24 @@ impl NCRangeFilter {
 }
 
 impl NCFilter for NCRangeFilter {
-    fn apply(&self, file: &netcdf::File) -> Result<FilterResult, Box<dyn std::error::Error>> {
-        if let Some(var) = file.variable(&self.dimension_name) {
-            let values = var.get::<f64, _>(..)?;
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        if let Some(values) = cache.get(file, &self.dimension_name)? {
             let filtered_indices: Vec<usize> = values
                 .iter()
                 .enumerate()
-                .filter(|(_, val)| **val >= self.min_value && **val <= self.max_value)
+                .filter(|(_, val)| {
+                    let (min, max) = (self.min_value, self.max_value);
+                    let (min_inclusive, max_inclusive) = (self.min_inclusive, self.max_inclusive);
+                    if self.normalize_longitude {
+                        longitude_in_range(**val, min, max, min_inclusive, max_inclusive)
+                    } else {
+                        in_bounds(**val, min, max, min_inclusive, max_inclusive)
+                    }
+                })
                 .map(|(idx, _)| idx)
                 .collect();
             Ok(FilterResult::Single {
@@ -141,15 +402,39 @@ impl NCFilter for NCRangeFilter {
                 indices: filtered_indices,
             })
         } else {
-            Err(format!("Dimension variable '{}' not found", self.dimension_name).into())
+            Err(missing_coordinate_message(file, &self.dimension_name).into())
         }
     }
+
+    fn dimension(&self) -> Option<&str> {
+        Some(&self.dimension_name)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Range on '{}' {}{}, {}{}{}",
+            self.dimension_name,
+            if self.min_inclusive { '[' } else { '(' },
+            self.min_value,
+            self.max_value,
+            if self.max_inclusive { ']' } else { ')' },
+            if self.normalize_longitude {
+                " (longitude, normalized)"
+            } else {
+                ""
+            }
+        )
+    }
 }
 
 #[derive(Deserialize)]
 pub struct NCListFilter {
     pub dimension_name: String,
     pub values: Vec<f64>,
+    /// Maximum distance between a coordinate and a listed value for it to match;
+    /// values must be equal when unset
+    #[serde(default)]
+    pub tolerance: Option<f64>,
 }
 
 impl NCListFilter {
@@ -157,6 +442,24 @@ impl NCListFilter {
         NCListFilter {
             dimension_name: dimension_name.to_string(),
             values,
+            tolerance: None,
+        }
+    }
+
+    /// Matches coordinates within `tolerance` of a listed value, so that e.g. a
+    /// stored `849.99999` is selected by `850.0`
+    pub fn with_tolerance(mut self, tolerance: Option<f64>) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    fn matches(&self, value: f64) -> bool {
+        match self.tolerance {
+            Some(tolerance) => self
+                .values
+                .iter()
+                .any(|target| (value - target).abs() <= tolerance),
+            None => self.values.contains(&value),
         }
     }
 
@@ -167,13 +470,16 @@ impl NCListFilter {
 }
 
 impl NCFilter for NCListFilter {
-    fn apply(&self, file: &netcdf::File) -> Result<FilterResult, Box<dyn std::error::Error>> {
-        if let Some(var) = file.variable(&self.dimension_name) {
-            let coord_values = var.get::<f64, _>(..)?;
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        if let Some(coord_values) = cache.get(file, &self.dimension_name)? {
             let filtered_indices: Vec<usize> = coord_values
                 .iter()
                 .enumerate()
-                .filter(|(_, val)| self.values.contains(val))
+                .filter(|(_, val)| self.matches(**val))
                 .map(|(idx, _)| idx)
                 .collect();
             Ok(FilterResult::Single {
@@ -181,17 +487,41 @@ impl NCFilter for NCListFilter {
                 indices: filtered_indices,
             })
         } else {
-            Err(format!("Dimension variable '{}' not found", self.dimension_name).into())
+            Err(missing_coordinate_message(file, &self.dimension_name).into())
+        }
+    }
+
+    fn dimension(&self) -> Option<&str> {
+        Some(&self.dimension_name)
+    }
+
+    fn describe(&self) -> String {
+        match self.tolerance {
+            Some(tolerance) => format!(
+                "List on '{}' {:?} (tolerance {})",
+                self.dimension_name, self.values, tolerance
+            ),
+            None => format!("List on '{}' {:?}", self.dimension_name, self.values),
         }
     }
 }
 
+/// Selects the grid cells within `tolerance` of each `(lat, lon)` point.
+///
+/// Latitude and longitude may be 1-D axes or 2-D curvilinear arrays indexed by
+/// `(y, x)`; in the latter case the result pairs refer to the `y` and `x` dimensions.
 #[derive(Deserialize)]
 pub struct NC2DPointFilter {
     pub lat_dimension_name: String,
     pub lon_dimension_name: String,
     pub points: Vec<(f64, f64)>,
     pub tolerance: f64,
+    /// Select the closest grid cell when no point lies within tolerance
+    #[serde(default)]
+    pub snap_nearest: bool,
+    /// Compare longitudes in the -180..180 convention
+    #[serde(default)]
+    pub normalize_longitude: bool,
 }
 
 impl NC2DPointFilter {
@@ -206,9 +536,21 @@ impl NC2DPointFilter {
             lon_dimension_name: lon_dimension_name.to_string(),
             points,
             tolerance,
+            snap_nearest: false,
+            normalize_longitude: false,
         }
     }
 
+    pub fn with_snap_nearest(mut self, snap_nearest: bool) -> Self {
+        self.snap_nearest = snap_nearest;
+        self
+    }
+
+    pub fn with_normalize_longitude(mut self, normalize_longitude: bool) -> Self {
+        self.normalize_longitude = normalize_longitude;
+        self
+    }
+
     pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let f: NC2DPointFilter = serde_json::from_str(json_str)?;
         Ok(f)
@@ -216,31 +558,65 @@ impl NC2DPointFilter {
 }
 
 impl NCFilter for NC2DPointFilter {
-    fn apply(&self, file: &netcdf::File) -> Result<FilterResult, Box<dyn std::error::Error>> {
-        let lat_var = file.variable(&self.lat_dimension_name).ok_or(format!(
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        let lat_values = cache.get(file, &self.lat_dimension_name)?.ok_or(format!(
             "Latitude variable '{}' not found",
             self.lat_dimension_name
         ))?;
-        let lon_var = file.variable(&self.lon_dimension_name).ok_or(format!(
+        let lon_values = cache.get(file, &self.lon_dimension_name)?.ok_or(format!(
             "Longitude variable '{}' not found",
             self.lon_dimension_name
         ))?;
 
-        let lat_values = lat_var.get::<f64, _>(..)?;
-        let lon_values = lon_var.get::<f64, _>(..)?;
+        let lat_dims = variable_dimensions(file, &self.lat_dimension_name);
+        let lon_dims = variable_dimensions(file, &self.lon_dimension_name);
+        if lat_dims.len() == 2 || lon_dims.len() == 2 {
+            if lat_dims.len() != 2 || lat_dims != lon_dims {
+                return Err(format!(
+                    "Curvilinear coordinates '{}' and '{}' must both be 2-D over the same dimensions",
+                    self.lat_dimension_name, self.lon_dimension_name
+                )
+                .into());
+            }
+            return Ok(self.apply_curvilinear(&lat_values, &lon_values, &lat_dims));
+        }
 
         let mut filtered_indices = Vec::new();
 
         for &(target_lat, target_lon) in &self.points {
+            let matches_before = filtered_indices.len();
             for (i, &lat) in lat_values.iter().enumerate() {
                 if (lat - target_lat).abs() <= self.tolerance {
                     for (j, &lon) in lon_values.iter().enumerate() {
-                        if (lon - target_lon).abs() <= self.tolerance {
+                        if longitude_distance(lon, target_lon, self.normalize_longitude)
+                            <= self.tolerance
+                        {
                             filtered_indices.push((i, j));
                         }
                     }
                 }
             }
+
+            if self.snap_nearest && filtered_indices.len() == matches_before {
+                // The tolerance box is separable, so the closest cell is the
+                // closest latitude paired with the closest longitude.
+                if let (Some(i), Some(j)) = (
+                    nearest_index(lat_values.iter().copied(), |lat| (lat - target_lat).abs()),
+                    nearest_index(lon_values.iter().copied(), |lon| {
+                        longitude_distance(lon, target_lon, self.normalize_longitude)
+                    }),
+                ) {
+                    warn!(
+                        "No grid point within tolerance {} of ({}, {}); snapping to nearest cell ({}, {})",
+                        self.tolerance, target_lat, target_lon, lat_values[i], lon_values[j]
+                    );
+                    filtered_indices.push((i, j));
+                }
+            }
         }
 
         Ok(FilterResult::Pairs {
@@ -249,6 +625,137 @@ impl NCFilter for NC2DPointFilter {
             pairs: filtered_indices,
         })
     }
+
+    fn describe(&self) -> String {
+        format!(
+            "2D points on ('{}', '{}') ±{}{}: {}",
+            self.lat_dimension_name,
+            self.lon_dimension_name,
+            self.tolerance,
+            if self.snap_nearest {
+                ", snapping to nearest"
+            } else {
+                ""
+            },
+            describe_points(&self.points)
+        )
+    }
+}
+
+impl NC2DPointFilter {
+    /// Matches points against 2-D latitude/longitude arrays indexed by `(y, x)`.
+    ///
+    /// Every grid cell is checked against the tolerance box; the returned pairs
+    /// are `(y, x)` indices, reported under the names of the underlying dimensions.
+    /// When snapping, the nearest cell is the one with the smallest squared
+    /// distance in degrees.
+    fn apply_curvilinear(
+        &self,
+        lat_values: &[f64],
+        lon_values: &[f64],
+        dims: &[(String, usize)],
+    ) -> FilterResult {
+        let x_len = dims[1].1;
+        let cells = || {
+            lat_values
+                .iter()
+                .copied()
+                .zip(lon_values.iter().copied())
+                .enumerate()
+        };
+        let mut filtered_indices = Vec::new();
+
+        for &(target_lat, target_lon) in &self.points {
+            let matches_before = filtered_indices.len();
+            for (cell, (lat, lon)) in cells() {
+                if (lat - target_lat).abs() <= self.tolerance
+                    && longitude_distance(lon, target_lon, self.normalize_longitude)
+                        <= self.tolerance
+                {
+                    filtered_indices.push((cell / x_len, cell % x_len));
+                }
+            }
+
+            if self.snap_nearest && filtered_indices.len() == matches_before {
+                let distance = |(lat, lon): (f64, f64)| {
+                    (lat - target_lat).powi(2)
+                        + longitude_distance(lon, target_lon, self.normalize_longitude).powi(2)
+                };
+                if let Some((cell, (lat, lon))) = cells()
+                    .filter(|(_, (lat, lon))| !lat.is_nan() && !lon.is_nan())
+                    .min_by(|(_, a), (_, b)| distance(*a).total_cmp(&distance(*b)))
+                {
+                    warn!(
+                        "No grid point within tolerance {} of ({}, {}); snapping to nearest cell ({}, {})",
+                        self.tolerance, target_lat, target_lon, lat, lon
+                    );
+                    filtered_indices.push((cell / x_len, cell % x_len));
+                }
+            }
+        }
+
+        FilterResult::Pairs {
+            lat_dimension: dims[0].0.clone(),
+            lon_dimension: dims[1].0.clone(),
+            pairs: filtered_indices,
+        }
+    }
+}
+
+/// Names of the dimensions of `file` that have a coordinate variable, i.e. a
+/// variable named after the dimension
+pub fn coordinate_dimensions(file: &netcdf::File) -> Vec<String> {
+    file.dimensions()
+        .map(|dim| dim.name())
+        .filter(|name| file.variable(name).is_some())
+        .collect()
+}
+
+/// Explains why a value filter cannot apply to `dimension`, for a file without
+/// a coordinate variable of that name. A dimension that exists can still be
+/// selected by position, so the message points to the index-based filters.
+pub fn missing_coordinate_message(file: &netcdf::File, dimension: &str) -> String {
+    let coordinates = coordinate_dimensions(file);
+    let coordinates = if coordinates.is_empty() {
+        "none".to_string()
+    } else {
+        coordinates.join(", ")
+    };
+    if file.dimension(dimension).is_some() {
+        format!(
+            "Dimension '{}' has no coordinate variable, so it cannot be filtered by value; select it by index with an index_range or stride filter instead (dimensions with coordinates: {})",
+            dimension, coordinates
+        )
+    } else {
+        format!(
+            "Dimension variable '{}' not found (dimensions with coordinates: {})",
+            dimension, coordinates
+        )
+    }
+}
+
+/// Returns the `(name, length)` of each dimension of the variable `name`
+fn variable_dimensions(file: &netcdf::File, name: &str) -> Vec<(String, usize)> {
+    file.variable(name)
+        .map(|var| {
+            var.dimensions()
+                .iter()
+                .map(|dim| (dim.name(), dim.len()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the index of the value with the smallest `distance`, ignoring NaNs.
+fn nearest_index(
+    values: impl Iterator<Item = f64>,
+    distance: impl Fn(f64) -> f64,
+) -> Option<usize> {
+    values
+        .enumerate()
+        .filter(|(_, value)| !value.is_nan())
+        .min_by(|(_, a), (_, b)| distance(*a).total_cmp(&distance(*b)))
+        .map(|(index, _)| index)
 }
 
 #[derive(Deserialize)]
@@ -256,9 +763,17 @@ pub struct NC3DPointFilter {
     pub time_dimension_name: String,
     pub lat_dimension_name: String,
     pub lon_dimension_name: String,
+    /// Raw values of the time coordinate to select
+    #[serde(default)]
     pub steps: Vec<f64>,
+    /// ISO 8601 times to select, converted with the time variable's CF `units`
+    #[serde(default)]
+    pub step_dates: Vec<String>,
     pub points: Vec<(f64, f64)>,
     pub tolerance: f64,
+    /// Compare longitudes in the -180..180 convention
+    #[serde(default)]
+    pub normalize_longitude: bool,
 }
 
 impl NC3DPointFilter {
@@ -275,11 +790,24 @@ impl NC3DPointFilter {
             lat_dimension_name: lat_dimension_name.to_string(),
             lon_dimension_name: lon_dimension_name.to_string(),
             steps,
+            step_dates: Vec::new(),
             points,
             tolerance,
+            normalize_longitude: false,
         }
     }
 
+    pub fn with_normalize_longitude(mut self, normalize_longitude: bool) -> Self {
+        self.normalize_longitude = normalize_longitude;
+        self
+    }
+
+    /// Also selects the time steps at these ISO 8601 times (see [`step_dates_to_values`])
+    pub fn with_step_dates(mut self, step_dates: Vec<String>) -> Self {
+        self.step_dates = step_dates;
+        self
+    }
+
     pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
         let f: NC3DPointFilter = serde_json::from_str(json_str)?;
         Ok(f)
@@ -287,27 +815,45 @@ impl NC3DPointFilter {
 }
 
 impl NCFilter for NC3DPointFilter {
-    fn apply(&self, file: &netcdf::File) -> Result<FilterResult, Box<dyn std::error::Error>> {
-        let time_var = file.variable(&self.time_dimension_name).ok_or(format!(
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        let time_values = cache.get(file, &self.time_dimension_name)?.ok_or(format!(
             "Time variable '{}' not found",
             self.time_dimension_name
         ))?;
-        let lat_var = file.variable(&self.lat_dimension_name).ok_or(format!(
+        let lat_values = cache.get(file, &self.lat_dimension_name)?.ok_or(format!(
             "Latitude variable '{}' not found",
             self.lat_dimension_name
         ))?;
-        let lon_var = file.variable(&self.lon_dimension_name).ok_or(format!(
+        let lon_values = cache.get(file, &self.lon_dimension_name)?.ok_or(format!(
             "Longitude variable '{}' not found",
             self.lon_dimension_name
         ))?;
-        let time_values = time_var.get::<f64, _>(..)?;
-        let lat_values = lat_var.get::<f64, _>(..)?;
-        let lon_values = lon_var.get::<f64, _>(..)?;
+
+        let date_steps = if self.step_dates.is_empty() {
+            None
+        } else {
+            Some(step_dates_to_values(
+                file,
+                &self.time_dimension_name,
+                &self.step_dates,
+            )?)
+        };
 
         let filtered_time_indices: Vec<usize> = time_values
             .iter()
             .enumerate()
-            .filter(|(_, val)| self.steps.contains(val))
+            .filter(|(_, val)| {
+                self.steps.contains(val)
+                    || date_steps.as_ref().is_some_and(|(values, resolution)| {
+                        values
+                            .iter()
+                            .any(|value| (*val - value).abs() <= *resolution)
+                    })
+            })
             .map(|(idx, _)| idx)
             .collect();
 
@@ -317,7 +863,9 @@ impl NCFilter for NC3DPointFilter {
             for (i, &lat) in lat_values.iter().enumerate() {
                 if (lat - target_lat).abs() <= self.tolerance {
                     for (j, &lon) in lon_values.iter().enumerate() {
-                        if (lon - target_lon).abs() <= self.tolerance {
+                        if longitude_distance(lon, target_lon, self.normalize_longitude)
+                            <= self.tolerance
+                        {
                             for &t_idx in &filtered_time_indices {
                                 filtered_indices.push((t_idx, i, j));
                             }
@@ -334,6 +882,574 @@ impl NCFilter for NC3DPointFilter {
             triplets: filtered_indices,
         })
     }
+
+    fn describe(&self) -> String {
+        let dates = if self.step_dates.is_empty() {
+            String::new()
+        } else {
+            format!(" and dates {:?}", self.step_dates)
+        };
+        format!(
+            "3D points on ('{}', '{}', '{}') ±{} at steps {:?}{}: {}",
+            self.time_dimension_name,
+            self.lat_dimension_name,
+            self.lon_dimension_name,
+            self.tolerance,
+            self.steps,
+            dates,
+            describe_points(&self.points)
+        )
+    }
+}
+
+/// Selects the (time, lat, lon) cells inside a volume: a time window and a
+/// latitude/longitude box, all bounds included.
+///
+/// The window is given either as raw values of the time coordinate (`start` and
+/// `end`) or as ISO 8601 times (`start_date` and `end_date`) converted with the
+/// time variable's CF `units` (see [`time_range_to_values`]). With
+/// `normalize_longitude`, the longitude bounds follow the range filter's
+/// convention, so a box such as 170..190 crosses the antimeridian.
+#[derive(Deserialize)]
+pub struct NC3DBoundingBoxFilter {
+    pub time_dimension_name: String,
+    pub lat_dimension_name: String,
+    pub lon_dimension_name: String,
+    #[serde(default)]
+    pub start: Option<f64>,
+    #[serde(default)]
+    pub end: Option<f64>,
+    #[serde(default)]
+    pub start_date: Option<String>,
+    #[serde(default)]
+    pub end_date: Option<String>,
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lon: f64,
+    pub max_lon: f64,
+    /// Compare longitudes in the -180..180 convention
+    #[serde(default)]
+    pub normalize_longitude: bool,
+}
+
+impl NC3DBoundingBoxFilter {
+    /// Selects the cells between the raw time values `start` and `end` inside the
+    /// box `(min_lat, max_lat)` x `(min_lon, max_lon)`
+    pub fn new(
+        time_dimension_name: &str,
+        lat_dimension_name: &str,
+        lon_dimension_name: &str,
+        (start, end): (f64, f64),
+        (min_lat, max_lat): (f64, f64),
+        (min_lon, max_lon): (f64, f64),
+    ) -> Self {
+        NC3DBoundingBoxFilter {
+            time_dimension_name: time_dimension_name.to_string(),
+            lat_dimension_name: lat_dimension_name.to_string(),
+            lon_dimension_name: lon_dimension_name.to_string(),
+            start: Some(start),
+            end: Some(end),
+            start_date: None,
+            end_date: None,
+            min_lat,
+            max_lat,
+            min_lon,
+            max_lon,
+            normalize_longitude: false,
+        }
+    }
+
+    /// Selects the cells between the ISO 8601 times `start_date` and `end_date`
+    /// inside the box `(min_lat, max_lat)` x `(min_lon, max_lon)`
+    pub fn between_dates(
+        time_dimension_name: &str,
+        lat_dimension_name: &str,
+        lon_dimension_name: &str,
+        (start_date, end_date): (&str, &str),
+        (min_lat, max_lat): (f64, f64),
+        (min_lon, max_lon): (f64, f64),
+    ) -> Self {
+        NC3DBoundingBoxFilter {
+            start: None,
+            end: None,
+            start_date: Some(start_date.to_string()),
+            end_date: Some(end_date.to_string()),
+            ..Self::new(
+                time_dimension_name,
+                lat_dimension_name,
+                lon_dimension_name,
+                (0.0, 0.0),
+                (min_lat, max_lat),
+                (min_lon, max_lon),
+            )
+        }
+    }
+
+    pub fn with_normalize_longitude(mut self, normalize_longitude: bool) -> Self {
+        self.normalize_longitude = normalize_longitude;
+        self
+    }
+
+    pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let f: NC3DBoundingBoxFilter = serde_json::from_str(json_str)?;
+        Ok(f)
+    }
+
+    /// The time window as values of the time coordinate
+    fn time_window(&self, file: &netcdf::File) -> Result<(f64, f64), Box<dyn std::error::Error>> {
+        match (self.start, self.end, &self.start_date, &self.end_date) {
+            (Some(start), Some(end), None, None) => Ok((start, end)),
+            (None, None, Some(start_date), Some(end_date)) => Ok(time_range_to_values(
+                file,
+                &self.time_dimension_name,
+                start_date,
+                end_date,
+            )?),
+            _ => Err(format!(
+                "3D bounding box on '{}' needs either start and end, or start_date and end_date",
+                self.time_dimension_name
+            )
+            .into()),
+        }
+    }
+}
+
+impl NCFilter for NC3DBoundingBoxFilter {
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        let (start, end) = self.time_window(file)?;
+        let time_values = cache
+            .get(file, &self.time_dimension_name)?
+            .ok_or_else(|| missing_coordinate_message(file, &self.time_dimension_name))?;
+        let lat_values = cache
+            .get(file, &self.lat_dimension_name)?
+            .ok_or_else(|| missing_coordinate_message(file, &self.lat_dimension_name))?;
+        let lon_values = cache
+            .get(file, &self.lon_dimension_name)?
+            .ok_or_else(|| missing_coordinate_message(file, &self.lon_dimension_name))?;
+
+        let time_indices: Vec<usize> = time_values
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| in_bounds(**val, start, end, true, true))
+            .map(|(idx, _)| idx)
+            .collect();
+        let lat_indices: Vec<usize> = lat_values
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| in_bounds(**val, self.min_lat, self.max_lat, true, true))
+            .map(|(idx, _)| idx)
+            .collect();
+        let lon_indices: Vec<usize> = lon_values
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| {
+                if self.normalize_longitude {
+                    longitude_in_range(**val, self.min_lon, self.max_lon, true, true)
+                } else {
+                    in_bounds(**val, self.min_lon, self.max_lon, true, true)
+                }
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+
+        let mut triplets =
+            Vec::with_capacity(time_indices.len() * lat_indices.len() * lon_indices.len());
+        for &t_idx in &time_indices {
+            for &lat_idx in &lat_indices {
+                for &lon_idx in &lon_indices {
+                    triplets.push((t_idx, lat_idx, lon_idx));
+                }
+            }
+        }
+
+        Ok(FilterResult::Triplets {
+            time_dimension: self.time_dimension_name.clone(),
+            lat_dimension: self.lat_dimension_name.clone(),
+            lon_dimension: self.lon_dimension_name.clone(),
+            triplets,
+        })
+    }
+
+    fn describe(&self) -> String {
+        let window = match (self.start, self.end, &self.start_date, &self.end_date) {
+            (Some(start), Some(end), _, _) => format!("{}..{}", start, end),
+            (_, _, Some(start_date), Some(end_date)) => format!("{}..{}", start_date, end_date),
+            _ => "?".to_string(),
+        };
+        format!(
+            "3D bounding box on ('{}', '{}', '{}'): time {}, lat [{}, {}], lon [{}, {}]{}",
+            self.time_dimension_name,
+            self.lat_dimension_name,
+            self.lon_dimension_name,
+            window,
+            self.min_lat,
+            self.max_lat,
+            self.min_lon,
+            self.max_lon,
+            if self.normalize_longitude {
+                " (normalized)"
+            } else {
+                ""
+            }
+        )
+    }
+}
+
+/// Converts ISO 8601 times into values of the time coordinate `time_name`.
+///
+/// The variable's `units` attribute must be a CF time unit such as
+/// `"hours since 1970-01-01"` (see [`crate::cftime::iso_times_to_values`]).
+///
+/// Returns the values along with the matching resolution (see
+/// [`TimeReference::resolution`]).
+pub fn step_dates_to_values(
+    file: &netcdf::File,
+    time_name: &str,
+    step_dates: &[String],
+) -> Result<(Vec<f64>, f64), Box<dyn std::error::Error>> {
+    let (values, reference) = iso_times_to_values(file, time_name, step_dates)?;
+    Ok((values, reference.resolution()))
+}
+
+#[derive(Deserialize)]
+pub struct NCStrideFilter {
+    pub dimension_name: String,
+    pub start: usize,
+    pub stride: usize,
+    pub count: Option<usize>,
+}
+
+impl NCStrideFilter {
+    pub fn new(dimension_name: &str, start: usize, stride: usize, count: Option<usize>) -> Self {
+        NCStrideFilter {
+            dimension_name: dimension_name.to_string(),
+            start,
+            stride,
+            count,
+        }
+    }
+
+    pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let f: NCStrideFilter = serde_json::from_str(json_str)?;
+        Ok(f)
+    }
+}
+
+impl NCFilter for NCStrideFilter {
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        _cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        if self.stride == 0 {
+            return Err(format!(
+                "Stride for dimension '{}' must be at least 1",
+                self.dimension_name
+            )
+            .into());
+        }
+
+        let dim_len = file
+            .dimension(&self.dimension_name)
+            .map(|dim| dim.len())
+            .ok_or(format!("Dimension '{}' not found", self.dimension_name))?;
+
+        let filtered_indices: Vec<usize> = (self.start..dim_len)
+            .step_by(self.stride)
+            .take(self.count.unwrap_or(usize::MAX))
+            .collect();
+
+        Ok(FilterResult::Single {
+            dimension: self.dimension_name.clone(),
+            indices: filtered_indices,
+        })
+    }
+
+    fn dimension(&self) -> Option<&str> {
+        Some(&self.dimension_name)
+    }
+
+    fn describe(&self) -> String {
+        let count = self
+            .count
+            .map(|count| format!(", at most {}", count))
+            .unwrap_or_default();
+        format!(
+            "Stride on '{}' every {} from index {}{}",
+            self.dimension_name, self.stride, self.start, count
+        )
+    }
+}
+
+/// Selects the indices `start..end` (end excluded) of a dimension, every `step`-th.
+///
+/// Only the dimension length is read, so this works on dimensions without a
+/// coordinate variable. An `end` past the dimension is clamped to its length.
+#[derive(Deserialize)]
+pub struct NCIndexRangeFilter {
+    pub dimension_name: String,
+    #[serde(default)]
+    pub start: usize,
+    pub end: usize,
+    #[serde(default = "default_step")]
+    pub step: usize,
+}
+
+fn default_step() -> usize {
+    1
+}
+
+impl NCIndexRangeFilter {
+    pub fn new(dimension_name: &str, start: usize, end: usize) -> Self {
+        NCIndexRangeFilter {
+            dimension_name: dimension_name.to_string(),
+            start,
+            end,
+            step: 1,
+        }
+    }
+
+    pub fn with_step(mut self, step: usize) -> Self {
+        self.step = step;
+        self
+    }
+
+    pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let f: NCIndexRangeFilter = serde_json::from_str(json_str)?;
+        Ok(f)
+    }
+}
+
+impl NCFilter for NCIndexRangeFilter {
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        _cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        if self.step == 0 {
+            return Err(format!(
+                "Step for dimension '{}' must be at least 1",
+                self.dimension_name
+            )
+            .into());
+        }
+
+        let dim_len = file
+            .dimension(&self.dimension_name)
+            .map(|dim| dim.len())
+            .ok_or(format!("Dimension '{}' not found", self.dimension_name))?;
+
+        let filtered_indices: Vec<usize> = (self.start..self.end.min(dim_len))
+            .step_by(self.step)
+            .collect();
+
+        Ok(FilterResult::Single {
+            dimension: self.dimension_name.clone(),
+            indices: filtered_indices,
+        })
+    }
+
+    fn dimension(&self) -> Option<&str> {
+        Some(&self.dimension_name)
+    }
+
+    fn describe(&self) -> String {
+        let step = if self.step == 1 {
+            String::new()
+        } else {
+            format!(":{}", self.step)
+        };
+        format!(
+            "Index range on '{}' [{}:{}{}]",
+            self.dimension_name, self.start, self.end, step
+        )
+    }
+}
+
+/// Selects the steps of a CF time axis between two ISO 8601 times, both included.
+///
+/// The bounds are converted to values of the time coordinate with its `units`
+/// attribute (see [`time_range_to_values`]), so callers never compute numeric
+/// offsets themselves. Times without a UTC offset are taken as UTC, and a date
+/// alone means midnight.
+#[derive(Deserialize)]
+pub struct NCTimeRangeFilter {
+    pub time_dimension_name: String,
+    pub start_date: String,
+    pub end_date: String,
+}
+
+impl NCTimeRangeFilter {
+    pub fn new(time_dimension_name: &str, start_date: &str, end_date: &str) -> Self {
+        NCTimeRangeFilter {
+            time_dimension_name: time_dimension_name.to_string(),
+            start_date: start_date.to_string(),
+            end_date: end_date.to_string(),
+        }
+    }
+
+    pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let f: NCTimeRangeFilter = serde_json::from_str(json_str)?;
+        Ok(f)
+    }
+}
+
+impl NCFilter for NCTimeRangeFilter {
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        let (min_value, max_value) = time_range_to_values(
+            file,
+            &self.time_dimension_name,
+            &self.start_date,
+            &self.end_date,
+        )?;
+        let values = cache
+            .get(file, &self.time_dimension_name)?
+            .ok_or_else(|| missing_coordinate_message(file, &self.time_dimension_name))?;
+
+        let filtered_indices: Vec<usize> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| (min_value..=max_value).contains(*val))
+            .map(|(idx, _)| idx)
+            .collect();
+
+        Ok(FilterResult::Single {
+            dimension: self.time_dimension_name.clone(),
+            indices: filtered_indices,
+        })
+    }
+
+    fn dimension(&self) -> Option<&str> {
+        Some(&self.time_dimension_name)
+    }
+
+    fn describe(&self) -> String {
+        format!(
+            "Time range on '{}' {}..{}",
+            self.time_dimension_name, self.start_date, self.end_date
+        )
+    }
+}
+
+/// Selects the `n` steps of a time axis closest to a target, e.g. for
+/// event-centered extracts.
+///
+/// The target is either a raw value of the time coordinate (`target`) or an
+/// ISO 8601 time (`target_date`) converted with the time variable's CF `units`
+/// (see [`iso_times_to_values`]); exactly one of them must be given. Steps at the
+/// same distance from the target are ranked by index, lowest first, so the
+/// selection is deterministic. Fewer than `n` steps are selected when the axis
+/// is shorter.
+#[derive(Deserialize)]
+pub struct NCNearestTimeFilter {
+    pub time_dimension_name: String,
+    #[serde(default)]
+    pub target: Option<f64>,
+    #[serde(default)]
+    pub target_date: Option<String>,
+    pub n: usize,
+}
+
+impl NCNearestTimeFilter {
+    /// Selects the `n` steps closest to the raw time value `target`
+    pub fn new(time_dimension_name: &str, target: f64, n: usize) -> Self {
+        NCNearestTimeFilter {
+            time_dimension_name: time_dimension_name.to_string(),
+            target: Some(target),
+            target_date: None,
+            n,
+        }
+    }
+
+    /// Selects the `n` steps closest to the ISO 8601 time `target_date`
+    pub fn at_date(time_dimension_name: &str, target_date: &str, n: usize) -> Self {
+        NCNearestTimeFilter {
+            time_dimension_name: time_dimension_name.to_string(),
+            target: None,
+            target_date: Some(target_date.to_string()),
+            n,
+        }
+    }
+
+    pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let f: NCNearestTimeFilter = serde_json::from_str(json_str)?;
+        Ok(f)
+    }
+
+    /// The target as a value of the time coordinate
+    fn target_value(&self, file: &netcdf::File) -> Result<f64, Box<dyn std::error::Error>> {
+        match (self.target, &self.target_date) {
+            (Some(target), None) => Ok(target),
+            (None, Some(target_date)) => {
+                let (values, _) = iso_times_to_values(
+                    file,
+                    &self.time_dimension_name,
+                    std::slice::from_ref(target_date),
+                )?;
+                Ok(values[0])
+            }
+            _ => Err(format!(
+                "Nearest time filter on '{}' needs exactly one of target and target_date",
+                self.time_dimension_name
+            )
+            .into()),
+        }
+    }
+}
+
+impl NCFilter for NCNearestTimeFilter {
+    fn apply_cached(
+        &self,
+        file: &netcdf::File,
+        cache: &mut CoordinateCache,
+    ) -> Result<FilterResult, Box<dyn std::error::Error>> {
+        let target = self.target_value(file)?;
+        let values = cache
+            .get(file, &self.time_dimension_name)?
+            .ok_or_else(|| missing_coordinate_message(file, &self.time_dimension_name))?;
+
+        // Rank by distance, then by index; the sort is stable, so ties keep index order
+        let mut ranked: Vec<(usize, f64)> = values
+            .iter()
+            .enumerate()
+            .filter(|(_, val)| !val.is_nan())
+            .map(|(idx, val)| (idx, (val - target).abs()))
+            .collect();
+        ranked.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut filtered_indices: Vec<usize> =
+            ranked.iter().take(self.n).map(|(idx, _)| *idx).collect();
+        filtered_indices.sort_unstable();
+
+        Ok(FilterResult::Single {
+            dimension: self.time_dimension_name.clone(),
+            indices: filtered_indices,
+        })
+    }
+
+    fn dimension(&self) -> Option<&str> {
+        Some(&self.time_dimension_name)
+    }
+
+    fn describe(&self) -> String {
+        let target = match (self.target, &self.target_date) {
+            (Some(target), _) => target.to_string(),
+            (None, Some(target_date)) => target_date.clone(),
+            (None, None) => "?".to_string(),
+        };
+        format!(
+            "Nearest {} time steps on '{}' to {}",
+            self.n, self.time_dimension_name, target
+        )
+    }
 }
 
 pub fn filter_factory(json_str: &str) -> Result<Box<dyn NCFilter>, Box<dyn std::error::Error>> {
@@ -356,6 +1472,26 @@ pub fn filter_factory(json_str: &str) -> Result<Box<dyn NCFilter>, Box<dyn std::
                 let filter = NC3DPointFilter::from_json(json_str)?;
                 Ok(Box::new(filter))
             }
+            "bbox_3d" => {
+                let filter = NC3DBoundingBoxFilter::from_json(json_str)?;
+                Ok(Box::new(filter))
+            }
+            "stride" => {
+                let filter = NCStrideFilter::from_json(json_str)?;
+                Ok(Box::new(filter))
+            }
+            "index_range" => {
+                let filter = NCIndexRangeFilter::from_json(json_str)?;
+                Ok(Box::new(filter))
+            }
+            "time_range" => {
+                let filter = NCTimeRangeFilter::from_json(json_str)?;
+                Ok(Box::new(filter))
+            }
+            "nearest_time" => {
+                let filter = NCNearestTimeFilter::from_json(json_str)?;
+                Ok(Box::new(filter))
+            }
             _ => Err(format!("Unknown filter kind: {}", filter_kind).into()),
         }
     } else {
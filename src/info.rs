@@ -2,15 +2,31 @@
 //!
 //! This module provides functionality to extract and display information about NetCDF files,
 //! including dimensions, variables, attributes, and metadata.
+//!
+//! Variables in NetCDF-4 groups are named by their group path, e.g.
+//! `diagnostics/temperature` (see [`resolve_variable`]).
+//!
+//! Two files can be compared with [`diff_netcdf_info`], which reports the
+//! dimensions, variables and attributes that were added, removed, renamed or
+//! changed.
+//!
+//! Detailed information also describes how each variable is stored (see
+//! [`NetCdfStorageInfo`]), like `ncdump -hs`: chunked variables whose chunks cut
+//! across the extracted selection, or that are heavily compressed, are slower to
+//! read.
+//!
+//! Information never reads coordinate or data arrays, only the file header, so
+//! it stays fast however large the file is. [`get_netcdf_header_info`] also
+//! skips the storage inquiry of detailed information, which reopens the file.
 
 use crate::storage::{StorageBackend, StorageFactory};
-use anyhow::{Context, Result};
+use anyhow::{Context, Result, bail};
 use log::debug;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
 /// Information about a NetCDF dimension
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NetCdfDimensionInfo {
     pub name: String,
     pub length: usize,
@@ -25,6 +41,45 @@ pub struct NetCdfVariableInfo {
     pub dimensions: Vec<String>,
     pub attributes: HashMap<String, String>,
     pub shape: Vec<usize>,
+    /// On-disk layout and compression, only read for detailed information
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<NetCdfStorageInfo>,
+}
+
+/// How a variable is laid out and compressed in the file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetCdfStorageInfo {
+    /// Chunk shape in dimension order, or `None` for contiguous storage
+    pub chunk_shape: Option<Vec<usize>>,
+    /// Deflate (zlib) level, or `None` when the variable is not deflate compressed
+    pub deflate_level: Option<i32>,
+    /// Whether the shuffle filter is applied before compression
+    pub shuffle: bool,
+}
+
+impl std::fmt::Display for NetCdfStorageInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.chunk_shape {
+            Some(chunk_shape) => write!(
+                f,
+                "chunked [{}]",
+                chunk_shape
+                    .iter()
+                    .map(|len| len.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?,
+            None => write!(f, "contiguous")?,
+        }
+        match self.deflate_level {
+            Some(level) => write!(f, ", deflate level {}", level)?,
+            None => write!(f, ", uncompressed")?,
+        }
+        if self.shuffle {
+            write!(f, ", shuffled")?;
+        }
+        Ok(())
+    }
 }
 
 /// Complete information about a NetCDF file
@@ -44,6 +99,26 @@ pub async fn get_netcdf_info(
     file_path: &str,
     variable: Option<&str>,
     detailed: bool,
+) -> Result<NetCdfInfo> {
+    load_netcdf_info(file_path, variable, detailed, false).await
+}
+
+/// Like [`get_netcdf_info`], but only reads the header of the file through a
+/// single handle, even when `detailed` is set: variables carry no
+/// [`NetCdfStorageInfo`].
+pub async fn get_netcdf_header_info(
+    file_path: &str,
+    variable: Option<&str>,
+    detailed: bool,
+) -> Result<NetCdfInfo> {
+    load_netcdf_info(file_path, variable, detailed, true).await
+}
+
+async fn load_netcdf_info(
+    file_path: &str,
+    variable: Option<&str>,
+    detailed: bool,
+    header_only: bool,
 ) -> Result<NetCdfInfo> {
     // Handle S3 paths - download to temporary file first
     let (temp_file, local_path) = if file_path.starts_with("s3://") {
@@ -54,8 +129,7 @@ pub async fn get_netcdf_info(
             .context("Failed to read S3 file for analysis")?;
 
         // Create temporary file
-        let temp_file =
-            tempfile::NamedTempFile::new().context("Failed to create temporary file")?;
+        let temp_file = crate::storage::temp_file().context("Failed to create temporary file")?;
         let temp_path = temp_file.path().to_path_buf();
 
         debug!("Writing S3 data to temporary path: {:?}", temp_path);
@@ -65,7 +139,7 @@ pub async fn get_netcdf_info(
 
         (Some(temp_file), temp_path.to_string_lossy().to_string())
     } else {
-        (None, file_path.to_string())
+        (None, StorageFactory::local_path(file_path)?.into_owned())
     };
 
     // Open and analyze NetCDF file
@@ -83,6 +157,35 @@ pub async fn get_netcdf_info(
             .map(|metadata| metadata.len())
     };
 
+    let mut info = read_netcdf_info(&file, file_path, variable, detailed, file_size);
+    if detailed && !header_only {
+        for variable in &mut info.variables {
+            match read_storage_info(&file, &local_path, &variable.name) {
+                Ok(storage) => variable.storage = Some(storage),
+                Err(e) => debug!("Could not read storage of '{}': {}", variable.name, e),
+            }
+        }
+    }
+
+    file.close().context("Failed to close NetCDF file")?;
+
+    // Keep temp file alive until after we close the netcdf file
+    drop(temp_file);
+
+    Ok(info)
+}
+
+/// Collects the [`NetCdfInfo`] of an open file; `path` is only recorded.
+///
+/// When `variable` is set, only that variable is described. Global attributes
+/// are only read when `detailed` is set.
+pub fn read_netcdf_info(
+    file: &netcdf::File,
+    path: &str,
+    variable: Option<&str>,
+    detailed: bool,
+    file_size: Option<u64>,
+) -> NetCdfInfo {
     // Extract dimensions
     let mut dimensions = Vec::new();
     for dim in file.dimensions() {
@@ -93,15 +196,18 @@ pub async fn get_netcdf_info(
         });
     }
 
-    // Extract variables
+    // Extract variables, including those in groups
     let mut variables = Vec::new();
-    for var in file.variables() {
+    for path in variable_paths(file) {
         // Skip if specific variable requested and this isn't it
         if let Some(var_name) = variable
-            && var.name() != var_name
+            && path != var_name.strip_prefix('/').unwrap_or(var_name)
         {
             continue;
         }
+        let Some(var) = file.variable(&path) else {
+            continue;
+        };
 
         let mut attributes = HashMap::new();
 
@@ -117,7 +223,7 @@ pub async fn get_netcdf_info(
         let shape: Vec<usize> = var.dimensions().iter().map(|d| d.len()).collect();
 
         variables.push(NetCdfVariableInfo {
-            name: var.name().to_string(),
+            name: path,
             data_type: format_variable_type(&var.vartype()),
             dimensions: var
                 .dimensions()
@@ -126,6 +232,7 @@ pub async fn get_netcdf_info(
                 .collect(),
             attributes,
             shape,
+            storage: None,
         });
     }
 
@@ -140,22 +247,238 @@ pub async fn get_netcdf_info(
         }
     }
 
-    file.close().context("Failed to close NetCDF file")?;
-
-    // Keep temp file alive until after we close the netcdf file
-    drop(temp_file);
-
-    Ok(NetCdfInfo {
-        path: file_path.to_string(),
+    NetCdfInfo {
+        path: path.to_string(),
         total_dimensions: dimensions.len(),
         total_variables: variables.len(),
         dimensions,
         variables,
         global_attributes,
         file_size,
+    }
+}
+
+/// Reads how the variable at `variable_path` of an open file, found at
+/// `local_path`, is stored. Classic files are always contiguous and uncompressed.
+pub fn read_storage_info(
+    file: &netcdf::File,
+    local_path: &str,
+    variable_path: &str,
+) -> Result<NetCdfStorageInfo> {
+    let var = resolve_variable(file, variable_path)?;
+    // Only NetCDF-4 files have groups, chunks and filters
+    if file.groups().is_err() {
+        return Ok(NetCdfStorageInfo {
+            chunk_shape: None,
+            deflate_level: None,
+            shuffle: false,
+        });
+    }
+
+    let chunk_shape = var.chunking()?;
+    let (deflate_level, shuffle) = read_deflate(local_path, variable_path)?;
+    Ok(NetCdfStorageInfo {
+        chunk_shape,
+        deflate_level,
+        shuffle,
     })
 }
 
+/// Reads the deflate level (if deflate compressed) and shuffle flag of a variable
+/// of a NetCDF-4 file.
+///
+/// The netcdf crate does not expose these, so they are queried from the C
+/// library through a second, read-only handle on the file.
+fn read_deflate(local_path: &str, variable_path: &str) -> Result<(Option<i32>, bool)> {
+    use netcdf_sys::{NC_NOWRITE, nc_close, nc_open};
+    use std::ffi::CString;
+
+    let variable_path = variable_path.strip_prefix('/').unwrap_or(variable_path);
+    let (group, name) = match variable_path.rsplit_once('/') {
+        Some((group, name)) => (Some(CString::new(format!("/{}", group))?), name),
+        None => (None, variable_path),
+    };
+    let path = CString::new(local_path)?;
+    let name = CString::new(name)?;
+
+    // The C library is not thread-safe; hold the lock the netcdf crate uses
+    let _guard = netcdf_sys::libnetcdf_lock.lock();
+    let mut ncid = 0;
+    check_status(
+        unsafe { nc_open(path.as_ptr(), NC_NOWRITE, &mut ncid) },
+        "nc_open",
+        variable_path,
+    )?;
+    let result = inquire_deflate(ncid, group.as_deref(), &name, variable_path);
+    unsafe { nc_close(ncid) };
+    result
+}
+
+/// Queries the deflate settings of variable `name` in `group` (the root group
+/// when `None`) of the open file `ncid`; the caller holds the library lock
+fn inquire_deflate(
+    ncid: i32,
+    group: Option<&std::ffi::CStr>,
+    name: &std::ffi::CStr,
+    variable_path: &str,
+) -> Result<(Option<i32>, bool)> {
+    use netcdf_sys::{nc_inq_grp_full_ncid, nc_inq_var_deflate, nc_inq_varid};
+
+    let mut group_id = ncid;
+    if let Some(group) = group {
+        check_status(
+            unsafe { nc_inq_grp_full_ncid(ncid, group.as_ptr(), &mut group_id) },
+            "nc_inq_grp_full_ncid",
+            variable_path,
+        )?;
+    }
+    let mut varid = 0;
+    check_status(
+        unsafe { nc_inq_varid(group_id, name.as_ptr(), &mut varid) },
+        "nc_inq_varid",
+        variable_path,
+    )?;
+    let (mut shuffle, mut deflate, mut deflate_level) = (0, 0, 0);
+    check_status(
+        unsafe {
+            nc_inq_var_deflate(
+                group_id,
+                varid,
+                &mut shuffle,
+                &mut deflate,
+                &mut deflate_level,
+            )
+        },
+        "nc_inq_var_deflate",
+        variable_path,
+    )?;
+    Ok(((deflate != 0).then_some(deflate_level), shuffle != 0))
+}
+
+fn check_status(status: i32, call: &str, variable_path: &str) -> Result<()> {
+    if status != netcdf_sys::NC_NOERR {
+        bail!(
+            "{} failed for '{}' (status {})",
+            call,
+            variable_path,
+            status
+        );
+    }
+    Ok(())
+}
+
+/// Paths of the groups of a file, e.g. `/diagnostics`, with nested groups after
+/// their parent. Classic (non NetCDF-4) files have none.
+pub fn group_paths(file: &netcdf::File) -> Vec<String> {
+    let mut groups = Vec::new();
+    collect_groups(file, &mut groups, &mut Vec::new());
+    groups
+}
+
+/// Names of all variables of a file: root variables by name, followed by the
+/// variables of each group as `group/subgroup/name`.
+pub fn variable_paths(file: &netcdf::File) -> Vec<String> {
+    let mut variables: Vec<String> = file.variables().map(|var| var.name()).collect();
+    collect_groups(file, &mut Vec::new(), &mut variables);
+    variables
+}
+
+fn collect_groups(file: &netcdf::File, groups: &mut Vec<String>, variables: &mut Vec<String>) {
+    fn visit(
+        group: &netcdf::Group,
+        path: String,
+        groups: &mut Vec<String>,
+        variables: &mut Vec<String>,
+    ) {
+        variables.extend(
+            group
+                .variables()
+                .map(|var| format!("{}/{}", path, var.name())),
+        );
+        groups.push(format!("/{}", path));
+        for subgroup in group.groups() {
+            let subpath = format!("{}/{}", path, subgroup.name());
+            visit(&subgroup, subpath, groups, variables);
+        }
+    }
+
+    // Classic files fail to list groups
+    if let Ok(root_groups) = file.groups() {
+        for group in root_groups {
+            let path = group.name();
+            visit(&group, path, groups, variables);
+        }
+    }
+}
+
+/// Opens the variable `name`, which may be qualified with the path of its
+/// NetCDF-4 group, e.g. `diagnostics/temperature` or `/diagnostics/temperature`.
+///
+/// # Errors
+///
+/// Returns an error listing the file's groups when a group of the path does not
+/// exist, or listing its variables (see [`variable_paths`]) when the variable does not.
+pub fn resolve_variable<'f>(file: &'f netcdf::File, name: &str) -> Result<netcdf::Variable<'f>> {
+    let path = name.strip_prefix('/').unwrap_or(name);
+    if path.split('/').any(str::is_empty) {
+        bail!("Invalid variable name '{}'", name);
+    }
+    // Check the groups first: looking up a grouped path in a classic file panics
+    if let Some((group_path, _)) = path.rsplit_once('/')
+        && !matches!(file.group(group_path), Ok(Some(_)))
+    {
+        let groups = group_paths(file);
+        bail!(
+            "Group '/{}' not found in NetCDF file (groups: {})",
+            group_path,
+            if groups.is_empty() {
+                "none".to_string()
+            } else {
+                groups.join(", ")
+            }
+        );
+    }
+    file.variable(path).with_context(|| {
+        format!(
+            "Variable '{}' not found in NetCDF file (variables: {})",
+            name,
+            variable_paths(file).join(", ")
+        )
+    })
+}
+
+/// Names of the data variables of a file, in file order.
+///
+/// Coordinate variables (named after a dimension) and scalar variables (such as
+/// CRS grid mappings) are not data variables.
+pub fn data_variable_names(file: &netcdf::File) -> Vec<String> {
+    let dimension_names: Vec<String> = file.dimensions().map(|dim| dim.name()).collect();
+    file.variables()
+        .filter(|var| !var.dimensions().is_empty() && !dimension_names.contains(&var.name()))
+        .map(|var| var.name())
+        .collect()
+}
+
+/// Selects the variable to convert when none is specified.
+///
+/// Succeeds when the file has exactly one data variable (see
+/// [`data_variable_names`]); otherwise the error lists the candidates.
+pub fn detect_main_variable(file: &netcdf::File) -> Result<String> {
+    let mut candidates = data_variable_names(file);
+    match candidates.len() {
+        0 => anyhow::bail!("No data variable found in NetCDF file; specify one with --variable"),
+        1 => {
+            let name = candidates.remove(0);
+            debug!("Auto-detected data variable: {}", name);
+            Ok(name)
+        }
+        _ => anyhow::bail!(
+            "Several data variables found in NetCDF file ({}); specify one with --variable",
+            candidates.join(", ")
+        ),
+    }
+}
+
 /// Format netcdf attribute value for display
 fn format_attribute_value(value: &netcdf::AttributeValue) -> String {
     format!("{:?}", value)
@@ -190,6 +513,9 @@ pub fn print_file_info_human(info: &NetCdfInfo) {
             var.data_type,
             var.dimensions.join(", ")
         );
+        if let Some(storage) = &var.storage {
+            println!("      storage: {}", storage);
+        }
         if !var.attributes.is_empty() {
             for (name, value) in &var.attributes {
                 println!("      @{}: {}", name, value);
@@ -251,3 +577,473 @@ pub fn print_file_info_csv(info: &NetCdfInfo) -> Result<()> {
     }
     Ok(())
 }
+
+/// Old and new value of something that changed between two files
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValueChange<T> {
+    pub old: T,
+    pub new: T,
+}
+
+/// A dimension or variable that appears under a new name with the same
+/// definition (see [`diff_netcdf_info`])
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Rename {
+    pub old: String,
+    pub new: String,
+}
+
+/// An attribute that was added (`old` is `None`), removed (`new` is `None`) or
+/// given a new value
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AttributeChange {
+    pub name: String,
+    pub old: Option<String>,
+    pub new: Option<String>,
+}
+
+/// A dimension present in both files whose length or unlimited flag changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DimensionChange {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<ValueChange<usize>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_unlimited: Option<ValueChange<bool>>,
+}
+
+/// A variable present in both files whose definition or attributes changed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct VariableChange {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data_type: Option<ValueChange<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<ValueChange<Vec<String>>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shape: Option<ValueChange<Vec<usize>>>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attributes: Vec<AttributeChange>,
+}
+
+/// Structural differences between two NetCDF files, from the `old` file to the
+/// `new` one (see [`diff_netcdf_info`])
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NetCdfDiff {
+    pub old_path: String,
+    pub new_path: String,
+    pub added_dimensions: Vec<NetCdfDimensionInfo>,
+    pub removed_dimensions: Vec<NetCdfDimensionInfo>,
+    pub renamed_dimensions: Vec<Rename>,
+    pub changed_dimensions: Vec<DimensionChange>,
+    pub added_variables: Vec<String>,
+    pub removed_variables: Vec<String>,
+    pub renamed_variables: Vec<Rename>,
+    pub changed_variables: Vec<VariableChange>,
+    pub changed_global_attributes: Vec<AttributeChange>,
+}
+
+/// One line of a [`NetCdfDiff`] in tabular form
+#[derive(Debug, Clone, PartialEq)]
+pub struct DiffEntry {
+    /// `added`, `removed`, `renamed` or `changed`
+    pub change: &'static str,
+    /// `dimension`, `variable` or `attribute`
+    pub kind: &'static str,
+    /// Name of the item; attributes are named `variable@attribute`, or
+    /// `@attribute` for global attributes
+    pub name: String,
+    pub details: String,
+}
+
+impl NetCdfDiff {
+    /// Whether the two files have the same structure
+    pub fn is_empty(&self) -> bool {
+        self.added_dimensions.is_empty()
+            && self.removed_dimensions.is_empty()
+            && self.renamed_dimensions.is_empty()
+            && self.changed_dimensions.is_empty()
+            && self.added_variables.is_empty()
+            && self.removed_variables.is_empty()
+            && self.renamed_variables.is_empty()
+            && self.changed_variables.is_empty()
+            && self.changed_global_attributes.is_empty()
+    }
+
+    /// Lists the differences one per line, dimensions first, then variables and
+    /// their attributes, then global attributes
+    pub fn entries(&self) -> Vec<DiffEntry> {
+        let entry = |change, kind, name: &str, details: String| DiffEntry {
+            change,
+            kind,
+            name: name.to_string(),
+            details,
+        };
+        let mut entries = Vec::new();
+
+        for dim in &self.added_dimensions {
+            entries.push(entry(
+                "added",
+                "dimension",
+                &dim.name,
+                dimension_details(dim),
+            ));
+        }
+        for dim in &self.removed_dimensions {
+            entries.push(entry(
+                "removed",
+                "dimension",
+                &dim.name,
+                dimension_details(dim),
+            ));
+        }
+        for rename in &self.renamed_dimensions {
+            entries.push(entry(
+                "renamed",
+                "dimension",
+                &rename.old,
+                format!("-> {}", rename.new),
+            ));
+        }
+        for change in &self.changed_dimensions {
+            if let Some(length) = &change.length {
+                entries.push(entry(
+                    "changed",
+                    "dimension",
+                    &change.name,
+                    format!("length {} -> {}", length.old, length.new),
+                ));
+            }
+            if let Some(unlimited) = &change.is_unlimited {
+                entries.push(entry(
+                    "changed",
+                    "dimension",
+                    &change.name,
+                    format!("unlimited {} -> {}", unlimited.old, unlimited.new),
+                ));
+            }
+        }
+
+        for name in &self.added_variables {
+            entries.push(entry("added", "variable", name, String::new()));
+        }
+        for name in &self.removed_variables {
+            entries.push(entry("removed", "variable", name, String::new()));
+        }
+        for rename in &self.renamed_variables {
+            entries.push(entry(
+                "renamed",
+                "variable",
+                &rename.old,
+                format!("-> {}", rename.new),
+            ));
+        }
+        for change in &self.changed_variables {
+            if let Some(data_type) = &change.data_type {
+                entries.push(entry(
+                    "changed",
+                    "variable",
+                    &change.name,
+                    format!("type {} -> {}", data_type.old, data_type.new),
+                ));
+            }
+            if let Some(dimensions) = &change.dimensions {
+                entries.push(entry(
+                    "changed",
+                    "variable",
+                    &change.name,
+                    format!(
+                        "dimensions [{}] -> [{}]",
+                        dimensions.old.join(", "),
+                        dimensions.new.join(", ")
+                    ),
+                ));
+            }
+            if let Some(shape) = &change.shape {
+                entries.push(entry(
+                    "changed",
+                    "variable",
+                    &change.name,
+                    format!("shape {:?} -> {:?}", shape.old, shape.new),
+                ));
+            }
+            for attribute in &change.attributes {
+                entries.push(attribute_entry(&change.name, attribute));
+            }
+        }
+
+        for attribute in &self.changed_global_attributes {
+            entries.push(attribute_entry("", attribute));
+        }
+        entries
+    }
+}
+
+fn dimension_details(dim: &NetCdfDimensionInfo) -> String {
+    if dim.is_unlimited {
+        format!("length {}, unlimited", dim.length)
+    } else {
+        format!("length {}", dim.length)
+    }
+}
+
+fn attribute_entry(variable: &str, attribute: &AttributeChange) -> DiffEntry {
+    let (change, details) = match (&attribute.old, &attribute.new) {
+        (None, Some(new)) => ("added", new.clone()),
+        (Some(old), None) => ("removed", old.clone()),
+        (old, new) => (
+            "changed",
+            format!(
+                "{} -> {}",
+                old.as_deref().unwrap_or_default(),
+                new.as_deref().unwrap_or_default()
+            ),
+        ),
+    };
+    DiffEntry {
+        change,
+        kind: "attribute",
+        name: format!("{}@{}", variable, attribute.name),
+        details,
+    }
+}
+
+/// Compares the structure of two files described by [`get_netcdf_info`].
+///
+/// A dimension that is only in `old` and one that is only in `new` are reported
+/// as a rename when they have the same length and unlimited flag, and no other
+/// dimension matches either of them; likewise for variables with the same type,
+/// shape and attributes. Global attributes are only compared when both infos
+/// are detailed.
+pub fn diff_netcdf_info(old: &NetCdfInfo, new: &NetCdfInfo) -> NetCdfDiff {
+    let old_dims: HashMap<&str, &NetCdfDimensionInfo> = old
+        .dimensions
+        .iter()
+        .map(|dim| (dim.name.as_str(), dim))
+        .collect();
+    let new_dims: HashMap<&str, &NetCdfDimensionInfo> = new
+        .dimensions
+        .iter()
+        .map(|dim| (dim.name.as_str(), dim))
+        .collect();
+
+    let mut changed_dimensions = Vec::new();
+    for dim in &old.dimensions {
+        let Some(new_dim) = new_dims.get(dim.name.as_str()) else {
+            continue;
+        };
+        let change = DimensionChange {
+            name: dim.name.clone(),
+            length: value_change(dim.length, new_dim.length),
+            is_unlimited: value_change(dim.is_unlimited, new_dim.is_unlimited),
+        };
+        if change.length.is_some() || change.is_unlimited.is_some() {
+            changed_dimensions.push(change);
+        }
+    }
+    let removed: Vec<&NetCdfDimensionInfo> = old
+        .dimensions
+        .iter()
+        .filter(|dim| !new_dims.contains_key(dim.name.as_str()))
+        .collect();
+    let added: Vec<&NetCdfDimensionInfo> = new
+        .dimensions
+        .iter()
+        .filter(|dim| !old_dims.contains_key(dim.name.as_str()))
+        .collect();
+    let (renamed_dimensions, removed_dimensions, added_dimensions) = match_renames(
+        removed,
+        added,
+        |dim| &dim.name,
+        |a, b| a.length == b.length && a.is_unlimited == b.is_unlimited,
+    );
+
+    let old_vars: HashMap<&str, &NetCdfVariableInfo> = old
+        .variables
+        .iter()
+        .map(|var| (var.name.as_str(), var))
+        .collect();
+    let new_vars: HashMap<&str, &NetCdfVariableInfo> = new
+        .variables
+        .iter()
+        .map(|var| (var.name.as_str(), var))
+        .collect();
+
+    let mut changed_variables = Vec::new();
+    for var in &old.variables {
+        let Some(new_var) = new_vars.get(var.name.as_str()) else {
+            continue;
+        };
+        let change = VariableChange {
+            name: var.name.clone(),
+            data_type: value_change(var.data_type.clone(), new_var.data_type.clone()),
+            dimensions: value_change(var.dimensions.clone(), new_var.dimensions.clone()),
+            shape: value_change(var.shape.clone(), new_var.shape.clone()),
+            attributes: diff_attributes(&var.attributes, &new_var.attributes),
+        };
+        if change.data_type.is_some()
+            || change.dimensions.is_some()
+            || change.shape.is_some()
+            || !change.attributes.is_empty()
+        {
+            changed_variables.push(change);
+        }
+    }
+    let removed: Vec<&NetCdfVariableInfo> = old
+        .variables
+        .iter()
+        .filter(|var| !new_vars.contains_key(var.name.as_str()))
+        .collect();
+    let added: Vec<&NetCdfVariableInfo> = new
+        .variables
+        .iter()
+        .filter(|var| !old_vars.contains_key(var.name.as_str()))
+        .collect();
+    let (renamed_variables, removed_variables, added_variables) = match_renames(
+        removed,
+        added,
+        |var| &var.name,
+        |a, b| a.data_type == b.data_type && a.shape == b.shape && a.attributes == b.attributes,
+    );
+
+    NetCdfDiff {
+        old_path: old.path.clone(),
+        new_path: new.path.clone(),
+        added_dimensions: added_dimensions.into_iter().cloned().collect(),
+        removed_dimensions: removed_dimensions.into_iter().cloned().collect(),
+        renamed_dimensions,
+        changed_dimensions,
+        added_variables: added_variables
+            .into_iter()
+            .map(|var| var.name.clone())
+            .collect(),
+        removed_variables: removed_variables
+            .into_iter()
+            .map(|var| var.name.clone())
+            .collect(),
+        renamed_variables,
+        changed_variables,
+        changed_global_attributes: diff_attributes(&old.global_attributes, &new.global_attributes),
+    }
+}
+
+fn value_change<T: PartialEq>(old: T, new: T) -> Option<ValueChange<T>> {
+    (old != new).then_some(ValueChange { old, new })
+}
+
+/// Attribute changes between two attribute maps, sorted by name
+fn diff_attributes(
+    old: &HashMap<String, String>,
+    new: &HashMap<String, String>,
+) -> Vec<AttributeChange> {
+    let mut names: Vec<&String> = old.keys().chain(new.keys()).collect();
+    names.sort();
+    names.dedup();
+    names
+        .into_iter()
+        .filter(|name| old.get(*name) != new.get(*name))
+        .map(|name| AttributeChange {
+            name: name.clone(),
+            old: old.get(name).cloned(),
+            new: new.get(name).cloned(),
+        })
+        .collect()
+}
+
+/// Pairs removed and added items that `same` considers identical, when the
+/// pairing is unambiguous. Returns the renames and the remaining removed and
+/// added items.
+fn match_renames<'a, T>(
+    removed: Vec<&'a T>,
+    added: Vec<&'a T>,
+    name: impl Fn(&T) -> &String,
+    same: impl Fn(&T, &T) -> bool,
+) -> (Vec<Rename>, Vec<&'a T>, Vec<&'a T>) {
+    let mut renames = Vec::new();
+    let mut renamed_added = vec![false; added.len()];
+    let mut unmatched = Vec::new();
+    for old in &removed {
+        let candidates: Vec<usize> = (0..added.len()).filter(|&i| same(old, added[i])).collect();
+        let unique = match candidates.as_slice() {
+            [i] => {
+                removed
+                    .iter()
+                    .filter(|other| same(other, added[*i]))
+                    .count()
+                    == 1
+            }
+            _ => false,
+        };
+        if unique {
+            renamed_added[candidates[0]] = true;
+            renames.push(Rename {
+                old: name(old).clone(),
+                new: name(added[candidates[0]]).clone(),
+            });
+        } else {
+            unmatched.push(*old);
+        }
+    }
+    let added = added
+        .into_iter()
+        .zip(renamed_added)
+        .filter(|(_, renamed)| !renamed)
+        .map(|(item, _)| item)
+        .collect();
+    (renames, unmatched, added)
+}
+
+/// Print a file comparison in human-readable format, as a table
+pub fn print_netcdf_diff_human(diff: &NetCdfDiff) {
+    println!("Comparing {} -> {}", diff.old_path, diff.new_path);
+    let entries = diff.entries();
+    if entries.is_empty() {
+        println!("  No structural differences");
+        return;
+    }
+    let name_width = entries
+        .iter()
+        .map(|entry| entry.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("name".len());
+    println!(
+        "  {:<8} {:<10} {:<name_width$} details",
+        "change", "kind", "name"
+    );
+    for entry in &entries {
+        println!(
+            "  {:<8} {:<10} {:<name_width$} {}",
+            entry.change, entry.kind, entry.name, entry.details
+        );
+    }
+}
+
+/// Print a file comparison in JSON format
+pub fn print_netcdf_diff_json(diff: &NetCdfDiff) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(diff)?);
+    Ok(())
+}
+
+/// Print a file comparison in YAML format
+pub fn print_netcdf_diff_yaml(diff: &NetCdfDiff) -> Result<()> {
+    let yaml =
+        serde_yaml::to_string(diff).context("Failed to serialize file comparison to YAML")?;
+    println!("{}", yaml);
+    Ok(())
+}
+
+/// Print a file comparison in CSV format (one row per difference)
+pub fn print_netcdf_diff_csv(diff: &NetCdfDiff) -> Result<()> {
+    println!("change,kind,name,details");
+    for entry in diff.entries() {
+        println!(
+            "{},{},{},\"{}\"",
+            entry.change,
+            entry.kind,
+            entry.name,
+            entry.details.replace('"', "\"\"")
+        );
+    }
+    Ok(())
+}
@@ -8,21 +8,88 @@
 //!
 //! A configuration file specifies:
 //! - **nc_key**: Path to the input NetCDF file
-//! - **variable_name**: Name of the variable to extract from the NetCDF file
+//! - **variable_name**: Name of the variable to extract from the NetCDF file (optional when
+//!   the file has a single data variable)
 //! - **parquet_key**: Path for the output Parquet file
 //! - **filters**: Array of filters to apply during extraction
+//! - **filters_dsl**: Filters in the compact syntax of the command line, e.g. `range latitude:30:60`
+//! - **coordinate_transforms**: Optional affine transforms applied to coordinate columns
+//! - **coordinate_renames**: Optional output names of coordinate columns, by dimension
+//! - **sort_coordinates**: Order coordinate columns by dimension name instead of file order
+//! - **coordinate_order**: Dimensions whose coordinate columns come first, in order
+//! - **limit**: Optional cap on the number of rows written
+//! - **sample**: Optional random sample of coordinate combinations to extract
+//! - **decode_times**: Convert CF time coordinates to UTC datetimes
+//! - **include_indices**: Add the NetCDF index of each row along every dimension
+//! - **write_cell_index**: Write a companion index mapping coordinate indices to rows
+//! - **write_manifest**: Write a companion JSON manifest describing the output's contents
+//! - **start_record**: Optional first index along the unlimited dimension to extract
+//! - **rename_from_attributes**: Name the value column after the variable's CF `standard_name`
+//!   or `long_name` attribute
+//! - **variable_overrides**: Per-variable fill value, valid range and packing that replace the
+//!   file's attributes
+//! - **nan_policy**: Keep (default), drop, or reject NaN values of the variable
+//! - **combine**: Intersect (`and`, default) or unite (`or`) filters on the same dimension
+//! - **fail_on_empty**: Fail instead of writing an empty output when the filters select no rows
+//! - **round_decimals**: Optional number of decimals to round float columns to before writing
 //!
 //! ## Filter Types
 //!
-//! The module supports four types of filters:
+//! The module supports nine types of filters:
 //! - **Range filters**: Select values within a numeric range
 //! - **List filters**: Select specific discrete values
 //! - **2D Point filters**: Select spatial coordinates with tolerance
 //! - **3D Point filters**: Select spatiotemporal coordinates with tolerance
+//! - **3D bounding box filters**: Select the cells inside a time window and a latitude/longitude box
+//! - **Stride filters**: Select every Nth index along a dimension
+//! - **Index range filters**: Select a slice of indices, without reading coordinate values
+//! - **Time range filters**: Select the steps between two ISO 8601 times, decoded with the
+//!   time variable's CF `units`
+//! - **Nearest time filters**: Select the N time steps closest to a raw value or ISO 8601 time
 //!
-use crate::filters::{NC2DPointFilter, NC3DPointFilter, NCFilter, NCListFilter, NCRangeFilter};
+//! ## Building Configurations in Code
+//!
+//! [`JobConfigBuilder`] sets the fields one at a time, leaving the rest at their
+//! defaults:
+//!
+//! ```rust
+//! use nc2parquet::input::{FilterConfig, JobConfig, RangeParams};
+//!
+//! let config = JobConfig::builder()
+//!     .input("data/temperature.nc")
+//!     .variable("temperature")
+//!     .output("output/temperature.parquet")
+//!     .add_filter(FilterConfig::Range {
+//!         params: RangeParams {
+//!             dimension_name: "latitude".to_string(),
+//!             min_value: 30.0,
+//!             max_value: 60.0,
+//!             normalize_longitude: false,
+//!             min_inclusive: true,
+//!             max_inclusive: true,
+//!             enabled: true,
+//!         },
+//!     })
+//!     .limit(1000)
+//!     .build()
+//!     .unwrap();
+//! assert_eq!(config.filters.len(), 1);
+//! ```
+//!
+use crate::error::{Nc2ParquetError, Nc2ParquetResult};
+use crate::extract::{
+    CoordTransform, ExtractOptions, FilterCombine, NanPolicy, SampleConfig, VarOverride,
+};
+use crate::filters::{
+    NC2DPointFilter, NC3DBoundingBoxFilter, NC3DPointFilter, NCFilter, NCIndexRangeFilter,
+    NCListFilter, NCNearestTimeFilter, NCRangeFilter, NCStrideFilter, NCTimeRangeFilter,
+    default_true,
+};
+use crate::output::{CompressionCodec, OutputOptions};
 use crate::postprocess::ProcessingPipelineConfig;
+use crate::storage::{StorageFactory, StorageResult};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -30,15 +97,123 @@ use std::path::Path;
 ///
 /// This struct represents the complete configuration needed to process a NetCDF file,
 /// including input specifications, variable selection, filtering criteria, and output destination.
-#[derive(Deserialize, Serialize, Clone)]
+///
+/// Besides a JSON file or a struct literal, a configuration can be assembled with
+/// [`JobConfig::builder`], which keeps compiling as new fields are added.
+#[derive(Deserialize, Serialize, Clone, Default)]
 pub struct JobConfig {
     pub nc_key: String,
+    /// Variable to extract; when empty, the file's only data variable is used
+    #[serde(default)]
     pub variable_name: String,
+    /// Variables written to separate outputs when `split_variables` is set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variable_names: Vec<String>,
+    /// Write one output per variable of `variable_names` (every data variable
+    /// when empty), named after `parquet_key` (see [`crate::split_output_path`])
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub split_variables: bool,
+    #[serde(default)]
     pub filters: Vec<FilterConfig>,
+    /// Filters in the compact syntax of the command line, e.g.
+    /// `range latitude:30:60` (see [`crate::cli::parse_filter_dsl`]); they are
+    /// appended to `filters` when the configuration is loaded
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub filters_dsl: Vec<String>,
     pub parquet_key: String,
     /// Optional post-processing pipeline configuration
     #[serde(skip_serializing_if = "Option::is_none")]
     pub postprocessing: Option<ProcessingPipelineConfig>,
+    /// Affine transforms applied to coordinate columns, keyed by dimension name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub coordinate_transforms: HashMap<String, CoordTransform>,
+    /// Output names of coordinate columns, keyed by dimension name (e.g.
+    /// `longitude` to `lon`), applied during extraction
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub coordinate_renames: HashMap<String, String>,
+    /// Order coordinate columns by dimension name instead of following the
+    /// variable's dimensions, so files storing them differently share a schema
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub sort_coordinates: bool,
+    /// Dimensions whose coordinate columns come first, in this order; the others
+    /// follow as usual
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub coordinate_order: Vec<String>,
+    /// Maximum number of rows to write, applied after post-processing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// Random sample of coordinate combinations to extract, applied after filtering
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample: Option<SampleConfig>,
+    /// Convert coordinate columns with CF time units to UTC datetimes
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub decode_times: bool,
+    /// Add a `<dimension>_idx` column per dimension holding each row's NetCDF index
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub include_indices: bool,
+    /// Write a `<parquet_key>.cellidx` file mapping coordinate index tuples to rows
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub write_cell_index: bool,
+    /// Write a `<parquet_key>.json` manifest describing the output's columns, row
+    /// count and source
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub write_manifest: bool,
+    /// First index to extract along the unlimited (record) dimension
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_record: Option<usize>,
+    /// Name the value column after the variable's `standard_name` (or `long_name`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub rename_from_attributes: bool,
+    /// Replacements for the fill value, valid range and packing attributes of
+    /// variables, keyed by variable name
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub variable_overrides: HashMap<String, VarOverride>,
+    /// Keep, drop or reject NaN values of the variable
+    #[serde(default, skip_serializing_if = "NanPolicy::is_keep")]
+    pub nan_policy: NanPolicy,
+    /// Intersect (`and`) or unite (`or`) filters on the same dimension; filters on
+    /// different dimensions always intersect
+    #[serde(default, skip_serializing_if = "FilterCombine::is_and")]
+    pub combine: FilterCombine,
+    /// Fail when the filters select no rows, instead of writing an empty output
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fail_on_empty: bool,
+    /// Output columns to add a Parquet Bloom filter on, for fast point lookups
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parquet_bloom_filter_columns: Vec<String>,
+    /// Target units by quantity (e.g. `temperature` to `celsius`); the value column
+    /// is converted according to its `units` attribute before post-processing
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub auto_unit_convert: HashMap<String, String>,
+    /// Round float columns to this many decimals after post-processing, just
+    /// before writing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub round_decimals: Option<u32>,
+    /// Columns rounded by `round_decimals`; every float value column when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub round_columns: Vec<String>,
+    /// Also round the coordinate columns when `round_columns` is empty
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub round_coordinates: bool,
+    /// Remove the coordinate columns (and their index columns) after
+    /// post-processing, leaving only the value columns
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub drop_coordinates: bool,
+    /// Compression codec of the Parquet output
+    #[serde(default, skip_serializing_if = "CompressionCodec::is_default")]
+    pub compression: CompressionCodec,
+    /// Level of `compression` (zstd 1-22, gzip 0-9, brotli 0-11); the codec's
+    /// default when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub compression_level: Option<i32>,
+    /// Whether to write min/max and null count statistics for each column chunk,
+    /// which query engines use to skip row groups; written when absent
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub statistics: Option<bool>,
+    /// AWS region of S3 inputs and outputs; resolved from the ambient AWS
+    /// configuration when absent (see [`crate::storage::set_aws_region`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aws_region: Option<String>,
 }
 
 /// Enumeration of all supported filter configurations.
@@ -57,6 +232,16 @@ pub enum FilterConfig {
     Point2D { params: Point2DParams },
     #[serde(rename = "3d_point")]
     Point3D { params: Point3DParams },
+    #[serde(rename = "bbox_3d")]
+    BoundingBox3D { params: BoundingBox3DParams },
+    #[serde(rename = "stride")]
+    Stride { params: StrideParams },
+    #[serde(rename = "index_range")]
+    IndexRange { params: IndexRangeParams },
+    #[serde(rename = "time_range")]
+    TimeRange { params: TimeRangeParams },
+    #[serde(rename = "nearest_time")]
+    NearestTime { params: NearestTimeParams },
 }
 
 /// Parameters for range-based filtering.
@@ -67,6 +252,24 @@ pub struct RangeParams {
     pub dimension_name: String,
     pub min_value: f64,
     pub max_value: f64,
+    /// Treat the dimension as longitude and compare values in the -180..180 convention
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub normalize_longitude: bool,
+    /// Whether values equal to `min_value` are selected
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub min_inclusive: bool,
+    /// Whether values equal to `max_value` are selected; set to `false` for
+    /// half-open bands that tile an axis without overlap
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub max_inclusive: bool,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+fn is_true(value: &bool) -> bool {
+    *value
 }
 
 /// Parameters for list-based filtering.
@@ -76,6 +279,13 @@ pub struct RangeParams {
 pub struct ListParams {
     pub dimension_name: String,
     pub values: Vec<f64>,
+    /// Match coordinates within this distance of a value instead of exactly
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tolerance: Option<f64>,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
 }
 
 /// Parameters for 2D spatial point filtering.
@@ -87,6 +297,16 @@ pub struct Point2DParams {
     pub lon_dimension_name: String,
     pub points: Vec<(f64, f64)>,
     pub tolerance: f64,
+    /// Fall back to the closest grid cell when no point is within tolerance
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub snap_nearest: bool,
+    /// Compare longitudes in the -180..180 convention, whatever the file uses
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub normalize_longitude: bool,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
 }
 
 /// Parameters for 3D spatiotemporal point filtering.
@@ -97,12 +317,139 @@ pub struct Point3DParams {
     pub time_dimension_name: String,
     pub lat_dimension_name: String,
     pub lon_dimension_name: String,
+    /// Raw values of the time coordinate to select
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub steps: Vec<f64>,
+    /// ISO 8601 times to select, converted with the time variable's CF `units`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub step_dates: Vec<String>,
     pub points: Vec<(f64, f64)>,
     pub tolerance: f64,
+    /// Compare longitudes in the -180..180 convention, whatever the file uses
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub normalize_longitude: bool,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+/// Parameters for 3D bounding box filtering.
+///
+/// Selects the (time, lat, lon) cells inside a time window and a latitude/longitude
+/// box, all bounds included. The window is given either as raw time values
+/// (`start` and `end`) or as ISO 8601 times (`start_date` and `end_date`) converted
+/// with the time variable's `units` attribute.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct BoundingBox3DParams {
+    pub time_dimension_name: String,
+    pub lat_dimension_name: String,
+    pub lon_dimension_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub start_date: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<String>,
+    pub min_lat: f64,
+    pub max_lat: f64,
+    pub min_lon: f64,
+    pub max_lon: f64,
+    /// Compare longitudes in the -180..180 convention, whatever the file uses
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub normalize_longitude: bool,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+/// Parameters for stride-based filtering.
+///
+/// Selects every `stride`-th index along a dimension, beginning at `start`,
+/// optionally limited to `count` indices.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct StrideParams {
+    pub dimension_name: String,
+    #[serde(default)]
+    pub start: usize,
+    pub stride: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub count: Option<usize>,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+/// Parameters for index-based range filtering.
+///
+/// Selects the indices `start..end` of a dimension (`end` excluded), every
+/// `step`-th, like the slice `dimension[start:end:step]`.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct IndexRangeParams {
+    pub dimension_name: String,
+    #[serde(default)]
+    pub start: usize,
+    pub end: usize,
+    #[serde(default = "default_step", skip_serializing_if = "is_one")]
+    pub step: usize,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+/// Parameters for time range filtering.
+///
+/// Selects the steps of a CF time axis between two ISO 8601 times (both
+/// included), converted with the time variable's `units` attribute.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct TimeRangeParams {
+    pub time_dimension_name: String,
+    pub start_date: String,
+    pub end_date: String,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+/// Parameters for nearest time filtering.
+///
+/// Selects the `n` time steps closest to a target, given either as a raw value
+/// of the time coordinate (`target`) or as an ISO 8601 time (`target_date`)
+/// converted with the time variable's `units` attribute.
+#[derive(Deserialize, Serialize, Clone)]
+pub struct NearestTimeParams {
+    pub time_dimension_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target_date: Option<String>,
+    pub n: usize,
+    /// Whether the filter is applied; a disabled filter is skipped but keeps
+    /// its parameters
+    #[serde(default = "default_true", skip_serializing_if = "is_true")]
+    pub enabled: bool,
+}
+
+fn default_step() -> usize {
+    1
+}
+
+fn is_one(value: &usize) -> bool {
+    *value == 1
 }
 
 impl JobConfig {
+    /// Returns a builder for a job configuration; see [`JobConfigBuilder`].
+    pub fn builder() -> JobConfigBuilder {
+        JobConfigBuilder::default()
+    }
+
     /// Loads a job configuration from a JSON file.
     ///
     /// This function reads and parses a JSON configuration file, validating
@@ -118,7 +465,8 @@ impl JobConfig {
     /// read or the JSON is invalid.
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, Box<dyn std::error::Error>> {
         let content = fs::read_to_string(path)?;
-        let config: JobConfig = serde_json::from_str(&content)?;
+        let mut config: JobConfig = serde_json::from_str(&content)?;
+        config.expand_filters_dsl()?;
         Ok(config)
     }
 
@@ -135,9 +483,280 @@ impl JobConfig {
     ///
     /// Returns `Ok(JobConfig)` on success, or an error if the JSON is invalid.
     pub fn from_json(json_str: &str) -> Result<Self, Box<dyn std::error::Error>> {
-        let config: JobConfig = serde_json::from_str(json_str)?;
+        let mut config: JobConfig = serde_json::from_str(json_str)?;
+        config.expand_filters_dsl()?;
         Ok(config)
     }
+
+    /// Parses the compact filters of `filters_dsl` and appends them to
+    /// `filters`, leaving `filters_dsl` empty.
+    ///
+    /// Errors name the offending entry by its position (from 1) and text.
+    pub fn expand_filters_dsl(&mut self) -> Nc2ParquetResult<()> {
+        for (i, line) in std::mem::take(&mut self.filters_dsl).iter().enumerate() {
+            let filter = crate::cli::parse_filter_dsl(line).map_err(|e| {
+                Nc2ParquetError::InvalidConfig(format!(
+                    "Invalid filters_dsl entry {} '{}': {}",
+                    i + 1,
+                    line,
+                    e
+                ))
+            })?;
+            self.filters.push(filter);
+        }
+        Ok(())
+    }
+
+    /// Returns the extraction options (coordinate transforms, sampling and overrides) of this job.
+    pub fn extract_options(&self) -> ExtractOptions {
+        ExtractOptions {
+            coordinate_transforms: self.coordinate_transforms.clone(),
+            coordinate_renames: self.coordinate_renames.clone(),
+            sample: self.sample,
+            serial: false,
+            decode_times: self.decode_times,
+            start_record: self.start_record,
+            variable_overrides: self.variable_overrides.clone(),
+            nan_policy: self.nan_policy,
+            combine: self.combine,
+            include_indices: self.include_indices,
+            sort_coordinates: self.sort_coordinates,
+            coordinate_order: self.coordinate_order.clone(),
+        }
+    }
+
+    /// Returns the Parquet output options (compression and Bloom filters) of this job.
+    pub fn output_options(&self) -> OutputOptions {
+        OutputOptions {
+            compression: self.compression,
+            compression_level: self.compression_level,
+            bloom_filter_columns: self.parquet_bloom_filter_columns.clone(),
+            statistics: self.statistics.unwrap_or(true),
+        }
+    }
+
+    /// Replaces `file://` URIs in the input and output keys by the local paths
+    /// they name (see [`StorageFactory::local_path`]).
+    pub fn resolve_file_uris(&mut self) -> StorageResult<()> {
+        self.nc_key = StorageFactory::local_path(&self.nc_key)?.into_owned();
+        self.parquet_key = StorageFactory::local_path(&self.parquet_key)?.into_owned();
+        Ok(())
+    }
+}
+
+/// Fluent builder for [`JobConfig`].
+///
+/// `input` and `output` are required; every other field keeps its default (no
+/// filters, no post-processing, zstd compression, ...) unless set.
+#[derive(Clone, Default)]
+pub struct JobConfigBuilder {
+    config: JobConfig,
+}
+
+impl JobConfigBuilder {
+    /// Sets the input NetCDF file (`nc_key`), local, gzip-compressed or on S3
+    pub fn input(mut self, nc_key: &str) -> Self {
+        self.config.nc_key = nc_key.to_string();
+        self
+    }
+
+    /// Sets the variable to extract; the file's only data variable is used when unset
+    pub fn variable(mut self, variable_name: &str) -> Self {
+        self.config.variable_name = variable_name.to_string();
+        self
+    }
+
+    /// Adds a variable to write to its own output; see [`Self::split_variables`]
+    pub fn add_variable(mut self, variable_name: &str) -> Self {
+        self.config.variable_names.push(variable_name.to_string());
+        self
+    }
+
+    /// Writes one output per variable instead of a single file
+    pub fn split_variables(mut self, split_variables: bool) -> Self {
+        self.config.split_variables = split_variables;
+        self
+    }
+
+    /// Sets the output Parquet file (`parquet_key`), local or on S3
+    pub fn output(mut self, parquet_key: &str) -> Self {
+        self.config.parquet_key = parquet_key.to_string();
+        self
+    }
+
+    /// Adds a filter, combined with the others according to `combine`
+    pub fn add_filter(mut self, filter: FilterConfig) -> Self {
+        self.config.filters.push(filter);
+        self
+    }
+
+    pub fn postprocessing(mut self, postprocessing: ProcessingPipelineConfig) -> Self {
+        self.config.postprocessing = Some(postprocessing);
+        self
+    }
+
+    pub fn coordinate_transform(mut self, dimension: &str, transform: CoordTransform) -> Self {
+        self.config
+            .coordinate_transforms
+            .insert(dimension.to_string(), transform);
+        self
+    }
+
+    /// Names the coordinate column of `dimension` `name` in the output
+    pub fn rename_coordinate(mut self, dimension: &str, name: &str) -> Self {
+        self.config
+            .coordinate_renames
+            .insert(dimension.to_string(), name.to_string());
+        self
+    }
+
+    /// Orders coordinate columns by dimension name; see [`JobConfig::sort_coordinates`]
+    pub fn sort_coordinates(mut self, sort_coordinates: bool) -> Self {
+        self.config.sort_coordinates = sort_coordinates;
+        self
+    }
+
+    /// Places the coordinate columns of `dimensions` first, in this order
+    pub fn coordinate_order(mut self, dimensions: &[&str]) -> Self {
+        self.config.coordinate_order = dimensions.iter().map(|d| d.to_string()).collect();
+        self
+    }
+
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.config.limit = Some(limit);
+        self
+    }
+
+    pub fn sample(mut self, sample: SampleConfig) -> Self {
+        self.config.sample = Some(sample);
+        self
+    }
+
+    pub fn decode_times(mut self, decode_times: bool) -> Self {
+        self.config.decode_times = decode_times;
+        self
+    }
+
+    pub fn include_indices(mut self, include_indices: bool) -> Self {
+        self.config.include_indices = include_indices;
+        self
+    }
+
+    pub fn write_cell_index(mut self, write_cell_index: bool) -> Self {
+        self.config.write_cell_index = write_cell_index;
+        self
+    }
+
+    pub fn write_manifest(mut self, write_manifest: bool) -> Self {
+        self.config.write_manifest = write_manifest;
+        self
+    }
+
+    pub fn start_record(mut self, start_record: usize) -> Self {
+        self.config.start_record = Some(start_record);
+        self
+    }
+
+    pub fn rename_from_attributes(mut self, rename_from_attributes: bool) -> Self {
+        self.config.rename_from_attributes = rename_from_attributes;
+        self
+    }
+
+    pub fn variable_override(mut self, variable: &str, variable_override: VarOverride) -> Self {
+        self.config
+            .variable_overrides
+            .insert(variable.to_string(), variable_override);
+        self
+    }
+
+    pub fn nan_policy(mut self, nan_policy: NanPolicy) -> Self {
+        self.config.nan_policy = nan_policy;
+        self
+    }
+
+    pub fn combine(mut self, combine: FilterCombine) -> Self {
+        self.config.combine = combine;
+        self
+    }
+
+    /// Fails the job when the filters select no rows; see [`JobConfig::fail_on_empty`]
+    pub fn fail_on_empty(mut self, fail_on_empty: bool) -> Self {
+        self.config.fail_on_empty = fail_on_empty;
+        self
+    }
+
+    pub fn add_bloom_filter_column(mut self, column: &str) -> Self {
+        self.config
+            .parquet_bloom_filter_columns
+            .push(column.to_string());
+        self
+    }
+
+    /// Converts the value column of `quantity` (e.g. `temperature`) to `unit`
+    pub fn auto_unit_convert(mut self, quantity: &str, unit: &str) -> Self {
+        self.config
+            .auto_unit_convert
+            .insert(quantity.to_string(), unit.to_string());
+        self
+    }
+
+    pub fn round_decimals(mut self, decimals: u32) -> Self {
+        self.config.round_decimals = Some(decimals);
+        self
+    }
+
+    pub fn round_columns(mut self, columns: Vec<String>) -> Self {
+        self.config.round_columns = columns;
+        self
+    }
+
+    pub fn round_coordinates(mut self, round_coordinates: bool) -> Self {
+        self.config.round_coordinates = round_coordinates;
+        self
+    }
+
+    /// Removes the coordinate columns from the output; see [`JobConfig::drop_coordinates`]
+    pub fn drop_coordinates(mut self, drop_coordinates: bool) -> Self {
+        self.config.drop_coordinates = drop_coordinates;
+        self
+    }
+
+    pub fn compression(mut self, compression: CompressionCodec) -> Self {
+        self.config.compression = compression;
+        self
+    }
+
+    pub fn compression_level(mut self, level: i32) -> Self {
+        self.config.compression_level = Some(level);
+        self
+    }
+
+    /// Sets whether column statistics are written; see [`JobConfig::statistics`]
+    pub fn statistics(mut self, statistics: bool) -> Self {
+        self.config.statistics = Some(statistics);
+        self
+    }
+
+    /// Sets the AWS region of S3 inputs and outputs
+    pub fn aws_region(mut self, region: &str) -> Self {
+        self.config.aws_region = Some(region.to_string());
+        self
+    }
+
+    /// Returns the configuration, or an error if the input or the output is not set
+    pub fn build(self) -> Nc2ParquetResult<JobConfig> {
+        if self.config.nc_key.is_empty() {
+            return Err(Nc2ParquetError::InvalidConfig(
+                "No input file given".to_string(),
+            ));
+        }
+        if self.config.parquet_key.is_empty() {
+            return Err(Nc2ParquetError::InvalidConfig(
+                "No output file given".to_string(),
+            ));
+        }
+        Ok(self.config)
+    }
 }
 
 impl FilterConfig {
@@ -154,11 +773,14 @@ impl FilterConfig {
         match self {
             FilterConfig::Range { params } => {
                 let filter =
-                    NCRangeFilter::new(&params.dimension_name, params.min_value, params.max_value);
+                    NCRangeFilter::new(&params.dimension_name, params.min_value, params.max_value)
+                        .with_normalize_longitude(params.normalize_longitude)
+                        .with_inclusive_bounds(params.min_inclusive, params.max_inclusive);
                 Ok(Box::new(filter))
             }
             FilterConfig::List { params } => {
-                let filter = NCListFilter::new(&params.dimension_name, params.values.clone());
+                let filter = NCListFilter::new(&params.dimension_name, params.values.clone())
+                    .with_tolerance(params.tolerance);
                 Ok(Box::new(filter))
             }
             FilterConfig::Point2D { params } => {
@@ -167,7 +789,9 @@ impl FilterConfig {
                     &params.lon_dimension_name,
                     params.points.clone(),
                     params.tolerance,
-                );
+                )
+                .with_snap_nearest(params.snap_nearest)
+                .with_normalize_longitude(params.normalize_longitude);
                 Ok(Box::new(filter))
             }
             FilterConfig::Point3D { params } => {
@@ -178,9 +802,123 @@ impl FilterConfig {
                     params.steps.clone(),
                     params.points.clone(),
                     params.tolerance,
+                )
+                .with_step_dates(params.step_dates.clone())
+                .with_normalize_longitude(params.normalize_longitude);
+                Ok(Box::new(filter))
+            }
+            FilterConfig::BoundingBox3D { params } => {
+                let lat_range = (params.min_lat, params.max_lat);
+                let lon_range = (params.min_lon, params.max_lon);
+                let filter = match (
+                    params.start,
+                    params.end,
+                    &params.start_date,
+                    &params.end_date,
+                ) {
+                    (Some(start), Some(end), None, None) => NC3DBoundingBoxFilter::new(
+                        &params.time_dimension_name,
+                        &params.lat_dimension_name,
+                        &params.lon_dimension_name,
+                        (start, end),
+                        lat_range,
+                        lon_range,
+                    ),
+                    (None, None, Some(start_date), Some(end_date)) => {
+                        NC3DBoundingBoxFilter::between_dates(
+                            &params.time_dimension_name,
+                            &params.lat_dimension_name,
+                            &params.lon_dimension_name,
+                            (start_date, end_date),
+                            lat_range,
+                            lon_range,
+                        )
+                    }
+                    _ => {
+                        return Err(format!(
+                            "3D bounding box on '{}' needs either start and end, or start_date and end_date",
+                            params.time_dimension_name
+                        )
+                        .into());
+                    }
+                };
+                Ok(Box::new(
+                    filter.with_normalize_longitude(params.normalize_longitude),
+                ))
+            }
+            FilterConfig::Stride { params } => {
+                if params.stride == 0 {
+                    return Err(format!(
+                        "Stride for dimension '{}' must be at least 1",
+                        params.dimension_name
+                    )
+                    .into());
+                }
+                let filter = NCStrideFilter::new(
+                    &params.dimension_name,
+                    params.start,
+                    params.stride,
+                    params.count,
+                );
+                Ok(Box::new(filter))
+            }
+            FilterConfig::IndexRange { params } => {
+                if params.step == 0 {
+                    return Err(format!(
+                        "Step for dimension '{}' must be at least 1",
+                        params.dimension_name
+                    )
+                    .into());
+                }
+                let filter =
+                    NCIndexRangeFilter::new(&params.dimension_name, params.start, params.end)
+                        .with_step(params.step);
+                Ok(Box::new(filter))
+            }
+            FilterConfig::TimeRange { params } => {
+                let filter = NCTimeRangeFilter::new(
+                    &params.time_dimension_name,
+                    &params.start_date,
+                    &params.end_date,
                 );
                 Ok(Box::new(filter))
             }
+            FilterConfig::NearestTime { params } => {
+                let filter = match (params.target, &params.target_date) {
+                    (Some(target), None) => {
+                        NCNearestTimeFilter::new(&params.time_dimension_name, target, params.n)
+                    }
+                    (None, Some(target_date)) => NCNearestTimeFilter::at_date(
+                        &params.time_dimension_name,
+                        target_date,
+                        params.n,
+                    ),
+                    _ => {
+                        return Err(format!(
+                            "Nearest time filter on '{}' needs exactly one of target and target_date",
+                            params.time_dimension_name
+                        )
+                        .into());
+                    }
+                };
+                Ok(Box::new(filter))
+            }
+        }
+    }
+
+    /// Whether the filter is applied (its `enabled` parameter); disabled filters
+    /// are skipped when the job runs.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            FilterConfig::Range { params } => params.enabled,
+            FilterConfig::List { params } => params.enabled,
+            FilterConfig::Point2D { params } => params.enabled,
+            FilterConfig::Point3D { params } => params.enabled,
+            FilterConfig::BoundingBox3D { params } => params.enabled,
+            FilterConfig::Stride { params } => params.enabled,
+            FilterConfig::IndexRange { params } => params.enabled,
+            FilterConfig::TimeRange { params } => params.enabled,
+            FilterConfig::NearestTime { params } => params.enabled,
         }
     }
 
@@ -198,6 +936,11 @@ impl FilterConfig {
             FilterConfig::List { .. } => "list",
             FilterConfig::Point2D { .. } => "2d_point",
             FilterConfig::Point3D { .. } => "3d_point",
+            FilterConfig::BoundingBox3D { .. } => "bbox_3d",
+            FilterConfig::Stride { .. } => "stride",
+            FilterConfig::IndexRange { .. } => "index_range",
+            FilterConfig::TimeRange { .. } => "time_range",
+            FilterConfig::NearestTime { .. } => "nearest_time",
         }
     }
 }
@@ -4,37 +4,128 @@
 //!
 //! ## Features
 //!
-//! - **Multiple filter types**: Range filters, list filters, 2D point filters, and 3D point filters
+//! - **Multiple filter types**: Range, list, stride, index range, time range, nearest time, 2D point, and 3D point filters
 //! - **Filter intersection**: Apply multiple filters that intersect properly across dimensions
 //! - **Efficient processing**: Only extracts data for coordinates that match all filter criteria
 //! - **Post-processing framework**: Transform DataFrames with built-in processors and custom pipelines
 //! - **Type safety**: Strong typing with comprehensive error handling
 
+pub mod bloom_filter;
+pub mod cell_index;
+pub mod cf_check;
+pub mod cftime;
 pub mod cli;
+pub mod error;
 pub mod extract;
 pub mod filters;
 pub mod info;
 pub mod input;
+pub mod logging;
+pub mod manifest;
+pub mod metadata;
+pub mod metrics;
 pub mod output;
+pub mod peek;
 pub mod postprocess;
+pub mod report;
+pub mod sample;
+pub mod schema_check;
 pub mod storage;
+pub mod units;
 
 #[cfg(test)]
 mod tests;
 
-use crate::extract::extract_data_to_dataframe;
-use crate::input::JobConfig;
-use crate::output::{write_dataframe_to_parquet, write_dataframe_to_parquet_async};
-use crate::storage::{StorageBackend, StorageFactory};
+use crate::cell_index::{CellIndex, cell_index_path};
+use crate::error::{Nc2ParquetError, Nc2ParquetResult};
+use crate::extract::{
+    ExtractionProgress, MemoryEstimate, estimate_memory, extract_data_to_dataframe_chunked,
+    extract_data_to_dataframe_with_cell_index, extract_data_to_dataframe_with_progress,
+};
+use crate::input::{FilterConfig, JobConfig, RangeParams};
+use crate::manifest::{OutputManifest, manifest_path};
+use crate::metadata::{parquet_metadata, value_column_name};
+use crate::output::{
+    OutputFileFormat, StreamingParquetWriter, WriteOutcome, append_dataframe_to_parquet,
+    partition_dataframe, write_dataframe_to_parquet_if_changed,
+    write_dataframe_to_parquet_with_metadata, write_dataframe_to_parquet_with_metadata_async,
+    write_partitions_concurrently_with_metadata,
+};
+use crate::postprocess::{
+    ProcessingPipeline, ProcessingPipelineConfig, auto_unit_conversions, packing_metadata,
+};
+use crate::storage::{StorageBackend, StorageFactory, netcdf_format_from_magic};
+use flate2::read::GzDecoder;
+use log::{debug, warn};
+use polars::prelude::{DataFrame, Schema};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+use std::io::Read;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tempfile::NamedTempFile;
+
+/// Thread count set by [`configure_threads`]
+static THREADS: OnceLock<usize> = OnceLock::new();
+
+/// Caps the Polars thread pool and the global rayon pool used by extraction at
+/// `threads`; `0` leaves both at one thread per core. Must run before any
+/// DataFrame work, since Polars reads `POLARS_MAX_THREADS` once, when its pool is
+/// first used. The count is then returned by [`configured_threads`].
+///
+/// # Safety
+///
+/// This sets the `POLARS_MAX_THREADS` environment variable, so it must be called
+/// while no other thread may read or write the environment, e.g. at the start of
+/// `main`, before an async runtime or any other thread is started.
+pub unsafe fn configure_threads(threads: usize) {
+    if threads == 0 {
+        return;
+    }
+    if let Ok(available) = std::thread::available_parallelism()
+        && threads > available.get()
+    {
+        warn!(
+            "{} threads exceed the {} available cores; threads will compete for CPU",
+            threads, available
+        );
+    }
+
+    // SAFETY: the caller guarantees that no other thread touches the environment
+    unsafe {
+        std::env::set_var("POLARS_MAX_THREADS", threads.to_string());
+    }
+    if let Err(e) = rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+    {
+        warn!("Failed to configure the rayon thread pool: {}", e);
+    }
+    let _ = THREADS.set(threads);
+    debug!("Using {} threads", threads);
+}
+
+/// Number of threads the process works with: the count set by
+/// [`configure_threads`], or one per available core
+pub fn configured_threads() -> usize {
+    THREADS.get().copied().unwrap_or_else(|| {
+        std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    })
+}
 
 /// Processes a NetCDF file according to the provided job configuration.
 ///
 /// This function orchestrates the entire conversion pipeline:
-/// 1. Opens the NetCDF file
-/// 2. Validates the specified variable exists
+/// 1. Opens the NetCDF file, decompressing it to a temporary file first when
+///    `nc_key` ends in `.gz` (see [`is_gzip_input`])
+/// 2. Opens the specified variable, or the only data variable when none is given
 /// 3. Applies all configured filters with intersection logic
 /// 4. Extracts the filtered data into a DataFrame
-/// 5. Writes the DataFrame to a Parquet file
+/// 5. Applies post-processing and the optional row limit
+/// 6. Writes the DataFrame to a Parquet file with the configured compression,
+///    along with any CRS metadata and Bloom filters
+/// 7. Writes the companion cell index when `write_cell_index` is set, and the
+///    JSON manifest when `write_manifest` is set
 ///
 /// # Arguments
 ///
@@ -50,40 +141,458 @@ use crate::storage::{StorageBackend, StorageFactory};
 /// - The NetCDF file cannot be opened
 /// - The specified variable is not found in the NetCDF file
 /// - Any filter fails to apply
+/// - `fail_on_empty` is set and the filters select no rows
 /// - The output Parquet file cannot be written
-pub fn process_netcdf_job(config: &JobConfig) -> Result<(), Box<dyn std::error::Error>> {
-    let file = netcdf::open(&config.nc_key)?;
-    let var = file.variable(&config.variable_name).ok_or(format!(
-        "Variable '{}' not found in NetCDF file",
-        config.variable_name
-    ))?;
+pub fn process_netcdf_job(config: &JobConfig) -> Nc2ParquetResult<()> {
+    process_netcdf_job_with_progress(config, &mut |_| {})
+}
 
-    let mut filters = Vec::new();
-    for filter_config in &config.filters {
-        let filter = filter_config.to_filter()?;
-        filters.push(filter);
+/// Processes a NetCDF file like `process_netcdf_job`, reporting extraction
+/// progress through `on_progress`.
+///
+/// See [`extract_data_to_dataframe_with_progress`] for the events emitted.
+pub fn process_netcdf_job_with_progress(
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<()> {
+    let config: &JobConfig = &local_config(config)?;
+    let output = build_local_job_output(config, on_progress)?;
+
+    write_dataframe_to_parquet_with_metadata(
+        &output.df,
+        &config.parquet_key,
+        &output.metadata,
+        &config.output_options(),
+    )
+    .map_err(Nc2ParquetError::write)?;
+    if let Some(cell_index) = output.cell_index {
+        std::fs::write(cell_index_path(&config.parquet_key), cell_index.to_bytes())
+            .map_err(Nc2ParquetError::write)?;
+    }
+    if let Some(manifest) = output.manifest {
+        let json = manifest.to_json().map_err(Nc2ParquetError::write)?;
+        std::fs::write(manifest_path(&config.parquet_key), json).map_err(Nc2ParquetError::write)?;
     }
 
-    let mut df = extract_data_to_dataframe(&file, &var, &config.variable_name, &filters)?;
+    Ok(())
+}
 
-    // Apply post-processing if configured
-    if let Some(ref postprocess_config) = config.postprocessing {
-        use crate::postprocess::ProcessingPipeline;
-        let mut pipeline = ProcessingPipeline::from_config(postprocess_config)?;
-        df = pipeline.execute(df)?;
+/// Builds the DataFrame a job would write, without writing anything.
+///
+/// This runs the same steps as `process_netcdf_job` up to the write: the NetCDF
+/// file is opened, the variable is resolved, the filters are applied, the data is
+/// extracted, and the post-processing and row limit are applied. The result is
+/// returned in memory for library consumers to use as they see fit.
+///
+/// `nc_key` must be a local path; S3 inputs are only supported by the async
+/// processing functions. `parquet_key`, `write_cell_index` and `write_manifest`
+/// are ignored.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The NetCDF file cannot be opened
+/// - The specified variable is not found in the NetCDF file
+/// - Any filter or post-processing step fails
+pub fn build_dataframe(config: &JobConfig) -> Nc2ParquetResult<DataFrame> {
+    build_dataframe_with_progress(config, &mut |_| {})
+}
+
+/// Builds a job's DataFrame like `build_dataframe`, reporting extraction progress
+/// through `on_progress`.
+pub fn build_dataframe_with_progress(
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<DataFrame> {
+    let config: &JobConfig = &local_config(config)?;
+    let config = JobConfig {
+        write_cell_index: false,
+        write_manifest: false,
+        ..config.clone()
+    };
+    Ok(build_local_job_output(&config, on_progress)?.df)
+}
+
+/// Builds the DataFrame of several jobs, e.g. the daily files of a month
+/// extracted with the same variable and filters: the rows of each input are
+/// extracted and concatenated vertically, in order, and the post-processing,
+/// `limit` and `drop_coordinates` of the first job are then applied once to the
+/// combined rows, so an aggregation groups the rows of every input together.
+///
+/// # Errors
+///
+/// Returns [`Nc2ParquetError::InvalidConfig`] when `configs` is empty, when a job
+/// samples its rows, or when a job's columns or column types differ from the
+/// first job's, naming its input, along with the errors of `build_dataframe`.
+pub fn build_dataframe_multi(configs: &[JobConfig]) -> Nc2ParquetResult<DataFrame> {
+    check_concat_configs(configs)?;
+    let mut inputs = Vec::with_capacity(configs.len());
+    for config in configs {
+        let config: &JobConfig = &local_config(config)?;
+        let (nc_path, temp_file) = local_input_path(&config.nc_key)?;
+        inputs.push(build_concat_input(nc_path, config, &mut |_| {})?);
+        drop(temp_file);
+    }
+    Ok(finish_concat(inputs, &configs[0])?.0)
+}
+
+/// Rejects an empty list of jobs to concatenate, and jobs that sample their
+/// rows, since each input would be sampled on its own
+fn check_concat_configs(configs: &[JobConfig]) -> Nc2ParquetResult<()> {
+    if configs.is_empty() {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "At least one input is needed to concatenate".to_string(),
+        ));
+    }
+    if let Some(config) = configs.iter().find(|config| config.sample.is_some()) {
+        return Err(Nc2ParquetError::InvalidConfig(format!(
+            "Rows cannot be sampled when concatenating inputs ({} samples its rows)",
+            config.nc_key
+        )));
     }
+    Ok(())
+}
+
+/// One input of a concatenation: its extracted rows, with what the steps applied
+/// once to the combined rows need from its variable
+struct ConcatInput {
+    nc_key: String,
+    df: DataFrame,
+    metadata: Vec<(String, String)>,
+    pipeline: Option<ProcessingPipelineConfig>,
+    coordinate_columns: Vec<String>,
+}
 
-    write_dataframe_to_parquet(&df, &config.parquet_key)?;
+/// Opens the NetCDF file at `nc_path` and extracts the job's rows, without
+/// post-processing, limit or dropped coordinates
+fn build_concat_input(
+    nc_path: impl AsRef<std::path::Path>,
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<ConcatInput> {
+    let file = open_netcdf(nc_path, &config.nc_key)?;
+    let var = job_variable(&file, config)?;
+    let filters = job_filters(config)?;
+
+    let extract_config = JobConfig {
+        postprocessing: None,
+        auto_unit_convert: HashMap::new(),
+        round_decimals: None,
+        limit: None,
+        drop_coordinates: false,
+        write_cell_index: false,
+        write_manifest: false,
+        ..config.clone()
+    };
+    let (df, _) = build_job_dataframe(&file, &var, &filters, &extract_config, on_progress)?;
+    let input = ConcatInput {
+        nc_key: config.nc_key.clone(),
+        df,
+        metadata: job_metadata(&file, &var, config)?,
+        pipeline: job_pipeline_config(&var, config)?,
+        coordinate_columns: coordinate_column_names(&var, config),
+    };
+    drop(var);
     file.close()?;
 
-    Ok(())
+    Ok(input)
+}
+
+/// Concatenates the rows of `inputs` and applies the post-processing, `limit`
+/// and `drop_coordinates` of `first`, the first input's job. Also returns the
+/// first input's footer metadata.
+fn finish_concat(
+    inputs: Vec<ConcatInput>,
+    first: &JobConfig,
+) -> Nc2ParquetResult<(DataFrame, Vec<(String, String)>)> {
+    let mut inputs = inputs.into_iter();
+    let Some(first_input) = inputs.next() else {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "At least one input is needed to concatenate".to_string(),
+        ));
+    };
+    let ConcatInput {
+        nc_key,
+        df,
+        metadata,
+        pipeline,
+        coordinate_columns,
+    } = first_input;
+    let frames = std::iter::once((nc_key, df))
+        .chain(inputs.map(|input| (input.nc_key, input.df)))
+        .collect();
+
+    let mut df = concat_dataframes(frames)?;
+    if let Some(pipeline_config) = pipeline {
+        df = ProcessingPipeline::from_config(&pipeline_config)?.execute(df)?;
+    }
+    if let Some(limit) = first.limit {
+        df = df.head(Some(limit));
+    }
+    if first.drop_coordinates {
+        df = drop_named_coordinate_columns(df, coordinate_columns, first)?;
+    }
+    Ok((df, metadata))
+}
+
+/// Concatenates the DataFrames of several inputs, keyed by input, into one,
+/// reordering columns that only differ in order from the first input's
+fn concat_dataframes(frames: Vec<(String, DataFrame)>) -> Nc2ParquetResult<DataFrame> {
+    let mut frames = frames.into_iter();
+    let Some((first_key, mut combined)) = frames.next() else {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "At least one input is needed to concatenate".to_string(),
+        ));
+    };
+    let columns: Vec<String> = combined
+        .get_column_names()
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+
+    for (nc_key, df) in frames {
+        if let Some(difference) = schema_difference(combined.schema(), df.schema()) {
+            return Err(Nc2ParquetError::InvalidConfig(format!(
+                "Cannot concatenate {}: {} (compared with {})",
+                nc_key, difference, first_key
+            )));
+        }
+        let df = df
+            .select(columns.iter().map(String::as_str))
+            .map_err(Nc2ParquetError::extraction)?;
+        debug!("Concatenating {} rows from {}", df.height(), nc_key);
+        combined
+            .vstack_mut(&df)
+            .map_err(Nc2ParquetError::extraction)?;
+    }
+    Ok(combined)
+}
+
+/// The first difference in column names or types between `expected` and
+/// `actual`, ignoring column order
+fn schema_difference(expected: &Schema, actual: &Schema) -> Option<String> {
+    for (name, expected_dtype) in expected.iter() {
+        match actual.get(name) {
+            None => return Some(format!("column '{}' is missing", name)),
+            Some(actual_dtype) if actual_dtype != expected_dtype => {
+                return Some(format!(
+                    "column '{}' is {} instead of {}",
+                    name, actual_dtype, expected_dtype
+                ));
+            }
+            Some(_) => {}
+        }
+    }
+    actual
+        .iter_names()
+        .find(|name| !expected.contains(name))
+        .map(|name| format!("column '{}' is unexpected", name))
+}
+
+/// Builds the DataFrame of several jobs, local or on S3, like
+/// [`build_dataframe_multi`] and writes it once, to the first job's output with
+/// its options and footer metadata. Returns the number of rows written.
+///
+/// # Errors
+///
+/// Besides the errors of [`build_dataframe_multi`], returns
+/// [`Nc2ParquetError::InvalidConfig`] when a cell index or a manifest is
+/// requested, as both describe a single input.
+pub async fn process_netcdf_jobs_concat_async(
+    configs: &[JobConfig],
+    on_progress: &mut (dyn FnMut(ExtractionProgress) + Send),
+) -> Nc2ParquetResult<usize> {
+    check_concat_configs(configs)?;
+    let first = &configs[0];
+    if first.write_cell_index {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A cell index cannot be written when concatenating inputs".to_string(),
+        ));
+    }
+    if first.write_manifest {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A manifest cannot be written when concatenating inputs".to_string(),
+        ));
+    }
+
+    let mut inputs = Vec::with_capacity(configs.len());
+    for config in configs {
+        let config: &JobConfig = &local_config(config)?;
+        let (nc_path, temp_file) = input_path_async(config).await?;
+        inputs.push(build_concat_input(nc_path, config, on_progress)?);
+
+        // Keep the temporary file alive until the NetCDF file is closed, then remove it
+        drop(temp_file);
+    }
+    let (df, metadata) = finish_concat(inputs, first)?;
+
+    let output = JobOutput {
+        df,
+        metadata,
+        cell_index: None,
+        manifest: None,
+    };
+    write_job_output(&output, &local_config(first)?).await?;
+    Ok(output.df.height())
+}
+
+/// Estimates the memory of extracting the job's variable (see
+/// [`extract::estimate_memory`]), applying its filters without reading values.
+///
+/// # Errors
+///
+/// Returns [`Nc2ParquetError::InvalidConfig`] for S3 inputs, which would have to
+/// be downloaded first, and the usual errors when the file cannot be opened, the
+/// variable is not found or a filter fails.
+pub fn estimate_job_memory(config: &JobConfig) -> Nc2ParquetResult<MemoryEstimate> {
+    let config: &JobConfig = &local_config(config)?;
+    if config.nc_key.starts_with("s3://") {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "Memory can only be estimated for local inputs".to_string(),
+        ));
+    }
+
+    // The temporary file of a compressed input lives until the end of the function
+    let (nc_path, _temp_file) = local_input_path(&config.nc_key)?;
+    let file = open_netcdf(nc_path, &config.nc_key)?;
+    let var = job_variable(&file, config)?;
+    let filters = job_filters(config)?;
+    estimate_memory(&file, &var, &filters, &config.extract_options())
+        .map_err(Nc2ParquetError::extraction)
+}
+
+/// Processes a NetCDF file in chunks, streaming the result to a local Parquet file.
+///
+/// This function behaves like `process_netcdf_job`, but extracts at most
+/// `chunk_size` rows at a time and appends each chunk to the output file as it
+/// is produced, keeping memory usage bounded for very large variables.
+///
+/// Post-processing is applied to each chunk independently, so only row-wise
+/// processors are supported (see [`ProcessorConfig::is_row_wise`]); pipelines
+/// containing an aggregation, a pivot or a running extremum, among others, are
+/// rejected.
+///
+/// [`ProcessorConfig::is_row_wise`]: crate::postprocess::ProcessorConfig::is_row_wise
+///
+/// # Arguments
+///
+/// * `config` - The job configuration specifying input file, filters, and output
+/// * `chunk_size` - Maximum number of rows extracted and written per chunk
+///
+/// # Returns
+///
+/// Returns the total number of rows written, or an error if any step fails.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The output path is an S3 location
+/// - A cell index, a manifest or Bloom filters are requested
+/// - The post-processing pipeline contains a processor that is not row-wise
+/// - The NetCDF file cannot be opened or the variable is not found
+/// - Any filter fails to apply
+/// - The output Parquet file cannot be written
+pub fn process_netcdf_job_streaming(
+    config: &JobConfig,
+    chunk_size: usize,
+) -> Nc2ParquetResult<usize> {
+    let config: &JobConfig = &local_config(config)?;
+
+    if config.parquet_key.starts_with("s3://") {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "Streaming output is only supported for local Parquet files".to_string(),
+        ));
+    }
+    if config.write_cell_index {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A cell index cannot be written when streaming in chunks".to_string(),
+        ));
+    }
+    if config.write_manifest {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A manifest cannot be written when streaming in chunks".to_string(),
+        ));
+    }
+    if !config.parquet_bloom_filter_columns.is_empty() {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "Bloom filters cannot be written when streaming in chunks".to_string(),
+        ));
+    }
+
+    if config
+        .postprocessing
+        .as_ref()
+        .is_some_and(|postprocess_config| {
+            postprocess_config
+                .processors
+                .iter()
+                .any(|processor| !processor.is_row_wise())
+        })
+    {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "Only row-wise post-processing can be applied when streaming in chunks".to_string(),
+        ));
+    }
+
+    // The temporary file of a compressed input lives until the end of the function
+    let (nc_path, _temp_file) = local_input_path(&config.nc_key)?;
+    let file = open_netcdf(nc_path, &config.nc_key)?;
+    let var = job_variable(&file, config)?;
+    let mut pipeline = job_pipeline(&var, config)?;
+    let filters = job_filters(config)?;
+
+    let metadata = job_metadata(&file, &var, config)?;
+    let output_options = config.output_options();
+    let compression = output_options
+        .parquet_compression()
+        .map_err(|e| Nc2ParquetError::InvalidConfig(e.to_string()))?;
+    let mut writer = StreamingParquetWriter::new(&config.parquet_key)
+        .with_metadata(metadata)
+        .with_compression(compression)
+        .with_statistics(output_options.parquet_statistics());
+    let mut remaining = config.limit.unwrap_or(usize::MAX);
+    let mut extracted = 0;
+    extract_data_to_dataframe_chunked(
+        &file,
+        &var,
+        &value_column_name(&var, config.rename_from_attributes),
+        &filters,
+        &config.extract_options(),
+        chunk_size,
+        |chunk| {
+            extracted += chunk.height();
+            let chunk = match pipeline.as_mut() {
+                Some(pipeline) => pipeline.execute(chunk)?,
+                None => chunk,
+            };
+            // Keep writing empty chunks once the limit is reached so the file
+            // is still created with the right schema
+            let mut chunk = chunk.head(Some(remaining));
+            remaining -= chunk.height();
+            if config.drop_coordinates {
+                chunk = drop_coordinate_columns(chunk, &var, config)?;
+            }
+            writer.write_chunk(&chunk)
+        },
+    )
+    .map_err(Nc2ParquetError::extraction)?;
+    let rows_written = writer.finish().map_err(Nc2ParquetError::write)?;
+    if config.fail_on_empty && extracted == 0 {
+        let error = empty_selection_error(&file, &filters);
+        std::fs::remove_file(&config.parquet_key).map_err(Nc2ParquetError::write)?;
+        return Err(error);
+    }
+    file.close()?;
+
+    Ok(rows_written)
 }
 
 /// Async version of NetCDF processing that supports both local files and S3.
 ///
 /// This function provides the same functionality as `process_netcdf_job` but with
 /// support for S3 input files. When an S3 path is detected, the file is downloaded
-/// to a temporary location, processed, and then cleaned up.
+/// to a temporary location, processed, and then cleaned up. Gzip-compressed
+/// inputs (`.gz`) are decompressed into that temporary file instead.
 ///
 /// # Arguments
 ///
@@ -101,66 +610,984 @@ pub fn process_netcdf_job(config: &JobConfig) -> Result<(), Box<dyn std::error::
 /// - The specified variable is not found in the NetCDF file
 /// - Any filter fails to apply
 /// - The output file cannot be written (local or S3)
-pub async fn process_netcdf_job_async(
+pub async fn process_netcdf_job_async(config: &JobConfig) -> Nc2ParquetResult<()> {
+    process_netcdf_job_async_with_progress(config, &mut |_| {}).await
+}
+
+/// Async NetCDF processing like `process_netcdf_job_async`, reporting extraction
+/// progress through `on_progress`.
+pub async fn process_netcdf_job_async_with_progress(
+    config: &JobConfig,
+    on_progress: &mut (dyn FnMut(ExtractionProgress) + Send),
+) -> Nc2ParquetResult<()> {
+    let config: &JobConfig = &local_config(config)?;
+    let output = build_job_dataframe_async(config, on_progress).await?;
+    write_job_output(&output, config).await
+}
+
+/// Writes a job's output, with its cell index and manifest, locally or on S3
+async fn write_job_output(output: &JobOutput, config: &JobConfig) -> Nc2ParquetResult<()> {
+    // Check if output is S3 path
+    if config.parquet_key.starts_with("s3://") {
+        write_dataframe_to_parquet_with_metadata_async(
+            &output.df,
+            &config.parquet_key,
+            &output.metadata,
+            &config.output_options(),
+        )
+        .await
+        .map_err(Nc2ParquetError::write)?;
+    } else {
+        write_dataframe_to_parquet_with_metadata(
+            &output.df,
+            &config.parquet_key,
+            &output.metadata,
+            &config.output_options(),
+        )
+        .map_err(Nc2ParquetError::write)?;
+    }
+    if let Some(ref cell_index) = output.cell_index {
+        write_cell_index_async(cell_index, &config.parquet_key).await?;
+    }
+    if let Some(ref manifest) = output.manifest {
+        manifest
+            .write(&config.parquet_key)
+            .await
+            .map_err(Nc2ParquetError::write)?;
+    }
+
+    Ok(())
+}
+
+/// Processes a NetCDF file, skipping the write when the output content is unchanged.
+///
+/// This function behaves like `process_netcdf_job_async`, but compares the SHA-256
+/// hash of the serialized Parquet output with the `.sha256` sidecar stored next to
+/// the destination. When they match, the existing output is left untouched; otherwise
+/// both the Parquet file and the sidecar are written. Works for local and S3 outputs.
+///
+/// # Arguments
+///
+/// * `config` - The job configuration specifying input file, filters, and output
+///
+/// # Returns
+///
+/// Returns whether the output was written or skipped, or an error if any step fails.
+pub async fn process_netcdf_job_if_changed(config: &JobConfig) -> Nc2ParquetResult<WriteOutcome> {
+    process_netcdf_job_if_changed_with_progress(config, &mut |_| {}).await
+}
+
+/// Change-aware processing like `process_netcdf_job_if_changed`, reporting
+/// extraction progress through `on_progress`.
+pub async fn process_netcdf_job_if_changed_with_progress(
+    config: &JobConfig,
+    on_progress: &mut (dyn FnMut(ExtractionProgress) + Send),
+) -> Nc2ParquetResult<WriteOutcome> {
+    let config: &JobConfig = &local_config(config)?;
+    let output = build_job_dataframe_async(config, on_progress).await?;
+    let outcome = write_dataframe_to_parquet_if_changed(
+        &output.df,
+        &config.parquet_key,
+        &output.metadata,
+        &config.output_options(),
+    )
+    .await
+    .map_err(Nc2ParquetError::write)?;
+    // An unchanged output implies an unchanged index and manifest, so they are
+    // only rewritten with the data
+    if outcome == WriteOutcome::Written {
+        if let Some(ref cell_index) = output.cell_index {
+            write_cell_index_async(cell_index, &config.parquet_key).await?;
+        }
+        if let Some(ref manifest) = output.manifest {
+            manifest
+                .write(&config.parquet_key)
+                .await
+                .map_err(Nc2ParquetError::write)?;
+        }
+    }
+    Ok(outcome)
+}
+
+/// Processes a NetCDF file and appends the result to the output Parquet file.
+///
+/// This function behaves like `process_netcdf_job_async`, but when the output
+/// already exists its rows are kept and the new rows are added after them (see
+/// [`append_dataframe_to_parquet`]). The existing file is read and rewritten in
+/// full, so the schemas must match exactly. Works for local and S3 outputs.
+///
+/// # Returns
+///
+/// Returns the total number of rows in the output, or an error if any step fails.
+/// A manifest, when requested, describes the whole output, but only names the
+/// source of the rows appended last.
+///
+/// # Errors
+///
+/// This function will also return an error if a cell index is requested, since
+/// the rows of earlier runs have no entries in it.
+pub async fn process_netcdf_job_append_with_progress(
+    config: &JobConfig,
+    on_progress: &mut (dyn FnMut(ExtractionProgress) + Send),
+) -> Nc2ParquetResult<usize> {
+    let config: &JobConfig = &local_config(config)?;
+    if config.write_cell_index {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A cell index cannot be written when appending".to_string(),
+        ));
+    }
+
+    let output = build_job_dataframe_async(config, on_progress).await?;
+    let rows = append_dataframe_to_parquet(
+        &output.df,
+        &config.parquet_key,
+        &output.metadata,
+        &config.output_options(),
+    )
+    .await
+    .map_err(Nc2ParquetError::write)?;
+    if let Some(manifest) = output.manifest {
+        manifest
+            .with_rows(rows)
+            .write(&config.parquet_key)
+            .await
+            .map_err(Nc2ParquetError::write)?;
+    }
+    Ok(rows)
+}
+
+/// One file written by [`process_netcdf_job_split_async`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitOutput {
+    pub variable: String,
+    pub parquet_key: String,
+    pub rows: usize,
+}
+
+/// Names the output of `variable` in a split job after `parquet_key`, e.g.
+/// `out.parquet` to `out_temperature.parquet`.
+///
+/// The group path of a variable in a NetCDF-4 group is joined with underscores
+/// (`/ocean/temp` gives `out_ocean_temp.parquet`).
+pub fn split_output_path(parquet_key: &str, variable: &str) -> String {
+    let suffix = variable.trim_start_matches('/').replace('/', "_");
+    let name_start = parquet_key.rfind('/').map_or(0, |slash| slash + 1);
+    match parquet_key[name_start..].rfind('.') {
+        Some(dot) if dot > 0 => {
+            let (stem, extension) = parquet_key.split_at(name_start + dot);
+            format!("{}_{}{}", stem, suffix, extension)
+        }
+        _ => format!("{}_{}", parquet_key, suffix),
+    }
+}
+
+/// Expands the tokens of an output path template, e.g.
+/// `out/{var}_{lat_min}-{lat_max}.parquet` to `out/temperature_-10-10.parquet`.
+///
+/// The tokens are:
+/// - `{var}`: the variable, with the group path joined by underscores as in
+///   [`split_output_path`]; not available for split jobs, which already name
+///   each output after its variable
+/// - `{stem}` and `{ext}`: the name of the input file without and with only its
+///   extension (`.gz` is skipped, so `data.nc.gz` gives `data` and `nc`)
+/// - `{<dim>_min}` and `{<dim>_max}`: the bounds of the only enabled range filter
+///   on dimension `<dim>`
+///
+/// `{{` and `}}` stand for literal braces. Paths without tokens are returned
+/// unchanged.
+///
+/// # Errors
+///
+/// Returns [`Nc2ParquetError::InvalidConfig`] for unknown tokens and unclosed
+/// braces, listing the tokens available for the job.
+pub fn expand_output_template(template: &str, config: &JobConfig) -> Nc2ParquetResult<String> {
+    if !template.contains(['{', '}']) {
+        return Ok(template.to_string());
+    }
+    let tokens = output_template_tokens(config);
+    let invalid = |message: String| {
+        let available: Vec<String> = tokens.keys().map(|name| format!("{{{}}}", name)).collect();
+        Nc2ParquetError::InvalidConfig(format!(
+            "{} in output path '{}' (available tokens: {})",
+            message,
+            template,
+            available.join(", ")
+        ))
+    };
+
+    let mut expanded = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                expanded.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                expanded.push('}');
+            }
+            '{' => {
+                let mut name = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => name.push(c),
+                        None => return Err(invalid(format!("Unclosed token '{{{}'", name))),
+                    }
+                }
+                match tokens.get(name.as_str()) {
+                    Some(value) => expanded.push_str(value),
+                    None if name == "var" && config.split_variables => {
+                        return Err(invalid(
+                            "Token '{var}' cannot be used with split_variables, which names each output after its variable"
+                                .to_string(),
+                        ));
+                    }
+                    None if name == "var" => {
+                        return Err(invalid(
+                            "Token '{var}' needs the variable name; set variable_name for S3 or gzip inputs"
+                                .to_string(),
+                        ));
+                    }
+                    None => return Err(invalid(format!("Unknown token '{{{}}}'", name))),
+                }
+            }
+            '}' => return Err(invalid("Unmatched '}'".to_string())),
+            c => expanded.push(c),
+        }
+    }
+    Ok(expanded)
+}
+
+/// The tokens [`expand_output_template`] can expand for `config`, by name
+fn output_template_tokens(config: &JobConfig) -> BTreeMap<String, String> {
+    let mut tokens = BTreeMap::new();
+    if !config.split_variables && !config.variable_name.is_empty() {
+        let variable = config
+            .variable_name
+            .trim_start_matches('/')
+            .replace('/', "_");
+        tokens.insert("var".to_string(), variable);
+    }
+
+    let file_name = config.nc_key.rsplit('/').next().unwrap_or_default();
+    let file_name = file_name.strip_suffix(".gz").unwrap_or(file_name);
+    let (stem, extension) = match file_name.rfind('.') {
+        Some(dot) if dot > 0 => (&file_name[..dot], &file_name[dot + 1..]),
+        _ => (file_name, ""),
+    };
+    tokens.insert("stem".to_string(), stem.to_string());
+    tokens.insert("ext".to_string(), extension.to_string());
+
+    // Bounds are only defined when a single range filter selects the dimension
+    let mut ranges: BTreeMap<&str, Vec<&RangeParams>> = BTreeMap::new();
+    for filter in config.filters.iter().filter(|filter| filter.is_enabled()) {
+        if let FilterConfig::Range { params } = filter {
+            ranges
+                .entry(params.dimension_name.as_str())
+                .or_default()
+                .push(params);
+        }
+    }
+    for (dimension, params) in ranges {
+        if let [params] = params.as_slice() {
+            tokens.insert(format!("{}_min", dimension), params.min_value.to_string());
+            tokens.insert(format!("{}_max", dimension), params.max_value.to_string());
+        }
+    }
+    tokens
+}
+
+/// Writes each variable of a job to its own Parquet file, locally or on S3.
+///
+/// The variables are those of `variable_names`, or every data variable of the
+/// file when it is empty (see [`info::data_variable_names`]). Each output is
+/// named by [`split_output_path`] and processed like `process_netcdf_job_async`,
+/// with `variable_name` set to the variable; cell indexes and manifests are
+/// written next to each output.
+///
+/// The input is opened (and downloaded, for S3) once, and the filters are applied
+/// once: they select coordinates, so their results hold for every variable.
+/// Every variable must therefore have the filtered dimensions.
+///
+/// # Returns
+///
+/// Returns the written files, in the order of the variables.
+pub async fn process_netcdf_job_split_async(
+    config: &JobConfig,
+) -> Nc2ParquetResult<Vec<SplitOutput>> {
+    process_netcdf_job_split_async_with_progress(config, &mut |_| {}).await
+}
+
+/// Split processing like `process_netcdf_job_split_async`, reporting extraction
+/// progress through `on_progress`; the events of each variable follow those of
+/// the previous one.
+pub async fn process_netcdf_job_split_async_with_progress(
+    config: &JobConfig,
+    on_progress: &mut (dyn FnMut(ExtractionProgress) + Send),
+) -> Nc2ParquetResult<Vec<SplitOutput>> {
+    let config: &JobConfig = &local_config(config)?;
+    let (nc_path, temp_file) = input_path_async(config).await?;
+    let outputs = build_split_outputs(nc_path, config, on_progress)?;
+    drop(temp_file);
+
+    let mut written = Vec::with_capacity(outputs.len());
+    for (variable_config, output) in outputs {
+        write_job_output(&output, &variable_config).await?;
+        debug!(
+            "Wrote {} rows of '{}' to {}",
+            output.df.height(),
+            variable_config.variable_name,
+            variable_config.parquet_key
+        );
+        written.push(SplitOutput {
+            variable: variable_config.variable_name,
+            parquet_key: variable_config.parquet_key,
+            rows: output.df.height(),
+        });
+    }
+    Ok(written)
+}
+
+/// Opens the NetCDF file at `nc_path` once and builds the output of each variable
+/// of a split job, along with the single-variable configuration that writes it
+fn build_split_outputs(
+    nc_path: impl AsRef<std::path::Path>,
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<Vec<(JobConfig, JobOutput)>> {
+    let file = open_netcdf(nc_path, &config.nc_key)?;
+    let variable_names = if config.variable_names.is_empty() {
+        info::data_variable_names(&file)
+    } else {
+        config.variable_names.clone()
+    };
+    if variable_names.is_empty() {
+        return Err(Nc2ParquetError::VariableNotFound(format!(
+            "{} has no data variables to split",
+            config.nc_key
+        )));
+    }
+
+    let filters = applied_filters(&file, config)?;
+    let mut outputs = Vec::with_capacity(variable_names.len());
+    for variable_name in variable_names {
+        let variable_config = JobConfig {
+            parquet_key: split_output_path(&config.parquet_key, &variable_name),
+            variable_name,
+            variable_names: Vec::new(),
+            split_variables: false,
+            ..config.clone()
+        };
+        let var = job_variable(&file, &variable_config)?;
+        let output = variable_output(&file, &var, &filters, &variable_config, on_progress)?;
+        outputs.push((variable_config, output));
+    }
+    file.close()?;
+
+    Ok(outputs)
+}
+
+/// A filter whose result was computed up front, so that it can be reused for
+/// several variables of the same file
+struct AppliedFilter {
+    result: filters::FilterResult,
+    description: String,
+}
+
+impl filters::NCFilter for AppliedFilter {
+    fn apply_cached(
+        &self,
+        _file: &netcdf::File,
+        _cache: &mut filters::CoordinateCache,
+    ) -> Result<filters::FilterResult, Box<dyn std::error::Error>> {
+        Ok(self.result.clone())
+    }
+
+    fn dimension(&self) -> Option<&str> {
+        match &self.result {
+            filters::FilterResult::Single { dimension, .. } => Some(dimension),
+            _ => None,
+        }
+    }
+
+    fn describe(&self) -> String {
+        self.description.clone()
+    }
+}
+
+/// Applies the job's filters to `file` once, sharing one coordinate cache
+fn applied_filters(
+    file: &netcdf::File,
+    config: &JobConfig,
+) -> Nc2ParquetResult<Vec<Box<dyn filters::NCFilter>>> {
+    let mut cache = filters::CoordinateCache::new();
+    job_filters(config)?
+        .into_iter()
+        .map(|filter| {
+            let result = filter
+                .apply_cached(file, &mut cache)
+                .map_err(Nc2ParquetError::filter)?;
+            Ok(Box::new(AppliedFilter {
+                result,
+                description: filter.describe(),
+            }) as Box<dyn filters::NCFilter>)
+        })
+        .collect()
+}
+
+/// A job's processed DataFrame, ready to be written
+struct JobOutput {
+    df: DataFrame,
+    /// Key-value metadata for the Parquet footer
+    metadata: Vec<(String, String)>,
+    /// Present when the job asks for a cell index
+    cell_index: Option<CellIndex>,
+    /// Present when the job asks for a manifest
+    manifest: Option<OutputManifest>,
+}
+
+/// Processes a NetCDF file like `process_netcdf_job_async`, but writes a
+/// partitioned dataset instead of a single file: `parquet_key` names a directory
+/// (or S3 prefix) holding one file per distinct combination of the
+/// `partition_by` columns, laid out as described by [`partition_dataframe`].
+/// The parts are Arrow IPC files when the directory's extension asks for it
+/// (e.g. `dataset.arrow/`, see [`OutputFileFormat::from_path`]), and Parquet
+/// otherwise; IPC parts keep the footer metadata as custom schema metadata.
+///
+/// The files are written concurrently, as many at once as the process has
+/// threads (see [`configured_threads`]), each with the job's compression,
+/// statistics, Bloom filters and footer metadata. Extraction progress is
+/// reported through `on_progress`.
+///
+/// # Returns
+///
+/// Returns the written files, in order of the first appearance of their values.
+///
+/// # Errors
+///
+/// Besides the errors of `process_netcdf_job_async`, returns
+/// [`Nc2ParquetError::InvalidConfig`] when `partition_by` is empty or names a
+/// column missing from the output, when a cell index or a manifest is
+/// requested, as both describe a single file, or when Bloom filters are
+/// requested for Arrow IPC parts.
+pub async fn process_netcdf_job_partitioned_async(
     config: &JobConfig,
-) -> Result<(), Box<dyn std::error::Error>> {
+    partition_by: &[String],
+    on_progress: &mut (dyn FnMut(ExtractionProgress) + Send),
+) -> Nc2ParquetResult<Vec<String>> {
+    if partition_by.is_empty() {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "At least one partition column is needed".to_string(),
+        ));
+    }
+    if config.write_cell_index {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A cell index cannot be written for a partitioned output".to_string(),
+        ));
+    }
+    if config.write_manifest {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A manifest cannot be written for a partitioned output".to_string(),
+        ));
+    }
+
+    let config: &JobConfig = &local_config(config)?;
+    let format = OutputFileFormat::from_path(&config.parquet_key);
+    if format == OutputFileFormat::ArrowIpc && !config.parquet_bloom_filter_columns.is_empty() {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "Bloom filters cannot be written to Arrow IPC partitions".to_string(),
+        ));
+    }
+
+    let output = build_job_dataframe_async(config, on_progress).await?;
+    if let Some(column) = partition_by
+        .iter()
+        .find(|column| output.df.get_column_index(column).is_none())
+    {
+        return Err(Nc2ParquetError::InvalidConfig(format!(
+            "Partition column '{}' is not in the output",
+            column
+        )));
+    }
+
+    let partitions = partition_dataframe(&output.df, partition_by, &config.parquet_key, format)
+        .map_err(Nc2ParquetError::write)?;
+    debug!(
+        "Writing {} rows in {} partitions under {}",
+        output.df.height(),
+        partitions.len(),
+        config.parquet_key
+    );
+    write_partitions_concurrently_with_metadata(
+        partitions,
+        &output.metadata,
+        &config.output_options(),
+        None,
+    )
+    .await
+    .map_err(Nc2ParquetError::write)
+}
+
+/// Extracts, post-processes and limits the job's DataFrame, downloading S3 input
+/// to a temporary file when needed. Also returns the Parquet metadata to write.
+async fn build_job_dataframe_async(
+    config: &JobConfig,
+    on_progress: &mut (dyn FnMut(ExtractionProgress) + Send),
+) -> Nc2ParquetResult<JobOutput> {
+    let (nc_path, temp_file) = input_path_async(config).await?;
+    let output = build_job_output(nc_path, config, on_progress)?;
+
+    // Keep the temporary file alive until the NetCDF file is closed, then remove it
+    drop(temp_file);
+
+    Ok(output)
+}
+
+/// Returns the path to open for the job's input, like [`local_input_path`],
+/// downloading S3 inputs to a temporary file first
+async fn input_path_async(
+    config: &JobConfig,
+) -> Nc2ParquetResult<(PathBuf, Option<NamedTempFile>)> {
+    use_job_region(config);
+    let nc_key = config.nc_key.as_str();
+
     // Check if input is S3 path
-    let (file, temp_file_path) = if config.nc_key.starts_with("s3://") {
-        // Download from S3 to temporary file
-        let storage = StorageFactory::from_path(&config.nc_key).await?;
-        let data = storage.read(&config.nc_key).await?;
+    if !nc_key.starts_with("s3://") {
+        // Read the local file directly, or its decompressed copy
+        return local_input_path(nc_key);
+    }
+
+    // Download from S3 to temporary file
+    let storage = StorageFactory::from_path(nc_key).await?;
+    let data = storage.read(nc_key).await?;
 
+    if is_gzip_input(nc_key) {
+        let temp_file = decompress_to_temp_file(data.as_slice(), nc_key)?;
+        Ok((temp_file.path().to_path_buf(), Some(temp_file)))
+    } else {
         // Create temporary file
-        let temp_file = tempfile::NamedTempFile::new()?;
+        let temp_file = storage::temp_file()?;
         let temp_path = temp_file.path().to_path_buf();
 
         // Write S3 data to temporary file
         tokio::fs::write(&temp_path, data).await?;
 
-        // Open NetCDF file from temporary location
-        let file = netcdf::open(&temp_path)?;
-        (file, Some(temp_path))
+        Ok((temp_path, Some(temp_file)))
+    }
+}
+
+/// Makes the job's `aws_region` the region of the S3 clients created from now on.
+/// The region is process-wide and fixed once set, so a job asking for another one
+/// only gets a warning.
+fn use_job_region(config: &JobConfig) {
+    let Some(ref region) = config.aws_region else {
+        return;
+    };
+    if !storage::set_aws_region(region.as_str()) && storage::aws_region() != Some(region.as_str()) {
+        warn!(
+            "Ignoring AWS region '{}' of {}: S3 clients already use '{}'",
+            region,
+            config.nc_key,
+            storage::aws_region().unwrap_or_default()
+        );
+    }
+}
+
+/// The job with `file://` URIs in its input and output keys replaced by local paths
+fn local_config(config: &JobConfig) -> Nc2ParquetResult<Cow<'_, JobConfig>> {
+    if !StorageFactory::is_file_uri(&config.nc_key)
+        && !StorageFactory::is_file_uri(&config.parquet_key)
+    {
+        return Ok(Cow::Borrowed(config));
+    }
+    let mut config = config.clone();
+    config.resolve_file_uris()?;
+    Ok(Cow::Owned(config))
+}
+
+/// Whether `nc_key` names a gzip-compressed input, such as `data.nc.gz`
+pub fn is_gzip_input(nc_key: &str) -> bool {
+    nc_key.ends_with(".gz")
+}
+
+/// Returns the path to open for a local `nc_key`. Compressed inputs are
+/// decompressed to a temporary file, which is removed when the returned handle
+/// is dropped, so it must be kept alive until the NetCDF file is closed.
+fn local_input_path(nc_key: &str) -> Nc2ParquetResult<(PathBuf, Option<NamedTempFile>)> {
+    if !is_gzip_input(nc_key) {
+        return Ok((PathBuf::from(nc_key), None));
+    }
+    let compressed =
+        std::fs::File::open(nc_key).map_err(|e| Nc2ParquetError::file_open(nc_key, e))?;
+    let temp_file = decompress_to_temp_file(compressed, nc_key)?;
+    Ok((temp_file.path().to_path_buf(), Some(temp_file)))
+}
+
+/// Decompresses a gzip stream into a temporary file and checks that the result
+/// starts with a NetCDF signature
+fn decompress_to_temp_file(compressed: impl Read, nc_key: &str) -> Nc2ParquetResult<NamedTempFile> {
+    let mut temp_file = storage::temp_file()?;
+    let size = std::io::copy(&mut GzDecoder::new(compressed), temp_file.as_file_mut())
+        .map_err(|e| Nc2ParquetError::file_open(nc_key, format!("failed to decompress: {}", e)))?;
+
+    let mut magic = Vec::with_capacity(8);
+    temp_file.reopen()?.take(8).read_to_end(&mut magic)?;
+    let Some(format) = netcdf_format_from_magic(&magic) else {
+        return Err(Nc2ParquetError::file_open(
+            nc_key,
+            "does not contain a NetCDF file after decompression",
+        ));
+    };
+
+    debug!("Decompressed {} ({}, {} bytes)", nc_key, format, size);
+    Ok(temp_file)
+}
+
+/// Builds the job's output from a local `nc_key`, decompressing it first if needed
+fn build_local_job_output(
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<JobOutput> {
+    let (nc_path, temp_file) = local_input_path(&config.nc_key)?;
+    let output = build_job_output(nc_path, config, on_progress)?;
+
+    // Keep the temporary file alive until the NetCDF file is closed, then remove it
+    drop(temp_file);
+
+    Ok(output)
+}
+
+/// Opens the NetCDF file at `nc_path` and builds the job's output from it
+fn build_job_output(
+    nc_path: impl AsRef<std::path::Path>,
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<JobOutput> {
+    let file = open_netcdf(nc_path, &config.nc_key)?;
+    let var = job_variable(&file, config)?;
+    let filters = job_filters(config)?;
+
+    let output = variable_output(&file, &var, &filters, config, on_progress)?;
+    file.close()?;
+
+    Ok(output)
+}
+
+/// Builds the output of one variable of an open file, with the given filters
+fn variable_output(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    filters: &Vec<Box<dyn filters::NCFilter>>,
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<JobOutput> {
+    let (df, cell_index) = build_job_dataframe(file, var, filters, config, on_progress)?;
+
+    let metadata = job_metadata(file, var, config)?;
+    let manifest = config
+        .write_manifest
+        .then(|| OutputManifest::new(config, &var.name(), &df));
+
+    Ok(JobOutput {
+        df,
+        metadata,
+        cell_index,
+        manifest,
+    })
+}
+
+/// Footer metadata of the job's output: the variable's (see [`parquet_metadata`])
+/// followed by the packing of the columns packed by post-processing
+fn job_metadata(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    config: &JobConfig,
+) -> Nc2ParquetResult<Vec<(String, String)>> {
+    let mut metadata = parquet_metadata(file, var).map_err(Nc2ParquetError::extraction)?;
+    if let Some(ref postprocess_config) = config.postprocessing {
+        metadata.extend(packing_metadata(&postprocess_config.processors));
+    }
+    Ok(metadata)
+}
+
+/// Opens the NetCDF file at `nc_path`; `nc_key` names it in the error
+fn open_netcdf(
+    nc_path: impl AsRef<std::path::Path>,
+    nc_key: &str,
+) -> Nc2ParquetResult<netcdf::File> {
+    netcdf::open(nc_path).map_err(|e| Nc2ParquetError::file_open(nc_key, e))
+}
+
+/// Opens the job's variable, auto-detecting it when `variable_name` is empty
+/// (see [`info::detect_main_variable`]). Variables in NetCDF-4 groups are named
+/// by their group path (see [`info::resolve_variable`]).
+fn job_variable<'f>(
+    file: &'f netcdf::File,
+    config: &JobConfig,
+) -> Nc2ParquetResult<netcdf::Variable<'f>> {
+    let variable_name = if config.variable_name.is_empty() {
+        info::detect_main_variable(file)
+            .map_err(|e| Nc2ParquetError::VariableNotFound(e.to_string()))?
     } else {
-        // Open local file directly
-        let file = netcdf::open(&config.nc_key)?;
-        (file, None)
+        config.variable_name.clone()
     };
+    info::resolve_variable(file, &variable_name)
+        .map_err(|e| Nc2ParquetError::VariableNotFound(e.to_string()))
+}
 
-    let var = file.variable(&config.variable_name).ok_or(format!(
-        "Variable '{}' not found in NetCDF file",
-        config.variable_name
-    ))?;
+/// Builds the job's enabled filters from their configuration
+fn job_filters(config: &JobConfig) -> Nc2ParquetResult<Vec<Box<dyn filters::NCFilter>>> {
+    config
+        .filters
+        .iter()
+        .filter(|filter_config| filter_config.is_enabled())
+        .map(|filter_config| filter_config.to_filter().map_err(Nc2ParquetError::filter))
+        .collect()
+}
 
-    let mut filters = Vec::new();
-    for filter_config in &config.filters {
-        let filter = filter_config.to_filter()?;
-        filters.push(filter);
+/// Extracts, post-processes and limits the job's DataFrame from an open file,
+/// along with its cell index when `config.write_cell_index` is set.
+fn build_job_dataframe(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+    filters: &Vec<Box<dyn filters::NCFilter>>,
+    config: &JobConfig,
+    on_progress: &mut dyn FnMut(ExtractionProgress),
+) -> Nc2ParquetResult<(DataFrame, Option<CellIndex>)> {
+    if config.write_cell_index
+        && config
+            .postprocessing
+            .as_ref()
+            .is_some_and(|postprocess_config| {
+                postprocess_config
+                    .processors
+                    .iter()
+                    .any(|processor| !processor.keeps_rows())
+            })
+    {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "A cell index cannot be written when post-processing drops, adds or reorders rows"
+                .to_string(),
+        ));
     }
 
-    let mut df = extract_data_to_dataframe(&file, &var, &config.variable_name, &filters)?;
+    let (mut df, mut cell_index) = if config.write_cell_index {
+        let (df, cell_index) = extract_data_to_dataframe_with_cell_index(
+            file,
+            var,
+            &value_column_name(var, config.rename_from_attributes),
+            filters,
+            &config.extract_options(),
+            on_progress,
+        )
+        .map_err(Nc2ParquetError::extraction)?;
+        (df, Some(cell_index))
+    } else {
+        let df = extract_data_to_dataframe_with_progress(
+            file,
+            var,
+            &value_column_name(var, config.rename_from_attributes),
+            filters,
+            &config.extract_options(),
+            on_progress,
+        )
+        .map_err(Nc2ParquetError::extraction)?;
+        (df, None)
+    };
+    if config.fail_on_empty && df.height() == 0 {
+        return Err(empty_selection_error(file, filters));
+    }
 
     // Apply post-processing if configured
-    if let Some(ref postprocess_config) = config.postprocessing {
-        use crate::postprocess::ProcessingPipeline;
-        let mut pipeline = ProcessingPipeline::from_config(postprocess_config)?;
+    if let Some(mut pipeline) = job_pipeline(var, config)? {
         df = pipeline.execute(df)?;
     }
 
-    // Check if output is S3 path
-    if config.parquet_key.starts_with("s3://") {
-        write_dataframe_to_parquet_async(&df, &config.parquet_key).await?;
-    } else {
-        write_dataframe_to_parquet(&df, &config.parquet_key)?;
+    if let Some(limit) = config.limit {
+        df = df.head(Some(limit));
+    }
+    if config.drop_coordinates {
+        df = drop_coordinate_columns(df, var, config)?;
+    }
+    if let Some(ref mut cell_index) = cell_index {
+        cell_index.truncate(df.height());
     }
 
-    file.close()?;
+    Ok((df, cell_index))
+}
+
+/// Error of a job with `fail_on_empty` whose filters selected no rows, naming the
+/// filters that match nothing on their own
+fn empty_selection_error(
+    file: &netcdf::File,
+    filters: &[Box<dyn filters::NCFilter>],
+) -> Nc2ParquetError {
+    let mut cache = filters::CoordinateCache::new();
+    let empty: Vec<String> = filters
+        .iter()
+        .filter(|filter| {
+            filter
+                .apply_cached(file, &mut cache)
+                .is_ok_and(|result| result.is_empty())
+        })
+        .map(|filter| filter.describe())
+        .collect();
+    Nc2ParquetError::FilterError(if empty.is_empty() {
+        "No rows were selected, though no filter matches nothing on its own".to_string()
+    } else {
+        format!(
+            "No rows were selected; these filters match nothing: {}",
+            empty.join("; ")
+        )
+    })
+}
+
+/// Names of the coordinate columns of `var` in the output, one per dimension,
+/// as renamed by `coordinate_renames`
+fn coordinate_column_names(var: &netcdf::Variable, config: &JobConfig) -> Vec<String> {
+    extract::row_dimensions(var)
+        .iter()
+        .map(|dim| {
+            let name = dim.name();
+            config
+                .coordinate_renames
+                .get(&name)
+                .cloned()
+                .unwrap_or(name)
+        })
+        .collect()
+}
+
+/// Removes the coordinate columns of `var`, and their index columns, from `df`
+/// for `drop_coordinates`. Columns already removed or renamed by post-processing
+/// are left alone.
+///
+/// # Errors
+///
+/// Returns [`Nc2ParquetError::InvalidConfig`] when a removed column is one of
+/// `parquet_bloom_filter_columns`, or when no column would be left.
+fn drop_coordinate_columns(
+    df: DataFrame,
+    var: &netcdf::Variable,
+    config: &JobConfig,
+) -> Nc2ParquetResult<DataFrame> {
+    drop_named_coordinate_columns(df, coordinate_column_names(var, config), config)
+}
 
-    // Clean up temporary file if it was created
-    if let Some(temp_path) = temp_file_path
-        && temp_path.exists()
+/// Removes the given coordinate columns, and their index columns, from `df` like
+/// [`drop_coordinate_columns`]
+fn drop_named_coordinate_columns(
+    mut df: DataFrame,
+    coordinate_columns: Vec<String>,
+    config: &JobConfig,
+) -> Nc2ParquetResult<DataFrame> {
+    let dropped: Vec<String> = coordinate_columns
+        .into_iter()
+        .flat_map(|name| {
+            let index_name = extract::index_column_name(&name);
+            [name, index_name]
+        })
+        .filter(|name| df.get_column_index(name).is_some())
+        .collect();
+    if let Some(column) = config
+        .parquet_bloom_filter_columns
+        .iter()
+        .find(|column| dropped.contains(column))
     {
-        std::fs::remove_file(temp_path)?;
+        return Err(Nc2ParquetError::InvalidConfig(format!(
+            "Bloom filter column '{}' is a coordinate column removed by drop_coordinates",
+            column
+        )));
+    }
+    if dropped.len() == df.width() {
+        return Err(Nc2ParquetError::InvalidConfig(
+            "drop_coordinates would remove every column of the output".to_string(),
+        ));
+    }
+    for name in &dropped {
+        df.drop_in_place(name)
+            .map_err(Nc2ParquetError::extraction)?;
     }
+    Ok(df)
+}
+
+/// Builds the job's post-processing pipeline: the unit conversions requested by
+/// `auto_unit_convert` for the value column's `units` attribute, followed by the
+/// configured processors and the rounding set by `round_decimals`. Returns `None`
+/// when there is nothing to apply.
+pub fn job_pipeline(
+    var: &netcdf::Variable,
+    config: &JobConfig,
+) -> Nc2ParquetResult<Option<ProcessingPipeline>> {
+    match job_pipeline_config(var, config)? {
+        Some(pipeline_config) => Ok(Some(ProcessingPipeline::from_config(&pipeline_config)?)),
+        None => Ok(None),
+    }
+}
+
+/// Configuration of the pipeline built by [`job_pipeline`]
+fn job_pipeline_config(
+    var: &netcdf::Variable,
+    config: &JobConfig,
+) -> Nc2ParquetResult<Option<ProcessingPipelineConfig>> {
+    use crate::postprocess::ProcessorConfig;
+
+    let mut processors = Vec::new();
+    if !config.auto_unit_convert.is_empty() {
+        let units = match var.attribute_value("units").transpose()? {
+            Some(value) => String::try_from(value).ok(),
+            None => None,
+        };
+        match units {
+            Some(units) => {
+                let column_units = [(value_column_name(var, config.rename_from_attributes), units)];
+                processors = auto_unit_conversions(&column_units, &config.auto_unit_convert)?;
+            }
+            None => debug!(
+                "Variable '{}' has no units attribute; not converting its units",
+                var.name()
+            ),
+        }
+    }
+    if let Some(ref postprocess_config) = config.postprocessing {
+        processors.extend(postprocess_config.processors.iter().cloned());
+    }
+    if let Some(decimals) = config.round_decimals {
+        let exclude = if config.round_coordinates {
+            Vec::new()
+        } else {
+            coordinate_column_names(var, config)
+        };
+        processors.push(ProcessorConfig::Round {
+            decimals,
+            columns: config.round_columns.clone(),
+            exclude,
+        });
+    }
+
+    if processors.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(ProcessingPipelineConfig {
+        name: config
+            .postprocessing
+            .as_ref()
+            .and_then(|postprocess_config| postprocess_config.name.clone()),
+        processors,
+    }))
+}
 
+/// Writes `cell_index` next to `output_path`, locally or on S3
+async fn write_cell_index_async(cell_index: &CellIndex, output_path: &str) -> Nc2ParquetResult<()> {
+    let index_path = cell_index_path(output_path);
+    let storage = StorageFactory::from_path(&index_path)
+        .await
+        .map_err(Nc2ParquetError::write)?;
+    storage
+        .write(&index_path, &cell_index.to_bytes())
+        .await
+        .map_err(Nc2ParquetError::write)?;
     Ok(())
 }
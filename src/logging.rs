@@ -0,0 +1,128 @@
+//! # Logging Module
+//!
+//! This module sets up the logger of the command-line tool. Messages always go to
+//! stderr through `env_logger`; with `--log-file <path>` they are also appended
+//! to a file, one line per message, for log aggregation systems. Two line
+//! formats are supported:
+//!
+//! - **Text**: `[<timestamp> <LEVEL> <target>] <message>`, like stderr.
+//! - **JSON**: one object per line (JSON lines) with the `timestamp` (RFC 3339,
+//!   UTC), `level`, `target` and `message` of each record.
+//!
+//! Both destinations share the level filter of stderr, so `-v` and `-q` apply to
+//! the file too.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, SecondsFormat, Utc};
+use clap::ValueEnum;
+use log::{Log, Metadata, Record};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// Line format of the log file
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Plain text lines, like stderr
+    #[default]
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Renders `record`, logged at `time`, as one line (without the newline) in `format`
+pub fn format_record(record: &Record, format: LogFormat, time: DateTime<Utc>) -> String {
+    let timestamp = time.to_rfc3339_opts(SecondsFormat::Millis, true);
+    match format {
+        LogFormat::Text => format!(
+            "[{} {:<5} {}] {}",
+            timestamp,
+            record.level(),
+            record.target(),
+            record.args()
+        ),
+        LogFormat::Json => serde_json::json!({
+            "timestamp": timestamp,
+            "level": record.level().as_str(),
+            "target": record.target(),
+            "message": record.args().to_string(),
+        })
+        .to_string(),
+    }
+}
+
+/// A file log messages are appended to
+pub struct LogFile {
+    file: Mutex<File>,
+    format: LogFormat,
+}
+
+impl LogFile {
+    /// Opens the file at `path` for appending, creating it (but not its parent
+    /// directory) if missing
+    pub fn open(path: &Path, format: LogFormat) -> Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open log file: {}", path.display()))?;
+        Ok(LogFile {
+            file: Mutex::new(file),
+            format,
+        })
+    }
+
+    fn write(&self, record: &Record) {
+        let mut line = format_record(record, self.format, Utc::now());
+        line.push('\n');
+        // Logging must not fail the run; a line that cannot be written is lost
+        if let Ok(mut file) = self.file.lock() {
+            let _ = file.write_all(line.as_bytes());
+        }
+    }
+}
+
+/// Logs to stderr through `env_logger` and, when given, to a [`LogFile`]
+pub struct TeeLogger {
+    stderr: env_logger::Logger,
+    file: Option<LogFile>,
+}
+
+impl TeeLogger {
+    /// Logs the records accepted by the filters of `stderr` to both destinations
+    pub fn new(stderr: env_logger::Logger, file: Option<LogFile>) -> Self {
+        TeeLogger { stderr, file }
+    }
+
+    /// Installs this logger as the global logger
+    pub fn init(self) -> Result<(), log::SetLoggerError> {
+        log::set_max_level(self.stderr.filter());
+        log::set_boxed_logger(Box::new(self))
+    }
+}
+
+impl Log for TeeLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.stderr.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.stderr.matches(record) {
+            return;
+        }
+        self.stderr.log(record);
+        if let Some(ref file) = self.file {
+            file.write(record);
+        }
+    }
+
+    fn flush(&self) {
+        self.stderr.flush();
+        if let Some(ref file) = self.file
+            && let Ok(mut file) = file.file.lock()
+        {
+            let _ = file.flush();
+        }
+    }
+}
@@ -8,32 +8,87 @@ use clap::{CommandFactory, Parser};
 use clap_complete::{Shell, generate};
 use indicatif::{ProgressBar, ProgressStyle};
 use log::{debug, error, info, warn};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::process;
 use std::time::Duration;
 
 use nc2parquet::{
+    cell_index::cell_index_path,
+    cf_check::check_cf_compliance,
+    cftime::parse_reference_datetime,
     cli::*,
-    input::{FilterConfig, JobConfig},
-    postprocess::{ProcessingPipelineConfig, ProcessorConfig},
-    process_netcdf_job, process_netcdf_job_async,
-    storage::{StorageBackend, StorageFactory},
+    configure_threads, estimate_job_memory, expand_output_template,
+    extract::{ExtractionProgress, FilterCombine, NanPolicy, SampleConfig},
+    filters::{CoordinateCache, axis_spacing, missing_coordinate_message},
+    input::{FilterConfig, JobConfig, TimeRangeParams},
+    is_gzip_input,
+    logging::{LogFile, TeeLogger},
+    manifest::manifest_path,
+    metrics::RunMetrics,
+    output::{CompressionCodec, OutputFileFormat, WriteOutcome},
+    postprocess::{AggregationOp, AggregationOps, ProcessingPipelineConfig, ProcessorConfig},
+    process_netcdf_job_append_with_progress, process_netcdf_job_async_with_progress,
+    process_netcdf_job_if_changed_with_progress, process_netcdf_job_partitioned_async,
+    process_netcdf_job_split_async_with_progress, process_netcdf_job_streaming,
+    process_netcdf_job_with_progress, process_netcdf_jobs_concat_async,
+    report::{RunReport, read_output_stats},
+    split_output_path,
+    storage::{self, StorageBackend, StorageFactory, check_local_output, check_writable},
 };
 
-#[tokio::main]
-async fn main() {
+fn main() {
     let cli = Cli::parse();
 
     // Initialize logging
     init_logging(&cli);
 
+    // SAFETY: the async runtime is only built below, so this is the only thread
+    // and nothing can read the environment while the thread count is set
+    unsafe { configure_threads(cli.threads) };
+
+    let runtime = match tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()
+    {
+        Ok(runtime) => runtime,
+        Err(e) => {
+            error!("Failed to start the async runtime: {}", e);
+            process::exit(1);
+        }
+    };
+    runtime.block_on(run(cli));
+}
+
+/// Runs the parsed command, exiting with status 1 when it fails
+async fn run(cli: Cli) {
+    storage::set_s3_concurrency(cli.s3_concurrency);
+    if let Some(ref temp_dir) = cli.temp_dir {
+        storage::set_temp_dir(temp_dir);
+        debug!("Temporary files go to {}", temp_dir.display());
+    }
+    if let Some(ref cache_dir) = cli.s3_cache_dir {
+        storage::set_s3_cache_dir(cache_dir);
+        debug!("S3 inputs are cached in {}", cache_dir.display());
+    }
+    if let Some(ref region) = cli.aws_region {
+        storage::set_aws_region(region.as_str());
+        debug!("Using AWS region {}", region);
+    }
+
     debug!("CLI arguments: {:?}", std::env::args().collect::<Vec<_>>());
 
+    let start_time = std::time::Instant::now();
     let result = match &cli.command {
         Commands::Convert { .. } => handle_convert_command(&cli).await,
         Commands::Validate { .. } => handle_validate_command(&cli).await,
         Commands::Info { .. } => handle_info_command(&cli).await,
+        Commands::Diff { .. } => handle_diff_command(&cli).await,
+        Commands::Peek { .. } => handle_peek_command(&cli).await,
+        Commands::Sample { .. } => handle_sample_command(&cli).await,
         Commands::Template { .. } => handle_template_command(&cli).await,
+        Commands::CheckSchema { .. } => handle_check_schema_command(&cli).await,
         Commands::Completions { .. } => handle_completions_command(&cli).await,
     };
 
@@ -44,6 +99,18 @@ async fn main() {
         Err(e) => {
             error!("Command failed: {}", e);
 
+            // Failed conversions are recorded so monitoring can alert on them
+            if let Commands::Convert {
+                metrics_file: Some(metrics_path),
+                metrics_format,
+                ..
+            } = &cli.command
+                && let Err(metrics_error) =
+                    RunMetrics::failed(start_time.elapsed()).write(metrics_path, *metrics_format)
+            {
+                warn!("Failed to write metrics: {:#}", metrics_error);
+            }
+
             // Show error chain if verbose
             if cli.verbose {
                 let mut cause = e.source();
@@ -71,35 +138,149 @@ fn init_logging(cli: &Cli) {
     unsafe {
         std::env::set_var("RUST_LOG", format!("nc2parquet={}", log_level));
     }
-    env_logger::init();
+    let stderr = env_logger::Builder::from_default_env().build();
+    let log_file = match cli.log_file {
+        Some(ref path) => match LogFile::open(path, cli.log_format) {
+            Ok(log_file) => Some(log_file),
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+    TeeLogger::new(stderr, log_file)
+        .init()
+        .expect("the logger is only initialized once");
 
     debug!("Logging initialized at {} level", log_level);
 }
 
+/// Makes the configuration's `aws_region` the region of the S3 clients, unless
+/// the global `--aws-region` (applied at startup) overrides it
+fn use_aws_region(cli: &Cli, config: &mut JobConfig) {
+    if let Some(ref region) = cli.aws_region {
+        config.aws_region = Some(region.clone());
+    } else if let Some(ref region) = config.aws_region {
+        storage::set_aws_region(region.as_str());
+        debug!("Using AWS region {}", region);
+    }
+}
+
+/// Builds the filters given on the command line, merged with the environment
+/// filters of the same type according to `filter_merge`
+fn cli_filters(
+    range_filters: &[RangeFilterArg],
+    list_filters: &[ListFilterArg],
+    point2d_filters: &[Point2DFilterArg],
+    point3d_filters: &[Point3DFilterArg],
+    time_range: &Option<TimeRangeArg>,
+    time_dimension: &str,
+    filter_merge: FilterMergeMode,
+) -> Result<Vec<FilterConfig>> {
+    let (merged_range_filters, merged_list_filters, merged_point2d_filters, merged_point3d_filters) =
+        merge_filters(
+            range_filters.to_vec(),
+            list_filters.to_vec(),
+            point2d_filters.to_vec(),
+            point3d_filters.to_vec(),
+            filter_merge,
+        )
+        .map_err(|e| anyhow::anyhow!("Filter parsing error: {}", e))?;
+
+    let time_range_filter = time_range
+        .as_ref()
+        .map(|time_range| FilterConfig::TimeRange {
+            params: TimeRangeParams {
+                time_dimension_name: time_dimension.to_string(),
+                start_date: time_range.start.clone(),
+                end_date: time_range.end.clone(),
+                enabled: true,
+            },
+        });
+    let filters: Vec<FilterConfig> = merged_range_filters
+        .into_iter()
+        .map(FilterConfig::from)
+        .chain(merged_list_filters.into_iter().map(FilterConfig::from))
+        .chain(merged_point2d_filters.into_iter().map(FilterConfig::from))
+        .chain(merged_point3d_filters.into_iter().map(FilterConfig::from))
+        .chain(time_range_filter)
+        .collect();
+    for filter_config in &filters {
+        if let Ok(filter) = filter_config.to_filter() {
+            debug!("Added filter: {}", filter.describe());
+        }
+    }
+    Ok(filters)
+}
+
 /// Handle the convert subcommand
 async fn handle_convert_command(cli: &Cli) -> Result<()> {
     if let Commands::Convert {
         input,
         output,
         variable,
+        variables,
+        split_variables,
+        concat,
+        partition_by,
         input_override,
         output_override,
         range_filters,
         list_filters,
         point2d_filters,
         point3d_filters,
+        time_range,
+        time_dimension,
+        filter_merge,
+        combine,
+        fail_on_empty,
+        overwrite_policy,
         force,
         dry_run,
+        check_write,
+        skip_unchanged,
+        append,
         rename_columns,
         unit_conversions,
         kelvin_to_celsius,
+        auto_units,
         formulas,
+        aggregates,
+        group_by,
+        pack_outputs,
+        limit,
+        sample,
+        sample_seed,
+        decode_times,
+        include_indices,
+        drop_coordinates,
+        sort_coordinates,
+        coordinate_order,
+        start_record,
+        rename_from_attributes,
+        nan_policy,
+        strict_cf,
+        cell_index,
+        write_manifest,
+        bloom_filter_columns,
+        compression,
+        compression_level,
+        write_statistics,
+        chunk_size,
+        estimate_memory,
+        memory_limit,
+        state_file,
+        report,
+        metrics_file,
+        metrics_format,
     } = &cli.command
     {
         info!("Starting NetCDF to Parquet conversion");
 
         // Load configuration
         let mut config = load_configuration(cli, input, output, variable)?;
+        use_aws_region(cli, &mut config);
 
         // Apply command line overrides
         if let Some(input_path) = input_override {
@@ -111,73 +292,142 @@ async fn handle_convert_command(cli: &Cli) -> Result<()> {
             config.parquet_key = output_path.clone();
             debug!("Overriding output path: {}", output_path);
         }
+        config.resolve_file_uris()?;
 
-        // Merge CLI and environment variable filters
-        let (
-            merged_range_filters,
-            merged_list_filters,
-            merged_point2d_filters,
-            merged_point3d_filters,
-        ) = merge_filters(
-            range_filters.clone(),
-            list_filters.clone(),
-            point2d_filters.clone(),
-            point3d_filters.clone(),
-        )
-        .map_err(|e| anyhow::anyhow!("Filter parsing error: {}", e))?;
+        if let Some(limit) = limit {
+            config.limit = Some(*limit);
+            debug!("Limiting output to {} rows", limit);
+        }
 
-        // Add merged filters to configuration
-        for range_filter in &merged_range_filters {
-            let filter_config = range_filter.clone().into();
-            config.filters.push(filter_config);
-            debug!(
-                "Added range filter: {}:{}-{}",
-                range_filter.dimension, range_filter.min_value, range_filter.max_value
-            );
+        if let Some(n) = sample {
+            config.sample = Some(SampleConfig {
+                n: *n,
+                seed: *sample_seed,
+            });
+            debug!("Sampling {} coordinate combinations", n);
         }
 
-        for list_filter in &merged_list_filters {
-            let filter_config = list_filter.clone().into();
-            config.filters.push(filter_config);
-            debug!(
-                "Added list filter: {}:{:?}",
-                list_filter.dimension, list_filter.values
-            );
+        if *decode_times {
+            config.decode_times = true;
+            debug!("Decoding CF time coordinates to UTC datetimes");
+        }
+
+        if *include_indices {
+            config.include_indices = true;
+            debug!("Adding per-dimension source index columns");
+        }
+
+        if *drop_coordinates {
+            config.drop_coordinates = true;
+            debug!("Removing the coordinate columns from the output");
+        }
+
+        if *sort_coordinates {
+            config.sort_coordinates = true;
+            debug!("Sorting the coordinate columns by dimension name");
+        }
+
+        if !coordinate_order.is_empty() {
+            config.coordinate_order = coordinate_order.clone();
+            debug!("Coordinate column order: {:?}", coordinate_order);
         }
 
-        for point2d_filter in &merged_point2d_filters {
-            let filter_config = point2d_filter.clone().into();
-            config.filters.push(filter_config);
+        if let Some(start_record) = start_record {
+            config.start_record = Some(*start_record);
             debug!(
-                "Added 2D point filter: {},{} at ({},{}) tolerance={}",
-                point2d_filter.lat_dimension,
-                point2d_filter.lon_dimension,
-                point2d_filter.lat,
-                point2d_filter.lon,
-                point2d_filter.tolerance
+                "Starting at record {} of the unlimited dimension",
+                start_record
             );
         }
 
-        for point3d_filter in &merged_point3d_filters {
-            let filter_config = point3d_filter.clone().into();
-            config.filters.push(filter_config);
+        if *rename_from_attributes {
+            config.rename_from_attributes = true;
+            debug!("Naming the value column from the variable's attributes");
+        }
+
+        if let Some(nan_policy) = nan_policy {
+            config.nan_policy = *nan_policy;
+            debug!("NaN policy: {:?}", nan_policy);
+        }
+
+        if let Some(combine) = combine {
+            config.combine = *combine;
+            debug!("Filter combination: {:?}", combine);
+        }
+
+        if *fail_on_empty {
+            config.fail_on_empty = true;
+            debug!("Failing when the filters select no rows");
+        }
+
+        if *cell_index {
+            config.write_cell_index = true;
+            debug!("Writing a cell index next to the output");
+        }
+
+        if *write_manifest {
+            config.write_manifest = true;
+            debug!("Writing a manifest next to the output");
+        }
+
+        if !bloom_filter_columns.is_empty() {
+            config.parquet_bloom_filter_columns = bloom_filter_columns.clone();
+            debug!("Bloom filter columns: {:?}", bloom_filter_columns);
+        }
+
+        if let Some(compression) = compression {
+            config.compression = *compression;
+            debug!("Compression codec: {:?}", compression);
+        }
+
+        if let Some(level) = compression_level {
+            config.compression_level = Some(*level);
+            debug!("Compression level: {}", level);
+        }
+
+        if let Some(write_statistics) = write_statistics {
+            config.statistics = *write_statistics;
+            debug!("Column statistics: {}", write_statistics);
+        }
+
+        if !variables.is_empty() {
+            config.variable_names = variables.clone();
+            debug!("Variables: {:?}", variables);
+        }
+
+        if *split_variables {
+            config.split_variables = true;
+            debug!("Writing one output per variable");
+        }
+
+        for auto_unit in auto_units {
+            config
+                .auto_unit_convert
+                .insert(auto_unit.quantity.clone(), auto_unit.unit.clone());
             debug!(
-                "Added 3D point filter: {},{},{} at ({},{},{}) tolerance={}",
-                point3d_filter.time_dimension,
-                point3d_filter.lat_dimension,
-                point3d_filter.lon_dimension,
-                point3d_filter.time,
-                point3d_filter.lat,
-                point3d_filter.lon,
-                point3d_filter.tolerance
+                "Converting {} values to {}",
+                auto_unit.quantity, auto_unit.unit
             );
         }
 
+        // Merge CLI and environment variable filters into the configuration
+        config.filters.extend(cli_filters(
+            range_filters,
+            list_filters,
+            point2d_filters,
+            point3d_filters,
+            time_range,
+            time_dimension,
+            *filter_merge,
+        )?);
+
         // Build post-processing pipeline from CLI arguments
         if !rename_columns.is_empty()
             || !unit_conversions.is_empty()
             || !kelvin_to_celsius.is_empty()
             || !formulas.is_empty()
+            || !aggregates.is_empty()
+            || !pack_outputs.is_empty()
         {
             use std::collections::HashMap;
 
@@ -232,6 +482,52 @@ async fn handle_convert_command(cli: &Cli) -> Result<()> {
                 );
             }
 
+            // Aggregate last, so it sees the renamed, converted and derived columns
+            if !aggregates.is_empty() {
+                let mut ops: HashMap<String, Vec<AggregationOp>> = HashMap::new();
+                for aggregate in aggregates {
+                    ops.entry(aggregate.column.clone())
+                        .or_default()
+                        .push(aggregate.op);
+                }
+                let aggregations = ops
+                    .into_iter()
+                    .map(|(column, ops)| {
+                        let ops = if ops.len() == 1 {
+                            AggregationOps::from(ops[0])
+                        } else {
+                            AggregationOps::from(ops)
+                        };
+                        (column, ops)
+                    })
+                    .collect();
+                processors.push(ProcessorConfig::Aggregate {
+                    group_by: group_by.clone(),
+                    aggregations,
+                });
+                debug!(
+                    "Added aggregation: {:?} grouped by {:?}",
+                    aggregates, group_by
+                );
+            }
+
+            // Pack after aggregating, right before the output is written
+            for pack_output in pack_outputs {
+                processors.push(ProcessorConfig::Pack {
+                    column: pack_output.column.clone(),
+                    scale_factor: pack_output.scale_factor,
+                    add_offset: pack_output.add_offset,
+                    dtype: pack_output.dtype,
+                });
+                debug!(
+                    "Added packing of {} into {} (scale_factor {}, add_offset {})",
+                    pack_output.column,
+                    pack_output.dtype.name(),
+                    pack_output.scale_factor,
+                    pack_output.add_offset
+                );
+            }
+
             if !processors.is_empty() {
                 let pipeline_config = ProcessingPipelineConfig {
                     name: Some("CLI Pipeline".to_string()),
@@ -246,22 +542,204 @@ async fn handle_convert_command(cli: &Cli) -> Result<()> {
         }
 
         // Validate configuration
-        validate_config(&config).await?;
+        let validation = validate_config(&config).await;
+        validation.log_warnings();
+        validation.ensure_valid()?;
+
+        if config.split_variables {
+            if *skip_unchanged || *append {
+                return Err(anyhow::anyhow!(
+                    "--skip-unchanged and --append cannot be combined with split_variables"
+                ));
+            }
+            if report.is_some() || metrics_file.is_some() {
+                return Err(anyhow::anyhow!(
+                    "--report and --metrics-file cannot be combined with split_variables"
+                ));
+            }
+        }
+
+        if !concat.is_empty() {
+            if config.split_variables {
+                return Err(anyhow::anyhow!(
+                    "--concat cannot be combined with split_variables"
+                ));
+            }
+            if *skip_unchanged || *append {
+                return Err(anyhow::anyhow!(
+                    "--skip-unchanged and --append cannot be combined with --concat"
+                ));
+            }
+            if chunk_size.is_some() {
+                return Err(anyhow::anyhow!(
+                    "--chunk-size cannot be combined with --concat"
+                ));
+            }
+            if config.write_cell_index || config.write_manifest {
+                return Err(anyhow::anyhow!(
+                    "--cell-index and --write-manifest cannot be combined with --concat"
+                ));
+            }
+            if config.sample.is_some() {
+                return Err(anyhow::anyhow!("--sample cannot be combined with --concat"));
+            }
+        }
+
+        if !partition_by.is_empty() && config.split_variables {
+            return Err(anyhow::anyhow!(
+                "--partition-by cannot be combined with split_variables"
+            ));
+        }
+
+        // Only partitioned datasets are written as Arrow IPC; a single file is always Parquet
+        if partition_by.is_empty()
+            && OutputFileFormat::from_path(&config.parquet_key) == OutputFileFormat::ArrowIpc
+        {
+            return Err(anyhow::anyhow!(
+                "Output {} has an Arrow IPC extension, which is only supported with --partition-by; use a .parquet output",
+                config.parquet_key
+            ));
+        }
+
+        // Resolve the variables up front for local files so logs and reports name them
+        if !config.nc_key.starts_with("s3://") && !is_gzip_input(&config.nc_key) {
+            if config.split_variables && config.variable_names.is_empty() {
+                let file = netcdf::open(&config.nc_key)
+                    .with_context(|| format!("Failed to open NetCDF file: {}", config.nc_key))?;
+                config.variable_names = data_variable_names(&file);
+                info!("No variables given, splitting {:?}", config.variable_names);
+            } else if !config.split_variables && config.variable_name.is_empty() {
+                let file = netcdf::open(&config.nc_key)
+                    .with_context(|| format!("Failed to open NetCDF file: {}", config.nc_key))?;
+                config.variable_name = detect_main_variable(&file)?;
+                info!("No variable given, using '{}'", config.variable_name);
+            }
+        }
+
+        // Output templates are expanded once the variable is known
+        let output_path = expand_output_template(&config.parquet_key, &config)?;
+        if output_path != config.parquet_key {
+            info!("Output path template expanded to {}", output_path);
+            config.parquet_key = output_path;
+        }
+
+        if *strict_cf {
+            let variable =
+                (!config.variable_name.is_empty()).then_some(config.variable_name.as_str());
+            check_cf_compliance(&config.nc_key, variable)
+                .await?
+                .ensure_compliant()?;
+            info!("CF check passed for {}", config.nc_key);
+        }
+
+        // Outputs recorded in the state file by an earlier run are not produced again
+        let requested_output = config.parquet_key.clone();
+        if let Some(state_file) = state_file
+            && !*dry_run
+            && is_completed_output(state_file, &requested_output).await?
+        {
+            info!(
+                "{}: skipped (completed according to {})",
+                requested_output,
+                state_file.display()
+            );
+            return Ok(());
+        }
+
+        // Check output file exists (unchanged outputs are compared, not overwritten blindly)
+        let overwrite_policy = if *force {
+            OverwritePolicy::Overwrite
+        } else {
+            *overwrite_policy
+        };
+        if !*skip_unchanged && !*append {
+            match overwrite_policy {
+                OverwritePolicy::Error if !*dry_run && config.split_variables => {
+                    // Without a variable list (S3 or compressed input), the outputs
+                    // are only known once the file is opened
+                    for output_path in split_output_paths(&config) {
+                        check_output_overwrite(&output_path).await?;
+                    }
+                }
+                OverwritePolicy::Error if !*dry_run => {
+                    check_output_overwrite(&config.parquet_key).await?;
+                }
+                OverwritePolicy::Version if config.split_variables => {
+                    return Err(anyhow::anyhow!(
+                        "--overwrite-policy version cannot be combined with split_variables"
+                    ));
+                }
+                OverwritePolicy::Version if !partition_by.is_empty() => {
+                    return Err(anyhow::anyhow!(
+                        "--overwrite-policy version cannot be combined with --partition-by"
+                    ));
+                }
+                OverwritePolicy::Version => {
+                    let output_path = next_available_output(&config.parquet_key).await?;
+                    if output_path != config.parquet_key {
+                        info!(
+                            "{} already exists, writing to {}",
+                            config.parquet_key, output_path
+                        );
+                        config.parquet_key = output_path;
+                    }
+                }
+                _ => {}
+            }
+        }
 
-        // Check output file exists
-        if !force && !*dry_run {
-            check_output_overwrite(&config.parquet_key).await?;
+        // An estimate above the memory limit may pick the chunk size
+        let mut chunk_size = *chunk_size;
+        if (*estimate_memory || memory_limit.is_some()) && !concat.is_empty() {
+            warn!("Memory is only estimated for single-input jobs; ignoring it with --concat");
+        } else if *estimate_memory || memory_limit.is_some() {
+            let streamable = !config.split_variables
+                && partition_by.is_empty()
+                && !needs_async_processing(&config)
+                && !*skip_unchanged
+                && !*append
+                && !config.write_cell_index
+                && !config.write_manifest
+                && config.parquet_bloom_filter_columns.is_empty()
+                && !config.postprocessing.as_ref().is_some_and(|pipeline| {
+                    pipeline
+                        .processors
+                        .iter()
+                        .any(|processor| !processor.is_row_wise())
+                });
+            chunk_size = apply_memory_estimate(
+                &config,
+                chunk_size,
+                *estimate_memory,
+                *memory_limit,
+                streamable,
+            )?;
         }
 
         if *dry_run {
+            for warning in point_tolerance_warnings(&config)? {
+                warn!("Configuration warning: {}", warning);
+            }
             info!("Dry run mode - configuration validated successfully");
             print_config_summary(&config, &cli.output_format);
             return Ok(());
         }
 
+        // Split outputs are written next to the configured output, so one probe covers them
+        if *check_write {
+            check_output_writable(&config.parquet_key).await?;
+        }
+
         // Show progress and process
         info!("Processing: {} -> {}", config.nc_key, config.parquet_key);
-        info!("Variable: {}", config.variable_name);
+        if !concat.is_empty() {
+            info!("Concatenated after it: {}", concat.join(", "));
+        }
+        if config.split_variables {
+            info!("Variables: {:?} (one output each)", config.variable_names);
+        } else {
+            info!("Variable: {}", config.variable_name);
+        }
         info!("Filters: {} configured", config.filters.len());
 
         // Create progress bar for non-quiet mode
@@ -286,11 +764,108 @@ async fn handle_convert_command(cli: &Cli) -> Result<()> {
             pb.set_message("Reading NetCDF file...");
         }
 
-        if needs_async_processing(&config) {
+        if chunk_size.is_some() && config.split_variables {
+            warn!("--chunk-size cannot be combined with --split-variables; processing in memory");
+        } else if chunk_size.is_some() && needs_async_processing(&config) {
+            warn!("--chunk-size is only supported for local files; processing in memory");
+        } else if chunk_size.is_some() && *skip_unchanged {
+            warn!("--chunk-size cannot be combined with --skip-unchanged; processing in memory");
+        } else if chunk_size.is_some() && *append {
+            warn!("--chunk-size cannot be combined with --append; processing in memory");
+        } else if chunk_size.is_some() && config.write_cell_index {
+            warn!("--chunk-size cannot be combined with --cell-index; processing in memory");
+        } else if chunk_size.is_some() && config.write_manifest {
+            warn!("--chunk-size cannot be combined with --write-manifest; processing in memory");
+        } else if chunk_size.is_some() && !config.parquet_bloom_filter_columns.is_empty() {
+            warn!(
+                "--chunk-size cannot be combined with --bloom-filter-columns; processing in memory"
+            );
+        }
+
+        // Once the number of rows is known, the spinner turns into a bar with an ETA
+        let mut on_progress = |event: ExtractionProgress| {
+            if let Some(ref pb) = progress {
+                update_extraction_progress(pb, event);
+            }
+        };
+
+        let mut skipped_unchanged = false;
+        let mut split_outputs = Vec::new();
+        let mut partition_outputs = Vec::new();
+        if !concat.is_empty() {
+            if let Some(ref pb) = progress {
+                pb.set_message(format!("Concatenating {} inputs...", concat.len() + 1));
+            }
+            let configs: Vec<JobConfig> = std::iter::once(config.nc_key.clone())
+                .chain(concat.iter().cloned())
+                .map(|nc_key| JobConfig {
+                    nc_key,
+                    ..config.clone()
+                })
+                .collect();
+            let rows = process_netcdf_jobs_concat_async(&configs, &mut on_progress)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))
+                .context("Failed to concatenate the inputs")?;
+            debug!(
+                "Wrote {} rows from {} inputs to {}",
+                rows,
+                configs.len(),
+                config.parquet_key
+            );
+        } else if !partition_by.is_empty() {
+            if let Some(ref pb) = progress {
+                pb.set_message(format!("Partitioning by {}...", partition_by.join(", ")));
+            }
+            partition_outputs =
+                process_netcdf_job_partitioned_async(&config, partition_by, &mut on_progress)
+                    .await
+                    .map_err(|e| anyhow::anyhow!("{}", e))
+                    .context("Failed to write the partitioned output")?;
+        } else if config.split_variables {
+            if let Some(ref pb) = progress {
+                pb.set_message("Processing one variable at a time...");
+            }
+            split_outputs = process_netcdf_job_split_async_with_progress(&config, &mut on_progress)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))
+                .context("Failed to process NetCDF file by variable")?;
+        } else if *skip_unchanged {
+            if let Some(ref pb) = progress {
+                pb.set_message("Processing and comparing with existing output...");
+            }
+            let outcome = process_netcdf_job_if_changed_with_progress(&config, &mut on_progress)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))
+                .context("Failed to process NetCDF file")?;
+            skipped_unchanged = outcome == WriteOutcome::SkippedUnchanged;
+        } else if *append {
+            if let Some(ref pb) = progress {
+                pb.set_message("Processing and appending to existing output...");
+            }
+            let rows = process_netcdf_job_append_with_progress(&config, &mut on_progress)
+                .await
+                .map_err(|e| anyhow::anyhow!("{}", e))
+                .context("Failed to append to the output")?;
+            debug!("{} now holds {} rows", config.parquet_key, rows);
+        } else if let Some(chunk_size) = chunk_size.filter(|_| {
+            !needs_async_processing(&config)
+                && !config.write_cell_index
+                && !config.write_manifest
+                && config.parquet_bloom_filter_columns.is_empty()
+        }) {
+            if let Some(ref pb) = progress {
+                pb.set_message(format!("Streaming in chunks of {} rows...", chunk_size));
+            }
+            let rows = process_netcdf_job_streaming(&config, chunk_size)
+                .map_err(|e| anyhow::anyhow!("{}", e))
+                .context("Failed to process NetCDF file in streaming mode")?;
+            debug!("Streamed {} rows to {}", rows, config.parquet_key);
+        } else if needs_async_processing(&config) {
             if let Some(ref pb) = progress {
                 pb.set_message("Processing with async pipeline...");
             }
-            process_netcdf_job_async(&config)
+            process_netcdf_job_async_with_progress(&config, &mut on_progress)
                 .await
                 .map_err(|e| anyhow::anyhow!("{}", e))
                 .context("Failed to process NetCDF file with async pipeline")?;
@@ -298,19 +873,39 @@ async fn handle_convert_command(cli: &Cli) -> Result<()> {
             if let Some(ref pb) = progress {
                 pb.set_message("Processing with sync pipeline...");
             }
-            process_netcdf_job(&config)
+            process_netcdf_job_with_progress(&config, &mut on_progress)
                 .map_err(|e| anyhow::anyhow!("{}", e))
                 .context("Failed to process NetCDF file")?;
         }
 
         let duration = start_time.elapsed();
 
+        if let Some(state_file) = state_file {
+            record_completed_output(state_file, &requested_output)?;
+            debug!(
+                "Recorded {} as completed in {}",
+                requested_output,
+                state_file.display()
+            );
+        }
+
         if let Some(pb) = progress {
-            let success_message =
-                format!("✅ Conversion completed in {:.2}s", duration.as_secs_f64());
+            let success_message = if skipped_unchanged {
+                format!(
+                    "⏭️  {} skipped (unchanged) in {:.2}s",
+                    config.parquet_key,
+                    duration.as_secs_f64()
+                )
+            } else {
+                format!("✅ Conversion completed in {:.2}s", duration.as_secs_f64())
+            };
             pb.finish_with_message(success_message);
         }
 
+        if skipped_unchanged {
+            info!("{}: skipped (unchanged)", config.parquet_key);
+        }
+
         // Enhanced timing information
         if duration.as_secs() > 1 {
             info!(
@@ -334,7 +929,45 @@ async fn handle_convert_command(cli: &Cli) -> Result<()> {
         }
 
         // Show output information
-        show_output_info(&config.parquet_key, &cli.output_format).await?;
+        if config.split_variables {
+            for output in &split_outputs {
+                info!(
+                    "{}: {} rows of '{}'",
+                    output.parquet_key, output.rows, output.variable
+                );
+                show_output_info(&output.parquet_key, &cli.output_format).await?;
+            }
+        } else if !partition_by.is_empty() {
+            info!(
+                "Wrote {} partition files under {}",
+                partition_outputs.len(),
+                config.parquet_key
+            );
+            for path in &partition_outputs {
+                show_output_info(path, &cli.output_format).await?;
+            }
+        } else {
+            show_output_info(&config.parquet_key, &cli.output_format).await?;
+        }
+
+        if report.is_some() || metrics_file.is_some() {
+            let stats = read_output_stats(&config.parquet_key)
+                .await
+                .context("Failed to collect output statistics")?;
+
+            if let Some(report_path) = report {
+                RunReport::new(&config, stats, duration)
+                    .with_skipped_unchanged(skipped_unchanged)
+                    .write(report_path)
+                    .await?;
+                info!("Run report written to {}", report_path);
+            }
+
+            if let Some(metrics_path) = metrics_file {
+                RunMetrics::succeeded(stats, duration).write(metrics_path, *metrics_format)?;
+                info!("Metrics written to {}", metrics_path.display());
+            }
+        }
     } else {
         unreachable!("Convert command handler called with wrong command type");
     }
@@ -342,11 +975,31 @@ async fn handle_convert_command(cli: &Cli) -> Result<()> {
     Ok(())
 }
 
+/// Switches the convert progress display to a bar once the row count is known
+fn update_extraction_progress(pb: &ProgressBar, event: ExtractionProgress) {
+    match event {
+        ExtractionProgress::Started { total } => {
+            pb.set_style(
+                ProgressStyle::with_template(
+                    "{spinner:.green} {msg} [{bar:30.green/white}] {pos}/{len} rows ({eta})",
+                )
+                .unwrap()
+                .progress_chars("=> "),
+            );
+            pb.set_length(total as u64);
+            pb.set_position(0);
+            pb.set_message("Extracting");
+        }
+        ExtractionProgress::Advanced { rows } => pb.inc(rows as u64),
+    }
+}
+
 /// Handle the validate subcommand  
 async fn handle_validate_command(cli: &Cli) -> Result<()> {
     if let Commands::Validate {
         config_file,
         detailed,
+        check_write,
     } = &cli.command
     {
         info!("Validating configuration");
@@ -367,7 +1020,7 @@ async fn handle_validate_command(cli: &Cli) -> Result<()> {
         };
 
         // Load and validate configuration
-        let config = load_configuration(
+        let mut config = load_configuration(
             cli,
             &config_file
                 .as_ref()
@@ -375,12 +1028,64 @@ async fn handle_validate_command(cli: &Cli) -> Result<()> {
             &None,
             &None,
         )?;
+        use_aws_region(cli, &mut config);
+        config.resolve_file_uris()?;
 
         if let Some(pb) = &progress {
             pb.set_message("Running configuration checks...");
         }
 
-        validate_config(&config).await?;
+        let mut validation = validate_config(&config).await;
+        validation
+            .warnings
+            .extend(point_tolerance_warnings(&config)?);
+
+        // Remote checks only run once the configuration itself is valid
+        if validation.is_valid() {
+            // Confirm remote inputs are NetCDF from their first bytes, without
+            // downloading them; compressed inputs can only be checked once decompressed
+            if config.nc_key.starts_with("s3://") && !is_gzip_input(&config.nc_key) {
+                if let Some(pb) = &progress {
+                    pb.set_message("Checking input file signature...");
+                }
+                let storage = StorageFactory::from_path(&config.nc_key).await?;
+                match storage::validate_netcdf_magic(&storage, &config.nc_key).await {
+                    Ok(format) => info!("Input {} is a {} file", config.nc_key, format),
+                    Err(e) => validation
+                        .errors
+                        .push(format!("Input file check failed: {}: {}", config.nc_key, e)),
+                }
+            }
+
+            if *check_write {
+                if let Some(pb) = &progress {
+                    pb.set_message("Checking output is writable...");
+                }
+                if let Err(e) = check_output_writable(&config.parquet_key).await {
+                    validation.errors.push(format!("{:#}", e));
+                }
+            }
+        }
+
+        // JSON and YAML output carries the warnings and errors, so nothing is
+        // logged that a parser would have to skip
+        if matches!(cli.output_format, OutputFormat::Json | OutputFormat::Yaml) {
+            if let Some(pb) = &progress {
+                pb.finish_and_clear();
+            }
+            let config = detailed.then_some(&config);
+            print_validation_report(&validation, config, &cli.output_format)?;
+            if !validation.is_valid() {
+                return Err(anyhow::anyhow!(
+                    "Configuration validation failed with {} error(s)",
+                    validation.errors.len()
+                ));
+            }
+            return Ok(());
+        }
+
+        validation.log_warnings();
+        validation.ensure_valid()?;
 
         if let Some(pb) = &progress {
             pb.finish_with_message("✓ Configuration valid!");
@@ -404,6 +1109,7 @@ async fn handle_info_command(cli: &Cli) -> Result<()> {
         file,
         detailed,
         variable,
+        header_only,
         format,
     } = &cli.command
     {
@@ -426,7 +1132,11 @@ async fn handle_info_command(cli: &Cli) -> Result<()> {
 
         let output_format = format.as_ref().unwrap_or(&cli.output_format);
 
-        let file_info = get_netcdf_info(file, variable.as_deref(), *detailed).await?;
+        let file_info = if *header_only {
+            get_netcdf_header_info(file, variable.as_deref(), *detailed).await?
+        } else {
+            get_netcdf_info(file, variable.as_deref(), *detailed).await?
+        };
 
         if let Some(pb) = progress {
             pb.finish_with_message("✅ File analysis completed");
@@ -445,44 +1155,199 @@ async fn handle_info_command(cli: &Cli) -> Result<()> {
     Ok(())
 }
 
-/// Handle the template subcommand
-async fn handle_template_command(cli: &Cli) -> Result<()> {
-    if let Commands::Template {
-        template_type,
-        output,
+/// Handle the diff subcommand
+async fn handle_diff_command(cli: &Cli) -> Result<()> {
+    if let Commands::Diff { old, new, format } = &cli.command {
+        info!("Comparing {} with {}", old, new);
+
+        let old_info = get_netcdf_info(old, None, true).await?;
+        let new_info = get_netcdf_info(new, None, true).await?;
+        let diff = diff_netcdf_info(&old_info, &new_info);
+
+        let output_format = format.as_ref().unwrap_or(&cli.output_format);
+        match output_format {
+            OutputFormat::Human => print_netcdf_diff_human(&diff),
+            OutputFormat::Json => print_netcdf_diff_json(&diff)?,
+            OutputFormat::Yaml => print_netcdf_diff_yaml(&diff)?,
+            OutputFormat::Csv => print_netcdf_diff_csv(&diff)?,
+        }
+    } else {
+        unreachable!("Diff command handler called with wrong command type");
+    }
+
+    Ok(())
+}
+
+/// Handle the peek subcommand
+async fn handle_peek_command(cli: &Cli) -> Result<()> {
+    if let Commands::Peek {
+        file,
+        variable,
+        at,
+        index,
         format,
     } = &cli.command
     {
-        let template = generate_template(template_type, format)?;
+        let specs: Vec<_> = at.iter().chain(index).cloned().collect();
+        let cell = peek_netcdf_cell(file, variable.as_deref(), &specs).await?;
 
-        match output {
-            Some(path) => {
-                std::fs::write(path, &template).context("Failed to write template file")?;
-                info!("Template written to: {}", path.display());
-            }
-            None => {
-                println!("{}", template);
-            }
+        let output_format = format.as_ref().unwrap_or(&cli.output_format);
+        match output_format {
+            OutputFormat::Human => print_peeked_cell_human(&cell),
+            OutputFormat::Json => print_peeked_cell_json(&cell)?,
+            OutputFormat::Yaml => print_peeked_cell_yaml(&cell)?,
+            OutputFormat::Csv => print_peeked_cell_csv(&cell)?,
         }
     } else {
-        unreachable!("Template command handler called with wrong command type");
+        unreachable!("Peek command handler called with wrong command type");
     }
 
     Ok(())
 }
 
-/// Handle the completions subcommand
-async fn handle_completions_command(cli: &Cli) -> Result<()> {
-    if let Commands::Completions { shell, output } = &cli.command {
-        info!("Generating shell completions for: {:?}", shell);
-
-        let mut cmd = Cli::command();
-        let name = cmd.get_name().to_string();
-
-        let completions = match shell {
-            Shell::Bash => {
-                let mut buf = Vec::new();
-                generate(Shell::Bash, &mut cmd, name, &mut buf);
+/// Handle the sample subcommand
+async fn handle_sample_command(cli: &Cli) -> Result<()> {
+    if let Commands::Sample {
+        input,
+        variable,
+        rows,
+        range_filters,
+        list_filters,
+        point2d_filters,
+        point3d_filters,
+        time_range,
+        time_dimension,
+        filter_merge,
+        combine,
+        decode_times,
+        include_indices,
+        format,
+    } = &cli.command
+    {
+        // Nothing is written, so no output path is needed
+        let mut config = load_configuration(cli, input, &Some(String::new()), variable)?;
+        use_aws_region(cli, &mut config);
+        config.resolve_file_uris()?;
+
+        if *decode_times {
+            config.decode_times = true;
+        }
+        if *include_indices {
+            config.include_indices = true;
+        }
+        if let Some(combine) = combine {
+            config.combine = *combine;
+        }
+        config.filters.extend(cli_filters(
+            range_filters,
+            list_filters,
+            point2d_filters,
+            point3d_filters,
+            time_range,
+            time_dimension,
+            *filter_merge,
+        )?);
+
+        info!("Sampling {} rows of {}", rows, config.nc_key);
+        let df = sample_dataframe_async(&config, *rows).await?;
+
+        let output_format = format.as_ref().unwrap_or(&cli.output_format);
+        match output_format {
+            OutputFormat::Human => {
+                // Polars elides the middle of tables longer than POLARS_FMT_MAX_ROWS
+                unsafe {
+                    std::env::set_var("POLARS_FMT_MAX_ROWS", rows.to_string());
+                }
+                print_sample_human(&df);
+            }
+            OutputFormat::Json => print_sample_json(&df)?,
+            OutputFormat::Yaml => print_sample_yaml(&df)?,
+            OutputFormat::Csv => print_sample_csv(&df)?,
+        }
+    } else {
+        unreachable!("Sample command handler called with wrong command type");
+    }
+
+    Ok(())
+}
+
+/// Handle the check-schema subcommand
+async fn handle_check_schema_command(cli: &Cli) -> Result<()> {
+    if let Commands::CheckSchema { parquet, format } = &cli.command {
+        let config_path = cli
+            .config
+            .as_ref()
+            .context("A configuration file is required (use --config)")?;
+        let mut config = load_config_file(config_path)?;
+        config.resolve_file_uris()?;
+
+        info!(
+            "Checking schema of {} against {}",
+            parquet,
+            config_path.display()
+        );
+
+        let report = check_parquet_schema(&config, parquet).await?;
+
+        let output_format = format.as_ref().unwrap_or(&cli.output_format);
+        match output_format {
+            OutputFormat::Human => print_schema_report_human(&report),
+            OutputFormat::Json => print_schema_report_json(&report)?,
+            OutputFormat::Yaml => print_schema_report_yaml(&report)?,
+            OutputFormat::Csv => print_schema_report_csv(&report)?,
+        }
+
+        if !report.is_match() {
+            return Err(anyhow::anyhow!(
+                "Parquet schema does not match configuration ({} mismatch(es))",
+                report.mismatches.len()
+            ));
+        }
+    } else {
+        unreachable!("CheckSchema command handler called with wrong command type");
+    }
+
+    Ok(())
+}
+
+/// Handle the template subcommand
+async fn handle_template_command(cli: &Cli) -> Result<()> {
+    if let Commands::Template {
+        template_type,
+        output,
+        format,
+    } = &cli.command
+    {
+        let template = generate_template(template_type, format)?;
+
+        match output {
+            Some(path) => {
+                std::fs::write(path, &template).context("Failed to write template file")?;
+                info!("Template written to: {}", path.display());
+            }
+            None => {
+                println!("{}", template);
+            }
+        }
+    } else {
+        unreachable!("Template command handler called with wrong command type");
+    }
+
+    Ok(())
+}
+
+/// Handle the completions subcommand
+async fn handle_completions_command(cli: &Cli) -> Result<()> {
+    if let Commands::Completions { shell, output } = &cli.command {
+        info!("Generating shell completions for: {:?}", shell);
+
+        let mut cmd = Cli::command();
+        let name = cmd.get_name().to_string();
+
+        let completions = match shell {
+            Shell::Bash => {
+                let mut buf = Vec::new();
+                generate(Shell::Bash, &mut cmd, name, &mut buf);
                 String::from_utf8(buf).context("Failed to generate bash completions")?
             }
             Shell::Zsh => {
@@ -587,9 +1452,12 @@ fn load_configuration(
         .or(env_output.as_ref())
         .context("Output file path is required (use --config file, provide OUTPUT argument, or set NC2PARQUET_OUTPUT environment variable)")?;
 
-    let var_name = variable.as_ref()
+    // Without a variable, the file's only data variable is converted
+    let var_name = variable
+        .as_ref()
         .or(env_variable.as_ref())
-        .context("Variable name is required (use --config file, --variable option, or set NC2PARQUET_VARIABLE environment variable)")?;
+        .cloned()
+        .unwrap_or_default();
 
     debug!(
         "Created configuration from CLI/environment - input: {}, output: {}, variable: {}",
@@ -598,10 +1466,39 @@ fn load_configuration(
 
     Ok(JobConfig {
         nc_key: input_path.clone(),
-        variable_name: var_name.clone(),
+        variable_name: var_name,
+        filters_dsl: Vec::new(),
         parquet_key: output_path.clone(),
         filters: Vec::new(),
         postprocessing: None,
+        coordinate_transforms: HashMap::new(),
+        coordinate_renames: HashMap::new(),
+        sort_coordinates: false,
+        coordinate_order: Vec::new(),
+        limit: None,
+        sample: None,
+        decode_times: false,
+        include_indices: false,
+        write_cell_index: false,
+        write_manifest: false,
+        start_record: None,
+        rename_from_attributes: false,
+        variable_overrides: HashMap::new(),
+        nan_policy: NanPolicy::Keep,
+        combine: FilterCombine::And,
+        parquet_bloom_filter_columns: Vec::new(),
+        auto_unit_convert: HashMap::new(),
+        round_decimals: None,
+        round_columns: Vec::new(),
+        round_coordinates: false,
+        drop_coordinates: false,
+        fail_on_empty: false,
+        compression: CompressionCodec::Zstd,
+        compression_level: None,
+        statistics: None,
+        variable_names: Vec::new(),
+        split_variables: false,
+        aws_region: None,
     })
 }
 
@@ -611,20 +1508,169 @@ fn load_config_file(path: &Path) -> Result<JobConfig> {
         .with_context(|| format!("Failed to read configuration file: {}", path.display()))?;
 
     // Try to determine format by extension, fallback to JSON
-    let config = if path.extension().and_then(|s| s.to_str()) == Some("yaml")
+    let mut config: JobConfig = if path.extension().and_then(|s| s.to_str()) == Some("yaml")
         || path.extension().and_then(|s| s.to_str()) == Some("yml")
     {
         serde_yaml::from_str(&content).context("Failed to parse YAML configuration")?
     } else {
         serde_json::from_str(&content).context("Failed to parse JSON configuration")?
     };
+    config.expand_filters_dsl()?;
 
     debug!("Configuration loaded successfully from {}", path.display());
     Ok(config)
 }
 
-/// Validate configuration
-async fn validate_config(config: &JobConfig) -> Result<()> {
+/// Warnings for point filters whose tolerance is less than half the grid spacing
+/// of a local input, so that points between grid nodes match nothing.
+///
+/// Remote and compressed inputs are not checked, as their coordinates could only
+/// be read after downloading them. Snapping 2-D point filters never come up empty
+/// and are skipped too.
+fn point_tolerance_warnings(config: &JobConfig) -> Result<Vec<String>> {
+    let checks: Vec<(usize, f64, [&String; 2])> = config
+        .filters
+        .iter()
+        .enumerate()
+        .filter(|(_, filter)| filter.is_enabled())
+        .filter_map(|(i, filter)| match filter {
+            FilterConfig::Point2D { params } if !params.snap_nearest => Some((
+                i,
+                params.tolerance,
+                [&params.lat_dimension_name, &params.lon_dimension_name],
+            )),
+            FilterConfig::Point3D { params } => Some((
+                i,
+                params.tolerance,
+                [&params.lat_dimension_name, &params.lon_dimension_name],
+            )),
+            _ => None,
+        })
+        .collect();
+    if checks.is_empty()
+        || config.nc_key.starts_with("s3://")
+        || is_gzip_input(&config.nc_key)
+        || !Path::new(&config.nc_key).is_file()
+    {
+        return Ok(Vec::new());
+    }
+
+    let file = netcdf::open(&config.nc_key)
+        .with_context(|| format!("Failed to open NetCDF file: {}", config.nc_key))?;
+    let mut cache = CoordinateCache::new();
+    let mut warnings = Vec::new();
+    for (i, tolerance, dimensions) in checks {
+        for dimension in dimensions {
+            let spacing =
+                axis_spacing(&file, &mut cache, dimension).map_err(|e| anyhow::anyhow!("{}", e))?;
+            if let Some(spacing) = spacing
+                && tolerance < spacing / 2.0
+            {
+                warnings.push(format!(
+                    "Filter {}: point tolerance {} is less than half the '{}' grid spacing ({}); points between grid nodes will match nothing",
+                    i + 1,
+                    tolerance,
+                    dimension,
+                    spacing
+                ));
+            }
+        }
+    }
+    Ok(warnings)
+}
+
+/// Errors for the range, list and time filters on dimensions without a coordinate
+/// variable, which would only fail once the file is processed.
+///
+/// Like [`point_tolerance_warnings`], only local, uncompressed inputs are checked;
+/// files that cannot be opened are left for the conversion to report.
+fn missing_coordinate_errors(config: &JobConfig) -> Vec<String> {
+    let checks: Vec<(usize, &String)> = config
+        .filters
+        .iter()
+        .enumerate()
+        .filter(|(_, filter)| filter.is_enabled())
+        .filter_map(|(i, filter)| match filter {
+            FilterConfig::Range { params } => Some((i, &params.dimension_name)),
+            FilterConfig::List { params } => Some((i, &params.dimension_name)),
+            FilterConfig::TimeRange { params } => Some((i, &params.time_dimension_name)),
+            FilterConfig::NearestTime { params } => Some((i, &params.time_dimension_name)),
+            _ => None,
+        })
+        .collect();
+    if checks.is_empty()
+        || config.nc_key.starts_with("s3://")
+        || is_gzip_input(&config.nc_key)
+        || !Path::new(&config.nc_key).is_file()
+    {
+        return Vec::new();
+    }
+
+    let Ok(file) = netcdf::open(&config.nc_key) else {
+        return Vec::new();
+    };
+    checks
+        .into_iter()
+        .filter(|(_, dimension)| file.variable(dimension).is_none())
+        .map(|(i, dimension)| {
+            format!(
+                "Filter {}: {}",
+                i + 1,
+                missing_coordinate_message(&file, dimension)
+            )
+        })
+        .collect()
+}
+
+/// Errors and warnings found while validating a configuration
+#[derive(Debug, Default, Serialize)]
+struct ValidationReport {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+}
+
+impl ValidationReport {
+    fn is_valid(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Logs the warnings, for human-readable output
+    fn log_warnings(&self) {
+        for warning in &self.warnings {
+            warn!("Configuration warning: {}", warning);
+        }
+    }
+
+    /// Fails with every error listed when the configuration is invalid
+    fn ensure_valid(&self) -> Result<()> {
+        if !self.is_valid() {
+            let error_msg = format!(
+                "Configuration validation failed with {} error(s):\n{}",
+                self.errors.len(),
+                self.errors
+                    .iter()
+                    .enumerate()
+                    .map(|(i, e)| format!("  {}. {}", i + 1, e))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            );
+            return Err(anyhow::anyhow!(error_msg));
+        }
+
+        if self.warnings.is_empty() {
+            info!("Configuration validation passed");
+        } else {
+            info!(
+                "Configuration validation passed with {} warning(s)",
+                self.warnings.len()
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Validate configuration, collecting every error and warning found
+async fn validate_config(config: &JobConfig) -> ValidationReport {
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
@@ -642,8 +1688,9 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
             }
         }
 
-        // Check file extension
-        if !config.nc_key.ends_with(".nc") && !config.nc_key.ends_with(".nc4") {
+        // Check file extension, ignoring the .gz suffix of compressed inputs
+        let nc_key = config.nc_key.strip_suffix(".gz").unwrap_or(&config.nc_key);
+        if !nc_key.ends_with(".nc") && !nc_key.ends_with(".nc4") {
             warnings.push(format!(
                 "Input file does not have a typical NetCDF extension (.nc or .nc4): {}",
                 config.nc_key
@@ -654,10 +1701,11 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
     if config.parquet_key.is_empty() {
         errors.push("Output Parquet path cannot be empty".to_string());
     } else {
-        // Check output directory exists (for local files)
+        // Check output directory exists and accepts new files (for local files)
         if !config.parquet_key.starts_with("s3://") {
             let output_path = std::path::Path::new(&config.parquet_key);
             if let Some(parent) = output_path.parent()
+                && !parent.as_os_str().is_empty()
                 && !parent.exists()
             {
                 warnings.push(format!(
@@ -665,6 +1713,9 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
                     parent.display()
                 ));
             }
+            if let Err(e) = check_local_output(&config.parquet_key) {
+                errors.push(format!("Output is not writable: {}", e));
+            }
         }
 
         // Check file extension
@@ -676,15 +1727,36 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
         }
     }
 
-    if config.variable_name.is_empty() {
-        errors.push("Variable name cannot be empty".to_string());
-    } else if config.variable_name.contains(" ") || config.variable_name.contains("\t") {
+    if config.variable_name.contains(" ") || config.variable_name.contains("\t") {
         errors.push(format!(
             "Variable name contains whitespace: '{}'",
             config.variable_name
         ));
     }
 
+    if !config.variable_names.is_empty() && !config.split_variables {
+        errors.push(
+            "variable_names is only used with split_variables (one output per variable)"
+                .to_string(),
+        );
+    }
+    if config.split_variables && !config.variable_name.is_empty() {
+        errors.push(format!(
+            "variable_name '{}' cannot be combined with split_variables; list the variables in variable_names",
+            config.variable_name
+        ));
+    }
+
+    if let Some(temp_dir) = storage::temp_dir()
+        && let Err(e) = storage::check_temp_dir(temp_dir)
+    {
+        errors.push(format!("Invalid temporary directory: {}", e));
+    }
+
+    if let Err(e) = config.output_options().parquet_compression() {
+        errors.push(e.to_string());
+    }
+
     // Validate filters
     for (i, filter) in config.filters.iter().enumerate() {
         match filter.to_filter() {
@@ -720,6 +1792,15 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
                                 i + 1
                             ));
                         }
+                        if let Some(tolerance) = params.tolerance
+                            && tolerance < 0.0
+                        {
+                            errors.push(format!(
+                                "Filter {}: List tolerance cannot be negative: {}",
+                                i + 1,
+                                tolerance
+                            ));
+                        }
                     }
                     nc2parquet::input::FilterConfig::Point2D { params } => {
                         if params.points.is_empty() {
@@ -759,6 +1840,144 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
                             errors.push(format!("Filter {}: 3D point time, latitude, and longitude dimension names cannot be empty", i + 1));
                         }
                     }
+                    nc2parquet::input::FilterConfig::BoundingBox3D { params } => {
+                        if params.min_lat > params.max_lat {
+                            errors.push(format!(
+                                "Filter {}: 3D bounding box min_lat ({}) is greater than max_lat ({})",
+                                i + 1,
+                                params.min_lat,
+                                params.max_lat
+                            ));
+                        }
+                        if params.min_lon > params.max_lon && !params.normalize_longitude {
+                            errors.push(format!(
+                                "Filter {}: 3D bounding box min_lon ({}) is greater than max_lon ({})",
+                                i + 1,
+                                params.min_lon,
+                                params.max_lon
+                            ));
+                        }
+                        if let (Some(start), Some(end)) = (params.start, params.end)
+                            && start > end
+                        {
+                            errors.push(format!(
+                                "Filter {}: 3D bounding box start ({}) is after its end ({})",
+                                i + 1,
+                                start,
+                                end
+                            ));
+                        }
+                        if let (Some(start_date), Some(end_date)) =
+                            (&params.start_date, &params.end_date)
+                        {
+                            match (
+                                parse_reference_datetime(start_date),
+                                parse_reference_datetime(end_date),
+                            ) {
+                                (Ok((start, _)), Ok((end, _))) if start > end => {
+                                    errors.push(format!(
+                                        "Filter {}: 3D bounding box start_date {} is after its end_date {}",
+                                        i + 1,
+                                        start_date,
+                                        end_date
+                                    ));
+                                }
+                                (Ok(_), Ok(_)) => {}
+                                _ => errors.push(format!(
+                                    "Filter {}: 3D bounding box dates must be ISO 8601 times (got {}..{})",
+                                    i + 1,
+                                    start_date,
+                                    end_date
+                                )),
+                            }
+                        }
+                        if params.time_dimension_name.is_empty()
+                            || params.lat_dimension_name.is_empty()
+                            || params.lon_dimension_name.is_empty()
+                        {
+                            errors.push(format!("Filter {}: 3D bounding box time, latitude, and longitude dimension names cannot be empty", i + 1));
+                        }
+                    }
+                    nc2parquet::input::FilterConfig::Stride { params } => {
+                        if params.count == Some(0) {
+                            warnings.push(format!(
+                                "Filter {}: Stride filter has a count of 0 (will match nothing)",
+                                i + 1
+                            ));
+                        }
+                        if params.dimension_name.is_empty() {
+                            errors.push(format!(
+                                "Filter {}: Stride dimension_name cannot be empty",
+                                i + 1
+                            ));
+                        }
+                    }
+                    nc2parquet::input::FilterConfig::IndexRange { params } => {
+                        if params.start >= params.end {
+                            warnings.push(format!(
+                                "Filter {}: Index range [{}:{}] is empty (will match nothing)",
+                                i + 1,
+                                params.start,
+                                params.end
+                            ));
+                        }
+                        if params.dimension_name.is_empty() {
+                            errors.push(format!(
+                                "Filter {}: Index range dimension_name cannot be empty",
+                                i + 1
+                            ));
+                        }
+                    }
+                    nc2parquet::input::FilterConfig::TimeRange { params } => {
+                        if params.time_dimension_name.is_empty() {
+                            errors.push(format!(
+                                "Filter {}: Time range time_dimension_name cannot be empty",
+                                i + 1
+                            ));
+                        }
+                        let start = parse_reference_datetime(&params.start_date);
+                        let end = parse_reference_datetime(&params.end_date);
+                        match (start, end) {
+                            (Ok((start, _)), Ok((end, _))) if start > end => {
+                                errors.push(format!(
+                                    "Filter {}: Time range start {} is after its end {}",
+                                    i + 1,
+                                    params.start_date,
+                                    params.end_date
+                                ));
+                            }
+                            (Ok(_), Ok(_)) => {}
+                            _ => errors.push(format!(
+                                "Filter {}: Time range bounds must be ISO 8601 times (got {}..{})",
+                                i + 1,
+                                params.start_date,
+                                params.end_date
+                            )),
+                        }
+                    }
+                    nc2parquet::input::FilterConfig::NearestTime { params } => {
+                        if params.time_dimension_name.is_empty() {
+                            errors.push(format!(
+                                "Filter {}: Nearest time time_dimension_name cannot be empty",
+                                i + 1
+                            ));
+                        }
+                        if params.n == 0 {
+                            warnings.push(format!(
+                                "Filter {}: Nearest time filter has n = 0 (will match nothing)",
+                                i + 1
+                            ));
+                        }
+                        if let Some(target_date) = &params.target_date
+                            && parse_reference_datetime(target_date).is_err()
+                        {
+                            errors.push(format!(
+                                "Filter {}: Nearest time target_date must be an ISO 8601 time (got {})",
+                                i + 1,
+                                target_date
+                            ));
+                        }
+                    }
                 }
             }
             Err(e) => {
@@ -767,6 +1986,81 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
         }
     }
 
+    errors.extend(missing_coordinate_errors(config));
+
+    if config.limit == Some(0) {
+        warnings.push("Row limit is 0; the output file will be empty".to_string());
+    }
+
+    if let Some(sample) = config.sample {
+        if sample.n == 0 {
+            warnings.push("Sample size is 0; the output file will be empty".to_string());
+        }
+        if sample.seed.is_none() {
+            warnings
+                .push("Sample has no seed; the selected rows will differ between runs".to_string());
+        }
+    }
+
+    if let Some(postprocessing) = &config.postprocessing {
+        for processor in &postprocessing.processors {
+            if let ProcessorConfig::Aggregate {
+                group_by,
+                aggregations,
+            } = processor
+                && let Some(column) = aggregations.keys().find(|column| group_by.contains(column))
+            {
+                errors.push(format!(
+                    "Column '{}' cannot be both grouped by and aggregated",
+                    column
+                ));
+            }
+        }
+    }
+
+    if config.write_cell_index
+        && let Some(postprocessing) = &config.postprocessing
+        && postprocessing
+            .processors
+            .iter()
+            .any(|processor| !processor.keeps_rows())
+    {
+        errors.push(
+            "A cell index cannot be written when post-processing drops, adds or reorders rows"
+                .to_string(),
+        );
+    }
+
+    for (dimension, transform) in &config.coordinate_transforms {
+        if !transform.scale.is_finite() || !transform.offset.is_finite() {
+            errors.push(format!(
+                "Coordinate transform for '{}' must have finite scale and offset",
+                dimension
+            ));
+        } else if transform.scale == 0.0 {
+            warnings.push(format!(
+                "Coordinate transform for '{}' has scale 0 and collapses all values",
+                dimension
+            ));
+        }
+    }
+
+    let mut rename_targets: Vec<&String> = config.coordinate_renames.values().collect();
+    rename_targets.sort();
+    for (dimension, new_name) in &config.coordinate_renames {
+        if new_name.trim().is_empty() {
+            errors.push(format!(
+                "Coordinate rename for '{}' must have a non-empty name",
+                dimension
+            ));
+        }
+    }
+    for pair in rename_targets.windows(2) {
+        if pair[0] == pair[1] {
+            errors.push(format!("Several coordinates are renamed to '{}'", pair[0]));
+        }
+    }
+
     // Environment variable validation (if any are set)
     if std::env::var("NC2PARQUET_CONFIG").is_ok()
         && std::env::var("NC2PARQUET_CONFIG").unwrap().is_empty()
@@ -774,36 +2068,50 @@ async fn validate_config(config: &JobConfig) -> Result<()> {
         warnings.push("NC2PARQUET_CONFIG environment variable is set but empty".to_string());
     }
 
-    // Output warnings
-    for warning in &warnings {
-        warn!("Configuration warning: {}", warning);
-    }
+    debug!(
+        "Configuration validation found {} error(s) and {} warning(s)",
+        errors.len(),
+        warnings.len()
+    );
+    ValidationReport { errors, warnings }
+}
 
-    // Check for errors
-    if !errors.is_empty() {
-        let error_msg = format!(
-            "Configuration validation failed with {} error(s):\n{}",
-            errors.len(),
-            errors
-                .iter()
-                .enumerate()
-                .map(|(i, e)| format!("  {}. {}", i + 1, e))
-                .collect::<Vec<_>>()
-                .join("\n")
-        );
-        return Err(anyhow::anyhow!(error_msg));
+/// Prints a validation report, with the validated configuration when given, as
+/// a `validation` object for JSON and YAML output
+fn print_validation_report(
+    report: &ValidationReport,
+    config: Option<&JobConfig>,
+    format: &OutputFormat,
+) -> Result<()> {
+    let mut output = serde_json::json!({
+        "validation": {
+            "valid": report.is_valid(),
+            "errors": report.errors,
+            "warnings": report.warnings,
+        }
+    });
+    if let Some(config) = config {
+        output["config"] = serde_json::to_value(config)?;
     }
-
-    if warnings.is_empty() {
-        info!("Configuration validation passed");
-    } else {
-        info!(
-            "Configuration validation passed with {} warning(s)",
-            warnings.len()
-        );
+    match format {
+        OutputFormat::Yaml => {
+            let yaml = serde_yaml::to_string(&output)
+                .context("Failed to serialize validation report to YAML")?;
+            println!("{}", yaml);
+        }
+        _ => println!("{}", serde_json::to_string_pretty(&output)?),
     }
+    Ok(())
+}
 
-    debug!("Configuration validation completed successfully");
+/// Checks that `output_path` can be written by writing and deleting an empty
+/// object next to it, so missing permissions fail before a long extraction
+async fn check_output_writable(output_path: &str) -> Result<()> {
+    let storage = StorageFactory::from_path(output_path).await?;
+    check_writable(&storage, output_path)
+        .await
+        .with_context(|| format!("Output is not writable: {}", output_path))?;
+    debug!("Output {} is writable", output_path);
     Ok(())
 }
 
@@ -813,7 +2121,7 @@ async fn check_output_overwrite(output_path: &str) -> Result<()> {
 
     if storage.exists(output_path).await? {
         return Err(anyhow::anyhow!(
-            "Output file already exists: {}. Use --force to overwrite",
+            "Output file already exists: {}. Use --force to overwrite, or --overwrite-policy version to write next to it",
             output_path
         ));
     }
@@ -821,6 +2129,183 @@ async fn check_output_overwrite(output_path: &str) -> Result<()> {
     Ok(())
 }
 
+/// Reads the outputs listed in a state file, one per line; a missing file lists none
+fn read_state_file(state_file: &Path) -> Result<HashSet<String>> {
+    if !state_file.exists() {
+        return Ok(HashSet::new());
+    }
+    let contents = std::fs::read_to_string(state_file)
+        .with_context(|| format!("Failed to read state file: {}", state_file.display()))?;
+    Ok(contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Whether `output_path` is listed in the state file and still exists, locally or
+/// on S3; a listed output that has since been removed is produced again
+async fn is_completed_output(state_file: &Path, output_path: &str) -> Result<bool> {
+    if !read_state_file(state_file)?.contains(output_path) {
+        return Ok(false);
+    }
+    let storage = StorageFactory::from_path(output_path).await?;
+    if storage.exists(output_path).await? {
+        return Ok(true);
+    }
+    warn!(
+        "{} is listed in {} but does not exist; converting it again",
+        output_path,
+        state_file.display()
+    );
+    Ok(false)
+}
+
+/// Appends `output_path` to the state file, creating the file if needed
+fn record_completed_output(state_file: &Path, output_path: &str) -> Result<()> {
+    use std::io::Write;
+
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(state_file)
+        .with_context(|| format!("Failed to open state file: {}", state_file.display()))?;
+    writeln!(file, "{}", output_path)
+        .with_context(|| format!("Failed to write state file: {}", state_file.display()))?;
+    Ok(())
+}
+
+/// Returns `output_path` if nothing exists there yet, or else the first versioned
+/// name (see [`versioned_output_path`]) that is free, locally or on S3
+async fn next_available_output(output_path: &str) -> Result<String> {
+    let storage = StorageFactory::from_path(output_path).await?;
+
+    if !storage.exists(output_path).await? {
+        return Ok(output_path.to_string());
+    }
+    for version in 1.. {
+        let candidate = versioned_output_path(output_path, version);
+        if !storage.exists(&candidate).await? {
+            return Ok(candidate);
+        }
+    }
+    unreachable!("ran out of output versions for {}", output_path)
+}
+
+/// Inserts `.{version}` before the extension of the file name in `path`, e.g.
+/// `out/data.parquet` becomes `out/data.1.parquet`
+fn versioned_output_path(path: &str, version: usize) -> String {
+    let name_start = path.rfind('/').map_or(0, |i| i + 1);
+    match path[name_start..].rfind('.') {
+        Some(dot) if dot > 0 => {
+            let dot = name_start + dot;
+            format!("{}.{}{}", &path[..dot], version, &path[dot..])
+        }
+        _ => format!("{}.{}", path, version),
+    }
+}
+
+/// Outputs of a split job whose variables are known
+fn split_output_paths(config: &JobConfig) -> Vec<String> {
+    config
+        .variable_names
+        .iter()
+        .map(|variable| split_output_path(&config.parquet_key, variable))
+        .collect()
+}
+
+/// Estimates the memory of the job (see [`estimate_job_memory`]), printing the
+/// estimate when `print` is set, and enforces `memory_limit`.
+///
+/// A `streamable` job without a chunk size whose estimate exceeds the limit is
+/// switched to the largest chunks that fit; any other job over the limit is
+/// aborted. Returns the chunk size to use. Remote and split jobs are not
+/// estimated, as their inputs are only opened later.
+fn apply_memory_estimate(
+    config: &JobConfig,
+    chunk_size: Option<usize>,
+    print: bool,
+    memory_limit: Option<u64>,
+    streamable: bool,
+) -> Result<Option<usize>> {
+    if needs_async_processing(config) || config.split_variables {
+        warn!("Memory is only estimated for local, single-variable jobs");
+        return Ok(chunk_size);
+    }
+    let estimate = estimate_job_memory(config)
+        .map_err(|e| anyhow::anyhow!("{}", e))
+        .context("Failed to estimate memory")?;
+
+    let streamed_chunk = chunk_size.filter(|_| streamable);
+    let peak = match streamed_chunk {
+        Some(chunk_size) => estimate.chunked_peak_bytes(chunk_size),
+        None => estimate.peak_bytes(),
+    };
+    if print {
+        println!("Memory Estimate:");
+        println!("  Rows:         {}", estimate.rows);
+        println!("  Row size:     {} bytes", estimate.row_bytes);
+        println!(
+            "  Combinations: {}",
+            format_bytes(estimate.combination_bytes)
+        );
+        match streamed_chunk {
+            Some(chunk_size) => println!(
+                "  Peak:         {} (chunks of {} rows)",
+                format_bytes(peak),
+                chunk_size
+            ),
+            None => println!("  Peak:         {}", format_bytes(peak)),
+        }
+    }
+
+    let Some(limit) = memory_limit else {
+        return Ok(chunk_size);
+    };
+    if peak <= limit {
+        return Ok(chunk_size);
+    }
+    if streamable
+        && chunk_size.is_none()
+        && let Some(rows) = estimate.chunk_size_within(limit)
+    {
+        warn!(
+            "Estimated peak memory {} exceeds the limit of {}; streaming in chunks of {} rows",
+            format_bytes(peak),
+            format_bytes(limit),
+            rows
+        );
+        return Ok(Some(rows));
+    }
+    Err(anyhow::anyhow!(
+        "Estimated peak memory {} exceeds the limit of {}; narrow the filters{} or raise --memory-limit",
+        format_bytes(peak),
+        format_bytes(limit),
+        if streamable {
+            ", lower --chunk-size"
+        } else {
+            ""
+        }
+    ))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.50 GiB`
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.2} {}", value, UNITS[unit])
+    }
+}
+
 /// Check if async processing is needed (for S3 paths)
 fn needs_async_processing(config: &JobConfig) -> bool {
     config.nc_key.starts_with("s3://") || config.parquet_key.starts_with("s3://")
@@ -832,12 +2317,131 @@ fn print_config_summary(config: &JobConfig, format: &OutputFormat) {
         OutputFormat::Human => {
             println!("\nConfiguration Summary:");
             println!("  Input:    {}", config.nc_key);
-            println!("  Variable: {}", config.variable_name);
-            println!("  Output:   {}", config.parquet_key);
+            if config.split_variables {
+                println!("  Outputs:  one per variable");
+                for (variable, output_path) in
+                    config.variable_names.iter().zip(split_output_paths(config))
+                {
+                    println!("    {}: {}", variable, output_path);
+                }
+            } else {
+                println!("  Variable: {}", config.variable_name);
+                println!("  Output:   {}", config.parquet_key);
+            }
             println!("  Filters:  {}", config.filters.len());
 
             for (i, filter) in config.filters.iter().enumerate() {
-                println!("    {}: {}", i + 1, filter.kind());
+                let status = if filter.is_enabled() {
+                    ""
+                } else {
+                    " (disabled)"
+                };
+                match filter.to_filter() {
+                    Ok(described) => println!("    {}: {}{}", i + 1, described.describe(), status),
+                    Err(_) => println!("    {}: {}{}", i + 1, filter.kind(), status),
+                }
+            }
+
+            if let Some(limit) = config.limit {
+                println!("  Limit:    {} rows", limit);
+            }
+
+            if let Some(sample) = config.sample {
+                match sample.seed {
+                    Some(seed) => println!("  Sample:   {} rows (seed {})", sample.n, seed),
+                    None => println!("  Sample:   {} rows (unseeded)", sample.n),
+                }
+            }
+
+            if let Some(start_record) = config.start_record {
+                println!("  Records:  from index {}", start_record);
+            }
+
+            if !config.nan_policy.is_keep() {
+                println!("  NaN:      {:?}", config.nan_policy);
+            }
+
+            if !config.combine.is_and() {
+                println!("  Combine:  {:?} within a dimension", config.combine);
+            }
+
+            if config.include_indices {
+                println!("  Indices:  <dimension>_idx columns");
+            }
+
+            if config.drop_coordinates {
+                println!("  Coords:   dropped from the output");
+            }
+
+            if !config.coordinate_order.is_empty() {
+                println!("  Order:    {} first", config.coordinate_order.join(", "));
+            }
+
+            if config.sort_coordinates {
+                println!("  Order:    coordinates sorted by name");
+            }
+
+            if !config.coordinate_renames.is_empty() {
+                let mut renames: Vec<String> = config
+                    .coordinate_renames
+                    .iter()
+                    .map(|(dimension, name)| format!("{} -> {}", dimension, name))
+                    .collect();
+                renames.sort();
+                println!("  Renames:  {}", renames.join(", "));
+            }
+
+            if config.rename_from_attributes {
+                println!("  Column:   named from standard_name/long_name");
+            }
+
+            if config.write_cell_index {
+                println!("  Index:    {}", cell_index_path(&config.parquet_key));
+            }
+
+            if config.write_manifest {
+                println!("  Manifest: {}", manifest_path(&config.parquet_key));
+            }
+
+            if !config.auto_unit_convert.is_empty() {
+                let mut targets: Vec<String> = config
+                    .auto_unit_convert
+                    .iter()
+                    .map(|(quantity, unit)| format!("{} -> {}", quantity, unit))
+                    .collect();
+                targets.sort();
+                println!("  Units:    {}", targets.join(", "));
+            }
+
+            if let Some(decimals) = config.round_decimals {
+                let columns = if !config.round_columns.is_empty() {
+                    config.round_columns.join(", ")
+                } else if config.round_coordinates {
+                    "all float columns".to_string()
+                } else {
+                    "float value columns".to_string()
+                };
+                println!("  Rounding: {} decimals ({})", decimals, columns);
+            }
+
+            if !config.compression.is_default() || config.compression_level.is_some() {
+                match config.compression_level {
+                    Some(level) => {
+                        println!("  Compress: {:?} (level {})", config.compression, level)
+                    }
+                    None => println!("  Compress: {:?}", config.compression),
+                }
+            }
+
+            if config.statistics == Some(false) {
+                println!("  Stats:    not written");
+            }
+
+            if !config.parquet_bloom_filter_columns.is_empty() {
+                println!(
+                    "  Bloom:    {}",
+                    config.parquet_bloom_filter_columns.join(", ")
+                );
             }
         }
         OutputFormat::Json => {
@@ -881,8 +2485,22 @@ async fn show_output_info(output_path: &str, format: &OutputFormat) -> Result<()
 }
 
 use nc2parquet::info::{
+    data_variable_names, detect_main_variable, diff_netcdf_info, get_netcdf_header_info,
     get_netcdf_info, print_file_info_csv, print_file_info_human, print_file_info_json,
-    print_file_info_yaml,
+    print_file_info_yaml, print_netcdf_diff_csv, print_netcdf_diff_human, print_netcdf_diff_json,
+    print_netcdf_diff_yaml,
+};
+use nc2parquet::peek::{
+    peek_netcdf_cell, print_peeked_cell_csv, print_peeked_cell_human, print_peeked_cell_json,
+    print_peeked_cell_yaml,
+};
+use nc2parquet::sample::{
+    print_sample_csv, print_sample_human, print_sample_json, print_sample_yaml,
+    sample_dataframe_async,
+};
+use nc2parquet::schema_check::{
+    check_parquet_schema, print_schema_report_csv, print_schema_report_human,
+    print_schema_report_json, print_schema_report_yaml,
 };
 
 async fn show_detailed_validation(config: &JobConfig, format: &OutputFormat) -> Result<()> {
@@ -916,32 +2534,45 @@ async fn show_detailed_validation(config: &JobConfig, format: &OutputFormat) ->
         println!("   Total Filters: {}", config.filters.len());
 
         for (i, filter) in config.filters.iter().enumerate() {
+            let status = if filter.is_enabled() {
+                ""
+            } else {
+                " (disabled)"
+            };
             match filter {
                 FilterConfig::Range { params } => {
                     println!(
-                        "     {}. Range Filter: {} ({} to {})",
+                        "     {}. Range Filter: {} ({} to {}){}",
                         i + 1,
                         params.dimension_name,
                         params.min_value,
-                        params.max_value
+                        params.max_value,
+                        status
                     );
                 }
                 FilterConfig::List { params } => {
                     println!(
-                        "     {}. List Filter: {} {:?}",
+                        "     {}. List Filter: {} {:?}{}",
                         i + 1,
                         params.dimension_name,
-                        params.values
+                        params.values,
+                        status
                     );
                 }
                 FilterConfig::Point2D { params } => {
                     println!(
-                        "     {}. Point2D Filter: {},{} {} points ±{}",
+                        "     {}. Point2D Filter: {},{} {} points ±{}{}{}",
                         i + 1,
                         params.lat_dimension_name,
                         params.lon_dimension_name,
                         params.points.len(),
-                        params.tolerance
+                        params.tolerance,
+                        if params.snap_nearest {
+                            " (snap to nearest)"
+                        } else {
+                            ""
+                        },
+                        status
                     );
                     for (j, (lat, lon)) in params.points.iter().enumerate() {
                         if j < 3 {
@@ -955,14 +2586,15 @@ async fn show_detailed_validation(config: &JobConfig, format: &OutputFormat) ->
                 }
                 FilterConfig::Point3D { params } => {
                     println!(
-                        "     {}. Point3D Filter: {},{},{} {} points, {} steps ±{}",
+                        "     {}. Point3D Filter: {},{},{} {} points, {} steps ±{}{}",
                         i + 1,
                         params.time_dimension_name,
                         params.lat_dimension_name,
                         params.lon_dimension_name,
                         params.points.len(),
                         params.steps.len(),
-                        params.tolerance
+                        params.tolerance,
+                        status
                     );
                     for (j, (lat, lon)) in params.points.iter().enumerate() {
                         if j < 2 {
@@ -974,6 +2606,79 @@ async fn show_detailed_validation(config: &JobConfig, format: &OutputFormat) ->
                         }
                     }
                 }
+                FilterConfig::BoundingBox3D { params } => {
+                    let window = match (params.start, params.end) {
+                        (Some(start), Some(end)) => format!("{}..{}", start, end),
+                        _ => format!(
+                            "{}..{}",
+                            params.start_date.as_deref().unwrap_or("?"),
+                            params.end_date.as_deref().unwrap_or("?")
+                        ),
+                    };
+                    println!(
+                        "     {}. 3D Bounding Box Filter: {} {}, {} [{}, {}], {} [{}, {}]{}",
+                        i + 1,
+                        params.time_dimension_name,
+                        window,
+                        params.lat_dimension_name,
+                        params.min_lat,
+                        params.max_lat,
+                        params.lon_dimension_name,
+                        params.min_lon,
+                        params.max_lon,
+                        status
+                    );
+                }
+                FilterConfig::Stride { params } => {
+                    println!(
+                        "     {}. Stride Filter: {} every {} from index {}{}{}",
+                        i + 1,
+                        params.dimension_name,
+                        params.stride,
+                        params.start,
+                        params
+                            .count
+                            .map(|count| format!(" (max {})", count))
+                            .unwrap_or_default(),
+                        status
+                    );
+                }
+                FilterConfig::IndexRange { params } => {
+                    println!(
+                        "     {}. Index Range Filter: {}[{}:{}] step {}{}",
+                        i + 1,
+                        params.dimension_name,
+                        params.start,
+                        params.end,
+                        params.step,
+                        status
+                    );
+                }
+                FilterConfig::TimeRange { params } => {
+                    println!(
+                        "     {}. Time Range Filter: {} from {} to {}{}",
+                        i + 1,
+                        params.time_dimension_name,
+                        params.start_date,
+                        params.end_date,
+                        status
+                    );
+                }
+                FilterConfig::NearestTime { params } => {
+                    let target = match (params.target, &params.target_date) {
+                        (Some(target), _) => target.to_string(),
+                        (None, Some(target_date)) => target_date.clone(),
+                        (None, None) => "?".to_string(),
+                    };
+                    println!(
+                        "     {}. Nearest Time Filter: {} nearest {} to {}{}",
+                        i + 1,
+                        params.time_dimension_name,
+                        params.n,
+                        target,
+                        status
+                    );
+                }
             }
         }
     } else {
@@ -994,6 +2699,19 @@ async fn show_detailed_validation(config: &JobConfig, format: &OutputFormat) ->
                 ProcessorConfig::UnitConvert { .. } => "Unit Convert",
                 ProcessorConfig::Aggregate { .. } => "Aggregate",
                 ProcessorConfig::ApplyFormula { .. } => "Apply Formula",
+                ProcessorConfig::CanonicalizeNulls { .. } => "Canonicalize Nulls",
+                ProcessorConfig::Season { .. } => "Season",
+                ProcessorConfig::CumMax { .. } => "Cumulative Max",
+                ProcessorConfig::CumMin { .. } => "Cumulative Min",
+                ProcessorConfig::AsofJoin { .. } => "As-of Join",
+                ProcessorConfig::DegreeDays { .. } => "Degree Days",
+                ProcessorConfig::Transform { .. } => "Transform",
+                ProcessorConfig::Round { .. } => "Round",
+                ProcessorConfig::RowFilter { .. } => "Row Filter",
+                ProcessorConfig::Melt { .. } => "Melt",
+                ProcessorConfig::Pivot { .. } => "Pivot",
+                ProcessorConfig::Pack { .. } => "Pack",
+                ProcessorConfig::Custom { .. } => "Custom",
             };
             println!("     {}. {}", i + 1, processor_type);
         }
@@ -1011,20 +2729,79 @@ fn generate_template(template_type: &TemplateType, format: &ConfigFormat) -> Res
         TemplateType::Basic => JobConfig {
             nc_key: "input.nc".to_string(),
             variable_name: "temperature".to_string(),
+            filters_dsl: Vec::new(),
             parquet_key: "output.parquet".to_string(),
             filters: vec![],
             postprocessing: None,
+            coordinate_transforms: HashMap::new(),
+            coordinate_renames: HashMap::new(),
+            sort_coordinates: false,
+            coordinate_order: Vec::new(),
+            limit: None,
+            sample: None,
+            decode_times: false,
+            include_indices: false,
+            write_cell_index: false,
+            write_manifest: false,
+            start_record: None,
+            rename_from_attributes: false,
+            variable_overrides: HashMap::new(),
+            nan_policy: NanPolicy::Keep,
+            combine: FilterCombine::And,
+            parquet_bloom_filter_columns: Vec::new(),
+            auto_unit_convert: HashMap::new(),
+            round_decimals: None,
+            round_columns: Vec::new(),
+            round_coordinates: false,
+            drop_coordinates: false,
+            fail_on_empty: false,
+            compression: CompressionCodec::Zstd,
+            compression_level: None,
+            statistics: None,
+            variable_names: Vec::new(),
+            split_variables: false,
+            aws_region: None,
         },
         TemplateType::S3 => JobConfig {
             nc_key: "s3://my-bucket/input.nc".to_string(),
             variable_name: "temperature".to_string(),
+            filters_dsl: Vec::new(),
             parquet_key: "s3://my-bucket/output.parquet".to_string(),
             filters: vec![],
             postprocessing: None,
+            coordinate_transforms: HashMap::new(),
+            coordinate_renames: HashMap::new(),
+            sort_coordinates: false,
+            coordinate_order: Vec::new(),
+            limit: None,
+            sample: None,
+            decode_times: false,
+            include_indices: false,
+            write_cell_index: false,
+            write_manifest: false,
+            start_record: None,
+            rename_from_attributes: false,
+            variable_overrides: HashMap::new(),
+            nan_policy: NanPolicy::Keep,
+            combine: FilterCombine::And,
+            parquet_bloom_filter_columns: Vec::new(),
+            auto_unit_convert: HashMap::new(),
+            round_decimals: None,
+            round_columns: Vec::new(),
+            round_coordinates: false,
+            drop_coordinates: false,
+            fail_on_empty: false,
+            compression: CompressionCodec::Zstd,
+            compression_level: None,
+            statistics: None,
+            variable_names: Vec::new(),
+            split_variables: false,
+            aws_region: None,
         },
         TemplateType::MultiFilter => JobConfig {
             nc_key: "weather_data.nc".to_string(),
             variable_name: "temperature".to_string(),
+            filters_dsl: Vec::new(),
             parquet_key: "filtered_weather.parquet".to_string(),
             filters: vec![
                 nc2parquet::input::FilterConfig::Range {
@@ -1032,42 +2809,142 @@ fn generate_template(template_type: &TemplateType, format: &ConfigFormat) -> Res
                         dimension_name: "latitude".to_string(),
                         min_value: 30.0,
                         max_value: 60.0,
+                        normalize_longitude: false,
+                        min_inclusive: true,
+                        max_inclusive: true,
+                        enabled: true,
                     },
                 },
                 nc2parquet::input::FilterConfig::List {
                     params: nc2parquet::input::ListParams {
                         dimension_name: "pressure".to_string(),
                         values: vec![1000.0, 850.0, 500.0],
+                        tolerance: None,
+                        enabled: true,
                     },
                 },
             ],
             postprocessing: None,
+            coordinate_transforms: HashMap::new(),
+            coordinate_renames: HashMap::new(),
+            sort_coordinates: false,
+            coordinate_order: Vec::new(),
+            limit: None,
+            sample: None,
+            decode_times: false,
+            include_indices: false,
+            write_cell_index: false,
+            write_manifest: false,
+            start_record: None,
+            rename_from_attributes: false,
+            variable_overrides: HashMap::new(),
+            nan_policy: NanPolicy::Keep,
+            combine: FilterCombine::And,
+            parquet_bloom_filter_columns: Vec::new(),
+            auto_unit_convert: HashMap::new(),
+            round_decimals: None,
+            round_columns: Vec::new(),
+            round_coordinates: false,
+            drop_coordinates: false,
+            fail_on_empty: false,
+            compression: CompressionCodec::Zstd,
+            compression_level: None,
+            statistics: None,
+            variable_names: Vec::new(),
+            split_variables: false,
+            aws_region: None,
         },
         TemplateType::Weather => JobConfig {
             nc_key: "weather_station_data.nc".to_string(),
             variable_name: "temperature".to_string(),
+            filters_dsl: Vec::new(),
             parquet_key: "weather_analysis.parquet".to_string(),
             filters: vec![nc2parquet::input::FilterConfig::Range {
                 params: nc2parquet::input::RangeParams {
                     dimension_name: "time".to_string(),
                     min_value: 20230101.0,
                     max_value: 20231231.0,
+                    normalize_longitude: false,
+                    min_inclusive: true,
+                    max_inclusive: true,
+                    enabled: true,
                 },
             }],
             postprocessing: None,
+            coordinate_transforms: HashMap::new(),
+            coordinate_renames: HashMap::new(),
+            sort_coordinates: false,
+            coordinate_order: Vec::new(),
+            limit: None,
+            sample: None,
+            decode_times: false,
+            include_indices: false,
+            write_cell_index: false,
+            write_manifest: false,
+            start_record: None,
+            rename_from_attributes: false,
+            variable_overrides: HashMap::new(),
+            nan_policy: NanPolicy::Keep,
+            combine: FilterCombine::And,
+            parquet_bloom_filter_columns: Vec::new(),
+            auto_unit_convert: HashMap::new(),
+            round_decimals: None,
+            round_columns: Vec::new(),
+            round_coordinates: false,
+            drop_coordinates: false,
+            fail_on_empty: false,
+            compression: CompressionCodec::Zstd,
+            compression_level: None,
+            statistics: None,
+            variable_names: Vec::new(),
+            split_variables: false,
+            aws_region: None,
         },
         TemplateType::Ocean => JobConfig {
             nc_key: "ocean_temperature.nc".to_string(),
             variable_name: "sea_surface_temperature".to_string(),
+            filters_dsl: Vec::new(),
             parquet_key: "sst_analysis.parquet".to_string(),
             filters: vec![nc2parquet::input::FilterConfig::Range {
                 params: nc2parquet::input::RangeParams {
                     dimension_name: "depth".to_string(),
                     min_value: 0.0,
                     max_value: 10.0,
+                    normalize_longitude: false,
+                    min_inclusive: true,
+                    max_inclusive: true,
+                    enabled: true,
                 },
             }],
             postprocessing: None,
+            coordinate_transforms: HashMap::new(),
+            coordinate_renames: HashMap::new(),
+            sort_coordinates: false,
+            coordinate_order: Vec::new(),
+            limit: None,
+            sample: None,
+            decode_times: false,
+            include_indices: false,
+            write_cell_index: false,
+            write_manifest: false,
+            start_record: None,
+            rename_from_attributes: false,
+            variable_overrides: HashMap::new(),
+            nan_policy: NanPolicy::Keep,
+            combine: FilterCombine::And,
+            parquet_bloom_filter_columns: Vec::new(),
+            auto_unit_convert: HashMap::new(),
+            round_decimals: None,
+            round_columns: Vec::new(),
+            round_coordinates: false,
+            drop_coordinates: false,
+            fail_on_empty: false,
+            compression: CompressionCodec::Zstd,
+            compression_level: None,
+            statistics: None,
+            variable_names: Vec::new(),
+            split_variables: false,
+            aws_region: None,
         },
     };
 
@@ -0,0 +1,91 @@
+//! # Output Manifest
+//!
+//! This module builds the optional JSON manifest written next to a Parquet output
+//! at `<output>.json` (see [`manifest_path`]), for data catalogs that index files
+//! without opening them.
+//!
+//! Unlike a run report, which records how a conversion went, the manifest
+//! describes the file's contents: its columns and their types, its row count, the
+//! NetCDF file and variable it came from, and the filters and post-processors that
+//! shaped it. It is rewritten whenever the output is.
+
+use crate::input::{FilterConfig, JobConfig};
+use crate::postprocess::ProcessorConfig;
+use crate::storage::{StorageBackend, StorageFactory};
+use polars::prelude::DataFrame;
+use serde::Serialize;
+use std::error::Error;
+
+/// Returns the path of the manifest stored next to an output file
+pub fn manifest_path(output_path: &str) -> String {
+    format!("{}.json", output_path)
+}
+
+/// Name and Polars type of an output column
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ManifestColumn {
+    pub name: String,
+    pub dtype: String,
+}
+
+/// Description of a Parquet output's contents
+#[derive(Serialize)]
+pub struct OutputManifest {
+    pub columns: Vec<ManifestColumn>,
+    pub rows: usize,
+    pub source_file: String,
+    pub source_variable: String,
+    pub filters: Vec<FilterConfig>,
+    pub postprocessors: Vec<ProcessorConfig>,
+}
+
+impl OutputManifest {
+    /// Builds the manifest of `df`, the final DataFrame of a job that extracted
+    /// `variable` from `config.nc_key`
+    pub fn new(config: &JobConfig, variable: &str, df: &DataFrame) -> Self {
+        OutputManifest {
+            columns: df
+                .get_columns()
+                .iter()
+                .map(|column| ManifestColumn {
+                    name: column.name().to_string(),
+                    dtype: column.dtype().to_string(),
+                })
+                .collect(),
+            rows: df.height(),
+            source_file: config.nc_key.clone(),
+            source_variable: variable.to_string(),
+            filters: config
+                .filters
+                .iter()
+                .filter(|filter| filter.is_enabled())
+                .cloned()
+                .collect(),
+            postprocessors: config
+                .postprocessing
+                .as_ref()
+                .map(|pipeline| pipeline.processors.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Sets the row count, for outputs holding more rows than the DataFrame
+    /// the manifest was built from (e.g. after appending)
+    pub fn with_rows(mut self, rows: usize) -> Self {
+        self.rows = rows;
+        self
+    }
+
+    /// Serializes the manifest as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String, Box<dyn Error>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Writes the manifest next to `output_path`, locally or on S3
+    pub async fn write(&self, output_path: &str) -> Result<(), Box<dyn Error>> {
+        let path = manifest_path(output_path);
+        let storage = StorageFactory::from_path(&path).await?;
+        storage.write(&path, self.to_json()?.as_bytes()).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,268 @@
+//! # Output Metadata Module
+//!
+//! This module derives file-level metadata for the Parquet output from the
+//! attributes of the source NetCDF file, so that information such as the
+//! coordinate reference system is not lost during conversion.
+//!
+//! ## Coordinate Reference System
+//!
+//! CF-compliant projected datasets attach a `grid_mapping` attribute to the data
+//! variable, naming a (usually scalar) variable whose attributes describe the
+//! projection. [`read_crs_metadata`] collects those attributes and
+//! [`parquet_metadata`] stores them as JSON under the [`CRS_METADATA_KEY`] key.
+//!
+//! ## Time Zones
+//!
+//! When a time axis of the variable is referenced to a zone other than UTC, its
+//! original offset is stored under the [`TIME_ZONES_METADATA_KEY`] key as a JSON
+//! object mapping dimension names to offsets (e.g. `{"time": "+05:00"}`), since
+//! decoded timestamps are always written in UTC.
+//!
+//! ## Provenance
+//!
+//! Every Parquet output also records the nc2parquet version that wrote it under
+//! [`CREATED_BY_METADATA_KEY`] (e.g. `nc2parquet 0.1.0`) and the UTC time of the
+//! write, in RFC 3339 format, under [`PROCESSED_AT_METADATA_KEY`]; see
+//! [`provenance_metadata`].
+//!
+//! ## Column Names
+//!
+//! With `rename_from_attributes`, the value column is named after the variable's
+//! CF `standard_name` (or `long_name`) instead of the variable name; see
+//! [`column_name_from_attributes`].
+
+use crate::cftime::read_time_reference;
+use chrono::{SecondsFormat, Utc};
+use log::{debug, warn};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Parquet key-value metadata key holding the serialized [`CrsMetadata`]
+pub const CRS_METADATA_KEY: &str = "crs";
+
+/// Parquet key-value metadata key holding the original zones of non-UTC time axes
+pub const TIME_ZONES_METADATA_KEY: &str = "time_zones";
+
+/// Parquet key-value metadata key naming the nc2parquet version that wrote the file
+pub const CREATED_BY_METADATA_KEY: &str = "created_by";
+
+/// Parquet key-value metadata key holding the UTC time the file was written
+pub const PROCESSED_AT_METADATA_KEY: &str = "processed_at";
+
+/// Builds the key-value metadata identifying this nc2parquet version and the
+/// current UTC time, stored in the footer of every Parquet output
+pub fn provenance_metadata() -> Vec<(String, String)> {
+    vec![
+        (
+            CREATED_BY_METADATA_KEY.to_string(),
+            format!("nc2parquet {}", env!("CARGO_PKG_VERSION")),
+        ),
+        (
+            PROCESSED_AT_METADATA_KEY.to_string(),
+            Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+        ),
+    ]
+}
+
+/// Coordinate reference system described by a CF grid mapping variable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CrsMetadata {
+    /// Name of the grid mapping variable in the NetCDF file
+    pub grid_mapping: String,
+    /// Value of the CF `grid_mapping_name` attribute (e.g. `lambert_conformal_conic`)
+    pub grid_mapping_name: Option<String>,
+    /// All attributes of the grid mapping variable, keyed by attribute name
+    pub parameters: BTreeMap<String, serde_json::Value>,
+}
+
+/// Reads the coordinate reference system referenced by a variable's `grid_mapping`.
+///
+/// Both the short form (`grid_mapping = "crs"`) and the extended form
+/// (`grid_mapping = "crs: x y"`) are supported; for the extended form the first
+/// grid mapping variable is used.
+///
+/// # Returns
+///
+/// Returns `Ok(None)` when the variable has no `grid_mapping` attribute or the
+/// referenced variable does not exist (a warning is logged in the latter case).
+pub fn read_crs_metadata(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+) -> Result<Option<CrsMetadata>, Box<dyn std::error::Error>> {
+    let grid_mapping = match var.attribute_value("grid_mapping").transpose()? {
+        Some(value) => String::try_from(value)?,
+        None => return Ok(None),
+    };
+
+    let Some(mapping_name) = grid_mapping
+        .split_whitespace()
+        .next()
+        .map(|name| name.trim_end_matches(':'))
+    else {
+        return Ok(None);
+    };
+
+    let Some(mapping_var) = file.variable(mapping_name) else {
+        warn!(
+            "Variable '{}' references grid mapping '{}', which does not exist; CRS metadata will not be written",
+            var.name(),
+            mapping_name
+        );
+        return Ok(None);
+    };
+
+    let mut parameters = BTreeMap::new();
+    for attr in mapping_var.attributes() {
+        parameters.insert(attr.name().to_string(), attribute_to_json(&attr.value()?));
+    }
+
+    let grid_mapping_name = parameters
+        .get("grid_mapping_name")
+        .and_then(|value| value.as_str())
+        .map(str::to_string);
+
+    debug!(
+        "Found grid mapping '{}' ({}) for variable '{}'",
+        mapping_name,
+        grid_mapping_name.as_deref().unwrap_or("unnamed"),
+        var.name()
+    );
+
+    Ok(Some(CrsMetadata {
+        grid_mapping: mapping_name.to_string(),
+        grid_mapping_name,
+        parameters,
+    }))
+}
+
+/// Builds the Parquet key-value metadata for extracting `var` from `file`
+pub fn parquet_metadata(
+    file: &netcdf::File,
+    var: &netcdf::Variable,
+) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    let mut metadata = Vec::new();
+
+    if let Some(crs) = read_crs_metadata(file, var)? {
+        metadata.push((CRS_METADATA_KEY.to_string(), serde_json::to_string(&crs)?));
+    }
+
+    let time_zones = read_time_zones(file, var);
+    if !time_zones.is_empty() {
+        metadata.push((
+            TIME_ZONES_METADATA_KEY.to_string(),
+            serde_json::to_string(&time_zones)?,
+        ));
+    }
+
+    Ok(metadata)
+}
+
+/// Reads the string-valued attributes of a variable, keyed by attribute name.
+///
+/// Attributes of other types, or whose value cannot be read, are skipped.
+pub fn string_attributes(var: &netcdf::Variable) -> BTreeMap<String, String> {
+    var.attributes()
+        .filter_map(|attr| match attr.value() {
+            Ok(netcdf::AttributeValue::Str(value)) => Some((attr.name().to_string(), value)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Picks the name of a variable's value column from its attributes.
+///
+/// The CF `standard_name` is preferred, then `long_name`; blank values are
+/// ignored and `variable_name` is returned when neither attribute is usable.
+pub fn column_name_from_attributes(
+    attributes: &BTreeMap<String, String>,
+    variable_name: &str,
+) -> String {
+    ["standard_name", "long_name"]
+        .iter()
+        .filter_map(|key| attributes.get(*key))
+        .map(|value| value.trim())
+        .find(|value| !value.is_empty())
+        .unwrap_or(variable_name)
+        .to_string()
+}
+
+/// Returns the name of the value column extracted from `var`.
+///
+/// This is the variable name, unless `rename_from_attributes` is set, in which
+/// case [`column_name_from_attributes`] is used. A name that clashes with one of
+/// the variable's dimensions is ignored with a warning, since it would duplicate
+/// a coordinate column.
+pub fn value_column_name(var: &netcdf::Variable, rename_from_attributes: bool) -> String {
+    let variable_name = var.name();
+    if !rename_from_attributes {
+        return variable_name;
+    }
+
+    let column_name = column_name_from_attributes(&string_attributes(var), &variable_name);
+    if column_name != variable_name && var.dimensions().iter().any(|dim| dim.name() == column_name)
+    {
+        warn!(
+            "Column name '{}' from the attributes of '{}' clashes with a dimension; keeping the variable name",
+            column_name, variable_name
+        );
+        return variable_name;
+    }
+
+    debug!(
+        "Naming the column of '{}' '{}' from its attributes",
+        variable_name, column_name
+    );
+    column_name
+}
+
+/// Collects the offsets of the variable's time axes that are not in UTC.
+///
+/// Axes whose time reference cannot be read are skipped with a warning; they
+/// only fail the job if their times are actually decoded.
+fn read_time_zones(file: &netcdf::File, var: &netcdf::Variable) -> BTreeMap<String, String> {
+    let mut time_zones = BTreeMap::new();
+    for dim in var.dimensions() {
+        let name = dim.name();
+        match read_time_reference(file, &name) {
+            Ok(Some(reference)) if reference.is_non_utc() => {
+                if let Some(zone) = reference.time_zone {
+                    time_zones.insert(name, zone.to_string());
+                }
+            }
+            Ok(_) => {}
+            Err(e) => warn!("Could not read the time reference of '{}': {}", name, e),
+        }
+    }
+    time_zones
+}
+
+/// Converts a NetCDF attribute value to JSON, keeping numbers numeric
+fn attribute_to_json(value: &netcdf::AttributeValue) -> serde_json::Value {
+    use netcdf::AttributeValue as A;
+    use serde_json::json;
+
+    match value {
+        A::Uchar(v) => json!(v),
+        A::Uchars(v) => json!(v),
+        A::Schar(v) => json!(v),
+        A::Schars(v) => json!(v),
+        A::Ushort(v) => json!(v),
+        A::Ushorts(v) => json!(v),
+        A::Short(v) => json!(v),
+        A::Shorts(v) => json!(v),
+        A::Uint(v) => json!(v),
+        A::Uints(v) => json!(v),
+        A::Int(v) => json!(v),
+        A::Ints(v) => json!(v),
+        A::Ulonglong(v) => json!(v),
+        A::Ulonglongs(v) => json!(v),
+        A::Longlong(v) => json!(v),
+        A::Longlongs(v) => json!(v),
+        A::Float(v) => json!(v),
+        A::Floats(v) => json!(v),
+        A::Double(v) => json!(v),
+        A::Doubles(v) => json!(v),
+        A::Str(v) => json!(v),
+        A::Strs(v) => json!(v),
+    }
+}
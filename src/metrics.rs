@@ -0,0 +1,147 @@
+//! # Metrics Module
+//!
+//! This module renders the metrics of a conversion run written by
+//! `nc2parquet convert --metrics-file <path>`, so runs can be monitored without a
+//! sidecar process. Two formats are supported:
+//!
+//! - **Prometheus**: the text exposition format read by the node_exporter
+//!   textfile collector. Every metric is a gauge describing the last run.
+//! - **StatsD**: one `name:value|type` line per metric, for agents that tail files.
+//!
+//! The file is replaced atomically (written next to the destination, then
+//! renamed), so a scraper never reads a partially written file.
+
+use crate::report::OutputStats;
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use log::debug;
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+/// File format of the metrics written after a run
+#[derive(ValueEnum, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MetricsFormat {
+    /// Prometheus text format, for the node_exporter textfile collector
+    #[default]
+    Prometheus,
+    /// StatsD lines
+    Statsd,
+}
+
+/// Metrics of a single conversion run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RunMetrics {
+    /// Number of rows in the output
+    pub rows: usize,
+    /// Size of the output in bytes
+    pub bytes_written: u64,
+    /// Wall-clock duration of the run
+    pub duration: Duration,
+    /// Number of failed conversions (0 or 1 for a single run)
+    pub failures: u64,
+}
+
+impl RunMetrics {
+    /// Metrics of a run that wrote an output with the given statistics
+    pub fn succeeded(stats: OutputStats, duration: Duration) -> Self {
+        RunMetrics {
+            rows: stats.rows,
+            bytes_written: stats.bytes,
+            duration,
+            failures: 0,
+        }
+    }
+
+    /// Metrics of a run that failed before completing its output
+    pub fn failed(duration: Duration) -> Self {
+        RunMetrics {
+            rows: 0,
+            bytes_written: 0,
+            duration,
+            failures: 1,
+        }
+    }
+
+    /// Renders the metrics in Prometheus text format
+    pub fn to_prometheus(&self) -> String {
+        let gauges: [(&str, &str, String); 5] = [
+            (
+                "nc2parquet_rows_processed",
+                "Rows written by the last conversion",
+                self.rows.to_string(),
+            ),
+            (
+                "nc2parquet_bytes_written",
+                "Size in bytes of the output of the last conversion",
+                self.bytes_written.to_string(),
+            ),
+            (
+                "nc2parquet_duration_seconds",
+                "Duration of the last conversion",
+                self.duration.as_secs_f64().to_string(),
+            ),
+            (
+                "nc2parquet_failures",
+                "Whether the last conversion failed (1) or succeeded (0)",
+                self.failures.to_string(),
+            ),
+            (
+                "nc2parquet_last_run_timestamp_seconds",
+                "Unix time at which the last conversion finished",
+                chrono::Utc::now().timestamp().to_string(),
+            ),
+        ];
+
+        let mut text = String::new();
+        for (name, help, value) in gauges {
+            let _ = writeln!(text, "# HELP {} {}", name, help);
+            let _ = writeln!(text, "# TYPE {} gauge", name);
+            let _ = writeln!(text, "{} {}", name, value);
+        }
+        text
+    }
+
+    /// Renders the metrics as StatsD lines
+    pub fn to_statsd(&self) -> String {
+        format!(
+            "nc2parquet.rows_processed:{}|g\n\
+             nc2parquet.bytes_written:{}|g\n\
+             nc2parquet.duration:{}|ms\n\
+             nc2parquet.failures:{}|c\n",
+            self.rows,
+            self.bytes_written,
+            self.duration.as_millis(),
+            self.failures
+        )
+    }
+
+    /// Renders the metrics in the given format
+    pub fn render(&self, format: MetricsFormat) -> String {
+        match format {
+            MetricsFormat::Prometheus => self.to_prometheus(),
+            MetricsFormat::Statsd => self.to_statsd(),
+        }
+    }
+
+    /// Atomically replaces the local file at `path` with the rendered metrics
+    pub fn write(&self, path: &Path, format: MetricsFormat) -> Result<()> {
+        if let Some(parent) = path
+            .parent()
+            .filter(|parent| !parent.as_os_str().is_empty())
+        {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let mut temp_path = path.as_os_str().to_owned();
+        temp_path.push(".tmp");
+        std::fs::write(&temp_path, self.render(format))
+            .with_context(|| format!("Failed to write metrics file: {}", path.display()))?;
+        std::fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to write metrics file: {}", path.display()))?;
+
+        debug!("Wrote {:?} metrics to {}", format, path.display());
+        Ok(())
+    }
+}
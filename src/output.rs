@@ -10,12 +10,151 @@
 //! - **Detailed logging**: Shows DataFrame statistics and writing progress
 //! - **Error handling**: Graceful fallback between different writing methods
 //! - **Schema validation**: Displays DataFrame schema before writing
+//! - **Streaming writes**: Append DataFrame chunks as row groups with [`StreamingParquetWriter`]
+//! - **Appending**: Add rows to an existing output with [`append_dataframe_to_parquet`]
+//! - **Concurrent partitions**: Split a DataFrame into Hive-style partitions with
+//!   [`partition_dataframe`] and write the files in parallel with [`write_partitions_concurrently`]
+//! - **Bloom filters**: Optional split-block Bloom filters on chosen columns (see
+//!   [`crate::bloom_filter`])
+//! - **Compression**: Selectable codec and level through [`OutputOptions`]
+//! - **Statistics**: Per-column min/max and null counts, on by default
+//! - **Provenance**: Every Parquet file records the nc2parquet version and the time it
+//!   was written (see [`crate::metadata::provenance_metadata`])
+//! - **Arrow IPC partitions**: Partition files ending in `.arrow`, `.feather` or `.ipc` are
+//!   written as Arrow IPC (Feather v2) instead of Parquet (see [`OutputFileFormat`]), with
+//!   the footer metadata as custom schema metadata
 //!
 
+use crate::bloom_filter::add_bloom_filters;
+use crate::metadata::provenance_metadata;
+use crate::schema_check::compare_schemas;
 use crate::storage::{StorageBackend, StorageFactory};
 use log::debug;
+use polars::io::parquet::write::{BatchedWriter, KeyValueMetadata, StatisticsOptions};
 use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs::File;
 use std::io::Cursor;
+use std::ops::RangeInclusive;
+use std::sync::Arc;
+
+/// Compression codec of the Parquet column chunks
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub enum CompressionCodec {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Brotli,
+    Lz4,
+    /// The Polars default
+    #[default]
+    Zstd,
+}
+
+impl CompressionCodec {
+    pub fn is_default(&self) -> bool {
+        *self == CompressionCodec::default()
+    }
+
+    /// Valid compression levels of the codec, or `None` if it has no levels
+    pub fn level_range(&self) -> Option<RangeInclusive<i32>> {
+        match self {
+            CompressionCodec::Gzip => Some(0..=9),
+            CompressionCodec::Brotli => Some(0..=11),
+            CompressionCodec::Zstd => Some(1..=22),
+            CompressionCodec::Uncompressed | CompressionCodec::Snappy | CompressionCodec::Lz4 => {
+                None
+            }
+        }
+    }
+}
+
+/// How a DataFrame is encoded when written to Parquet
+#[derive(Debug, Clone)]
+pub struct OutputOptions {
+    pub compression: CompressionCodec,
+    /// Level of `compression`; the codec's default level when `None`
+    pub compression_level: Option<i32>,
+    /// Columns to add a Bloom filter on (see [`crate::bloom_filter`])
+    pub bloom_filter_columns: Vec<String>,
+    /// Write min/max and null count statistics for each column chunk
+    pub statistics: bool,
+}
+
+impl Default for OutputOptions {
+    fn default() -> Self {
+        OutputOptions {
+            compression: CompressionCodec::default(),
+            compression_level: None,
+            bloom_filter_columns: Vec::new(),
+            statistics: true,
+        }
+    }
+}
+
+impl OutputOptions {
+    /// Returns the Polars statistics setting for these options: min/max and null
+    /// counts when `statistics` is set, nothing otherwise.
+    pub fn parquet_statistics(&self) -> StatisticsOptions {
+        if self.statistics {
+            StatisticsOptions::default()
+        } else {
+            StatisticsOptions::empty()
+        }
+    }
+
+    /// Returns the Polars compression setting for these options.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a level is given for a codec without levels, or is
+    /// outside the codec's [`CompressionCodec::level_range`].
+    pub fn parquet_compression(&self) -> Result<ParquetCompression, Box<dyn std::error::Error>> {
+        if let Some(level) = self.compression_level {
+            let Some(range) = self.compression.level_range() else {
+                return Err(format!(
+                    "Compression codec {:?} does not support compression levels",
+                    self.compression
+                )
+                .into());
+            };
+            if !range.contains(&level) {
+                return Err(format!(
+                    "Compression level {} is out of range for {:?} ({}..={})",
+                    level,
+                    self.compression,
+                    range.start(),
+                    range.end()
+                )
+                .into());
+            }
+        }
+
+        // Levels are in range here, so the casts below are lossless
+        let level = self.compression_level;
+        Ok(match self.compression {
+            CompressionCodec::Uncompressed => ParquetCompression::Uncompressed,
+            CompressionCodec::Snappy => ParquetCompression::Snappy,
+            CompressionCodec::Gzip => ParquetCompression::Gzip(
+                level
+                    .map(|level| GzipLevel::try_new(level as u8))
+                    .transpose()?,
+            ),
+            CompressionCodec::Brotli => ParquetCompression::Brotli(
+                level
+                    .map(|level| BrotliLevel::try_new(level as u32))
+                    .transpose()?,
+            ),
+            CompressionCodec::Lz4 => ParquetCompression::Lz4Raw,
+            CompressionCodec::Zstd => {
+                ParquetCompression::Zstd(level.map(ZstdLevel::try_new).transpose()?)
+            }
+        })
+    }
+}
 
 /// Writes a DataFrame to a Parquet file for local file systems.
 ///
@@ -41,6 +180,21 @@ use std::io::Cursor;
 pub fn write_dataframe_to_parquet(
     df: &DataFrame,
     output_path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    write_dataframe_to_parquet_with_metadata(df, output_path, &[], &OutputOptions::default())
+}
+
+/// Writes a DataFrame to a local Parquet file with file-level key-value metadata.
+///
+/// Behaves like [`write_dataframe_to_parquet`], additionally storing each
+/// `(key, value)` pair in the Parquet footer (e.g. CRS information from
+/// [`crate::metadata::parquet_metadata`]), and encoding the file with `options`.
+/// The `created_by` and `processed_at` entries are always added.
+pub fn write_dataframe_to_parquet_with_metadata(
+    df: &DataFrame,
+    output_path: &str,
+    metadata: &[(String, String)],
+    options: &OutputOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     debug!("Writing DataFrame to parquet file: {}\n", output_path);
 
@@ -54,12 +208,23 @@ pub fn write_dataframe_to_parquet(
         std::fs::create_dir_all(parent)?;
     }
 
-    // Write directly to file
-    let file = std::fs::File::create(output_path)?;
-    let writer = ParquetWriter::new(file);
-    let mut df_clone = df.clone();
+    if options.bloom_filter_columns.is_empty() {
+        // Write directly to file
+        let file = File::create(output_path)?;
+        let writer = ParquetWriter::new(file)
+            .with_compression(options.parquet_compression()?)
+            .with_statistics(options.parquet_statistics())
+            .with_key_value_metadata(key_value_metadata(metadata));
+        let mut df_clone = df.clone();
 
-    writer.finish(&mut df_clone)?;
+        writer.finish(&mut df_clone)?;
+    } else {
+        // Bloom filters are added to the serialized file before it is written
+        std::fs::write(
+            output_path,
+            dataframe_to_parquet_bytes(df, metadata, options)?,
+        )?;
+    }
     debug!("Successfully wrote parquet file: {}", output_path);
 
     Ok(())
@@ -88,6 +253,17 @@ pub fn write_dataframe_to_parquet(
 pub async fn write_dataframe_to_parquet_async(
     df: &DataFrame,
     output_path: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    write_dataframe_to_parquet_with_metadata_async(df, output_path, &[], &OutputOptions::default())
+        .await
+}
+
+/// Async version of [`write_dataframe_to_parquet_with_metadata`] using storage abstraction.
+pub async fn write_dataframe_to_parquet_with_metadata_async(
+    df: &DataFrame,
+    output_path: &str,
+    metadata: &[(String, String)],
+    options: &OutputOptions,
 ) -> Result<(), Box<dyn std::error::Error>> {
     debug!("Writing DataFrame to parquet file: {}\n", output_path);
 
@@ -97,7 +273,7 @@ pub async fn write_dataframe_to_parquet_async(
     debug!("First few rows:\n{}", df.head(Some(5)));
 
     // Convert DataFrame to Parquet bytes in memory
-    let parquet_bytes = dataframe_to_parquet_bytes(df)?;
+    let parquet_bytes = dataframe_to_parquet_bytes(df, metadata, options)?;
 
     // Use storage abstraction for all backends
     let storage = StorageFactory::from_path(output_path).await?;
@@ -107,6 +283,416 @@ pub async fn write_dataframe_to_parquet_async(
     Ok(())
 }
 
+/// Result of a write that may be skipped when the content is unchanged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WriteOutcome {
+    /// The Parquet file (and its hash sidecar) were written
+    Written,
+    /// The existing output already has identical content and was left untouched
+    SkippedUnchanged,
+}
+
+/// Computes the hex-encoded SHA-256 hash of serialized Parquet content
+pub fn content_hash(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Returns the path of the `.sha256` sidecar stored next to an output file
+pub fn hash_sidecar_path(output_path: &str) -> String {
+    format!("{}.sha256", output_path)
+}
+
+/// Writes a DataFrame to Parquet unless the destination already holds identical content.
+///
+/// The DataFrame is serialized in memory and hashed with SHA-256. If both the output
+/// file and its `.sha256` sidecar exist and the sidecar records the same hash, the
+/// write is skipped. Otherwise the Parquet file is written, followed by the sidecar
+/// (in `sha256sum` format). Both files go through the storage abstraction, so this
+/// works for local and S3 destinations.
+///
+/// The `processed_at` timestamp differs on every run, so the hash is taken over
+/// the file serialized without the provenance entries: it identifies the content
+/// of the output, not the exact bytes of the file.
+///
+/// # Arguments
+///
+/// * `df` - The DataFrame containing processed NetCDF data
+/// * `output_path` - Path where the Parquet file should be written (local or S3)
+/// * `metadata` - Key-value pairs to store in the Parquet footer
+/// * `options` - Compression and Bloom filters of the Parquet file
+///
+/// # Returns
+///
+/// Returns whether the file was written or skipped, or an error if writing fails.
+pub async fn write_dataframe_to_parquet_if_changed(
+    df: &DataFrame,
+    output_path: &str,
+    metadata: &[(String, String)],
+    options: &OutputOptions,
+) -> Result<WriteOutcome, Box<dyn std::error::Error>> {
+    let content_bytes = serialize_parquet(df, plain_key_value_metadata(metadata), options)?;
+    let hash = content_hash(&content_bytes);
+    let sidecar_path = hash_sidecar_path(output_path);
+
+    let storage = StorageFactory::from_path(output_path).await?;
+    if storage.exists(output_path).await? && storage.exists(&sidecar_path).await? {
+        let sidecar = storage.read(&sidecar_path).await?;
+        let previous_hash = String::from_utf8_lossy(&sidecar)
+            .split_whitespace()
+            .next()
+            .map(str::to_string);
+        if previous_hash.as_deref() == Some(hash.as_str()) {
+            debug!(
+                "Output unchanged (sha256 {}), skipping: {}",
+                hash, output_path
+            );
+            return Ok(WriteOutcome::SkippedUnchanged);
+        }
+    }
+
+    let parquet_bytes = dataframe_to_parquet_bytes(df, metadata, options)?;
+    storage.write(output_path, &parquet_bytes).await?;
+
+    let file_name = output_path.rsplit('/').next().unwrap_or(output_path);
+    let sidecar = format!("{}  {}\n", hash, file_name);
+    storage.write(&sidecar_path, sidecar.as_bytes()).await?;
+
+    debug!("Wrote parquet file {} (sha256 {})", output_path, hash);
+    Ok(WriteOutcome::Written)
+}
+
+/// Appends a DataFrame to a Parquet file, creating the file if it does not exist.
+///
+/// Parquet files cannot be extended in place, so this is a full rewrite rather
+/// than an O(1) append: the existing file is read into memory, the new rows are
+/// stacked below its rows, and the combined DataFrame is written back. The cost
+/// of each append therefore grows with the size of the output. Both files go
+/// through the storage abstraction, so S3 outputs are read, modified and written
+/// back the same way.
+///
+/// # Arguments
+///
+/// * `df` - The rows to append
+/// * `output_path` - Path of the Parquet file to extend (local or S3)
+/// * `metadata` - Key-value pairs to store in the Parquet footer of the rewritten file
+/// * `options` - Compression and Bloom filters of the rewritten file
+///
+/// # Returns
+///
+/// Returns the total number of rows in the output after appending.
+///
+/// # Errors
+///
+/// This function will return an error if the existing file cannot be read, or if
+/// its schema (column names, types and order) differs from the schema of `df`.
+pub async fn append_dataframe_to_parquet(
+    df: &DataFrame,
+    output_path: &str,
+    metadata: &[(String, String)],
+    options: &OutputOptions,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let storage = StorageFactory::from_path(output_path).await?;
+
+    let combined = if storage.exists(output_path).await? {
+        let data = storage.read(output_path).await?;
+        let mut existing = ParquetReader::new(Cursor::new(data)).finish()?;
+
+        if existing.schema() != df.schema() {
+            let mismatches = compare_schemas(existing.schema(), df.schema());
+            let details = if mismatches.is_empty() {
+                "columns are in a different order".to_string()
+            } else {
+                mismatches
+                    .iter()
+                    .map(|mismatch| mismatch.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            };
+            return Err(format!(
+                "Cannot append to {}: schema differs from the existing file ({})",
+                output_path, details
+            )
+            .into());
+        }
+
+        debug!(
+            "Appending {} rows to {} existing rows in {}",
+            df.height(),
+            existing.height(),
+            output_path
+        );
+        existing.vstack_mut(df)?;
+        existing
+    } else {
+        debug!("Output does not exist yet, creating it: {}", output_path);
+        df.clone()
+    };
+
+    let parquet_bytes = dataframe_to_parquet_bytes(&combined, metadata, options)?;
+    storage.write(output_path, &parquet_bytes).await?;
+
+    debug!(
+        "Wrote {} rows to parquet file: {}",
+        combined.height(),
+        output_path
+    );
+    Ok(combined.height())
+}
+
+/// File format of an output file, selected by its extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFileFormat {
+    Parquet,
+    /// Arrow IPC file format, also known as Feather v2
+    ArrowIpc,
+}
+
+impl OutputFileFormat {
+    /// Detects the format from a path: `.arrow`, `.feather` and `.ipc` select Arrow
+    /// IPC, anything else Parquet. The extension of a directory, such as a
+    /// partitioned dataset's `dataset.arrow/`, counts as well.
+    pub fn from_path(path: &str) -> Self {
+        let extension = path
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .and_then(|name| name.rsplit_once('.'))
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        match extension.as_deref() {
+            Some("arrow" | "feather" | "ipc") => OutputFileFormat::ArrowIpc,
+            _ => OutputFileFormat::Parquet,
+        }
+    }
+
+    /// File extension used for files of this format, without the leading dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            OutputFileFormat::Parquet => "parquet",
+            OutputFileFormat::ArrowIpc => "arrow",
+        }
+    }
+}
+
+/// Returns the deterministic file name of the `index`-th part of a partition,
+/// e.g. `part-0.parquet` or `part-0.arrow`
+pub fn partition_file_name(index: usize, format: OutputFileFormat) -> String {
+    format!("part-{}.{}", index, format.extension())
+}
+
+/// A single partition of a partitioned output: its data and destination path.
+pub struct OutputPartition {
+    pub path: String,
+    pub df: DataFrame,
+}
+
+/// Directory name of partition values that are null, as in Hive
+pub const NULL_PARTITION_VALUE: &str = "__HIVE_DEFAULT_PARTITION__";
+
+/// Splits `df` into one partition per distinct combination of the `columns`
+/// values, in order of first appearance, written under `output_dir` in the Hive
+/// layout: `<output_dir>/<column>=<value>/.../part-0.parquet`, or `part-0.arrow`
+/// for Arrow IPC parts (see [`partition_file_name`]).
+///
+/// Values are written as their string form, with `/`, `=` and `%` escaped as
+/// `%2F`, `%3D` and `%25`; nulls become [`NULL_PARTITION_VALUE`]. The partition
+/// columns are kept in each file. An empty `df` gives a single empty partition,
+/// `<output_dir>/part-0.<ext>`, so the dataset still exists with its schema.
+pub fn partition_dataframe(
+    df: &DataFrame,
+    columns: &[String],
+    output_dir: &str,
+    format: OutputFileFormat,
+) -> Result<Vec<OutputPartition>, Box<dyn std::error::Error>> {
+    if columns.is_empty() {
+        return Err("At least one partition column is needed".into());
+    }
+    let keys = columns
+        .iter()
+        .map(|column| Ok(df.column(column)?.cast(&DataType::String)?.str()?.clone()))
+        .collect::<PolarsResult<Vec<StringChunked>>>()?;
+
+    let output_dir = output_dir.trim_end_matches('/');
+    if df.height() == 0 {
+        return Ok(vec![OutputPartition {
+            path: format!("{}/{}", output_dir, partition_file_name(0, format)),
+            df: df.clone(),
+        }]);
+    }
+
+    let mut positions: std::collections::HashMap<Vec<String>, usize> = Default::default();
+    let mut groups: Vec<(Vec<String>, Vec<IdxSize>)> = Vec::new();
+    for row in 0..df.height() {
+        let key: Vec<String> = keys
+            .iter()
+            .map(|values| {
+                values
+                    .get(row)
+                    .map_or(NULL_PARTITION_VALUE.to_string(), escape_partition_value)
+            })
+            .collect();
+        let position = *positions.entry(key.clone()).or_insert_with(|| {
+            groups.push((key, Vec::new()));
+            groups.len() - 1
+        });
+        groups[position].1.push(row as IdxSize);
+    }
+
+    groups
+        .into_iter()
+        .map(|(key, rows)| {
+            let directories: Vec<String> = columns
+                .iter()
+                .zip(&key)
+                .map(|(column, value)| format!("{}={}", column, value))
+                .collect();
+            let path = format!(
+                "{}/{}/{}",
+                output_dir,
+                directories.join("/"),
+                partition_file_name(0, format)
+            );
+            let df = df.take(&IdxCa::from_vec("rows".into(), rows))?;
+            Ok(OutputPartition { path, df })
+        })
+        .collect()
+}
+
+/// Escapes the characters of a partition value that would change the path
+fn escape_partition_value(value: &str) -> String {
+    value
+        .replace('%', "%25")
+        .replace('/', "%2F")
+        .replace('=', "%3D")
+}
+
+/// Writes partition files concurrently with a bounded number of in-flight writes.
+///
+/// At most `max_concurrency` partitions are serialized and written at the same
+/// time (a value of 0 is treated as 1); with `None`, as many as the process has
+/// threads (see [`crate::configured_threads`]), so `--threads` caps the writes
+/// too. Each partition is written to exactly the path it was given, so file
+/// naming stays deterministic regardless of the order in which writes finish.
+/// Works for both local and S3 destinations.
+///
+/// Each file is written as Parquet or Arrow IPC according to its extension (see
+/// [`OutputFileFormat::from_path`]); [`partition_file_name`] gives matching part
+/// names.
+///
+/// Every partition is attempted even if some fail; the errors are collected and
+/// reported together.
+///
+/// # Returns
+///
+/// Returns the written paths in input order, or an error listing every
+/// partition that failed.
+pub async fn write_partitions_concurrently(
+    partitions: Vec<OutputPartition>,
+    max_concurrency: Option<usize>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    write_partitions_concurrently_with_metadata(
+        partitions,
+        &[],
+        &OutputOptions::default(),
+        max_concurrency,
+    )
+    .await
+}
+
+/// Writes partition files like [`write_partitions_concurrently`], with `metadata`
+/// in the footer of every Parquet file and its compression, statistics and
+/// Bloom filters set by `options`. Arrow IPC files carry `metadata` as custom
+/// schema metadata instead, and are written uncompressed and without Bloom
+/// filters.
+pub async fn write_partitions_concurrently_with_metadata(
+    partitions: Vec<OutputPartition>,
+    metadata: &[(String, String)],
+    options: &OutputOptions,
+    max_concurrency: Option<usize>,
+) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let metadata: Arc<[(String, String)]> = metadata.into();
+    let options = Arc::new(options.clone());
+    let max_concurrency = max_concurrency
+        .unwrap_or_else(crate::configured_threads)
+        .max(1);
+    let semaphore = Arc::new(tokio::sync::Semaphore::new(max_concurrency));
+    let mut tasks = tokio::task::JoinSet::new();
+    let paths: Vec<String> = partitions.iter().map(|p| p.path.clone()).collect();
+
+    debug!(
+        "Writing {} partitions with up to {} concurrent writes",
+        partitions.len(),
+        max_concurrency
+    );
+
+    for (index, partition) in partitions.into_iter().enumerate() {
+        let semaphore = Arc::clone(&semaphore);
+        let metadata = Arc::clone(&metadata);
+        let options = Arc::clone(&options);
+        tasks.spawn(async move {
+            let result = async {
+                let _permit = semaphore.acquire().await.map_err(|e| e.to_string())?;
+                let OutputPartition { path, df } = partition;
+
+                let format = OutputFileFormat::from_path(&path);
+
+                let bytes = tokio::task::spawn_blocking(move || {
+                    match format {
+                        OutputFileFormat::Parquet => {
+                            dataframe_to_parquet_bytes(&df, &metadata, &options)
+                        }
+                        OutputFileFormat::ArrowIpc => dataframe_to_ipc_bytes(&df, &metadata),
+                    }
+                    .map_err(|e| e.to_string())
+                })
+                .await
+                .map_err(|e| e.to_string())??;
+
+                let storage = StorageFactory::from_path(&path)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                storage
+                    .write(&path, &bytes)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                debug!("Wrote partition file: {}", path);
+                Ok::<(), String>(())
+            }
+            .await;
+            (index, result)
+        });
+    }
+
+    let mut failures = Vec::new();
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((_, Ok(()))) => {}
+            Ok((index, Err(e))) => failures.push((index, e)),
+            Err(e) => failures.push((usize::MAX, e.to_string())),
+        }
+    }
+
+    if !failures.is_empty() {
+        failures.sort_by_key(|(index, _)| *index);
+        let details = failures
+            .iter()
+            .map(|(index, e)| match paths.get(*index) {
+                Some(path) => format!("  {}: {}", path, e),
+                None => format!("  <unknown partition>: {}", e),
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        return Err(format!(
+            "Failed to write {} of {} partitions:\n{}",
+            failures.len(),
+            paths.len(),
+            details
+        )
+        .into());
+    }
+
+    Ok(paths)
+}
+
 /// Converts a DataFrame to Parquet format as bytes in memory.
 ///
 /// This helper function serializes a DataFrame to Parquet format without
@@ -115,16 +701,169 @@ pub async fn write_dataframe_to_parquet_async(
 /// # Arguments
 ///
 /// * `df` - The DataFrame to convert to Parquet format
+/// * `metadata` - Key-value pairs to store in the Parquet footer
+/// * `options` - Compression and Bloom filters of the Parquet file
 ///
 /// # Returns
 ///
 /// Returns the Parquet-formatted bytes, or an error if conversion fails.
-fn dataframe_to_parquet_bytes(df: &DataFrame) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+fn dataframe_to_parquet_bytes(
+    df: &DataFrame,
+    metadata: &[(String, String)],
+    options: &OutputOptions,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    serialize_parquet(df, key_value_metadata(metadata), options)
+}
+
+/// Serializes a DataFrame to Parquet bytes with exactly the given footer metadata
+fn serialize_parquet(
+    df: &DataFrame,
+    footer_metadata: Option<KeyValueMetadata>,
+    options: &OutputOptions,
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
+    let mut buffer = Vec::new();
+    let cursor = Cursor::new(&mut buffer);
+    let writer = ParquetWriter::new(cursor)
+        .with_compression(options.parquet_compression()?)
+        .with_statistics(options.parquet_statistics())
+        .with_key_value_metadata(footer_metadata);
+    let mut df_clone = df.clone();
+
+    writer.finish(&mut df_clone)?;
+    add_bloom_filters(buffer, df, &options.bloom_filter_columns)
+}
+
+/// Converts a DataFrame to the Arrow IPC file format as bytes in memory, with
+/// `metadata` and the provenance entries of this write as custom schema metadata
+/// (the IPC counterpart of a Parquet footer).
+fn dataframe_to_ipc_bytes(
+    df: &DataFrame,
+    metadata: &[(String, String)],
+) -> Result<Vec<u8>, Box<dyn std::error::Error>> {
     let mut buffer = Vec::new();
     let cursor = Cursor::new(&mut buffer);
-    let writer = ParquetWriter::new(cursor);
     let mut df_clone = df.clone();
 
+    let custom_metadata: BTreeMap<PlSmallStr, PlSmallStr> = metadata
+        .iter()
+        .cloned()
+        .chain(provenance_metadata())
+        .map(|(key, value)| (key.into(), value.into()))
+        .collect();
+    let mut writer = IpcWriter::new(cursor);
+    writer.set_custom_schema_metadata(Arc::new(custom_metadata));
     writer.finish(&mut df_clone)?;
     Ok(buffer)
 }
+
+/// Footer metadata of a Parquet output: `metadata` followed by the provenance
+/// entries of this write
+fn key_value_metadata(metadata: &[(String, String)]) -> Option<KeyValueMetadata> {
+    let mut entries = metadata.to_vec();
+    entries.extend(provenance_metadata());
+    plain_key_value_metadata(&entries)
+}
+
+fn plain_key_value_metadata(metadata: &[(String, String)]) -> Option<KeyValueMetadata> {
+    if metadata.is_empty() {
+        None
+    } else {
+        Some(KeyValueMetadata::from_static(metadata.to_vec()))
+    }
+}
+
+/// Incrementally writes DataFrame chunks to a local Parquet file.
+///
+/// The file is created when the first chunk arrives, using that chunk's schema.
+/// Every following chunk must share the same schema and is appended as new row
+/// groups, so the complete dataset never has to be held in memory at once.
+/// Call [`StreamingParquetWriter::finish`] to write the Parquet footer.
+pub struct StreamingParquetWriter {
+    output_path: String,
+    writer: Option<BatchedWriter<File>>,
+    metadata: Vec<(String, String)>,
+    compression: ParquetCompression,
+    statistics: StatisticsOptions,
+    rows_written: usize,
+}
+
+impl StreamingParquetWriter {
+    pub fn new(output_path: &str) -> Self {
+        StreamingParquetWriter {
+            output_path: output_path.to_string(),
+            writer: None,
+            metadata: Vec::new(),
+            compression: ParquetCompression::default(),
+            statistics: StatisticsOptions::default(),
+            rows_written: 0,
+        }
+    }
+
+    /// Sets key-value metadata to store in the Parquet footer.
+    pub fn with_metadata(mut self, metadata: Vec<(String, String)>) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Sets the compression of the column chunks (see [`OutputOptions::parquet_compression`]).
+    pub fn with_compression(mut self, compression: ParquetCompression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Sets the column statistics to write (see [`OutputOptions::parquet_statistics`]).
+    pub fn with_statistics(mut self, statistics: StatisticsOptions) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// Appends a chunk to the output file, creating the file on first use.
+    pub fn write_chunk(&mut self, df: &DataFrame) -> Result<(), Box<dyn std::error::Error>> {
+        let writer = match self.writer.as_mut() {
+            Some(writer) => writer,
+            None => {
+                debug!("Starting streaming parquet write: {}", self.output_path);
+                debug!("DataFrame schema:\n{:?}", df.schema());
+
+                if let Some(parent) = std::path::Path::new(&self.output_path).parent() {
+                    std::fs::create_dir_all(parent)?;
+                }
+
+                let file = File::create(&self.output_path)?;
+                self.writer.insert(
+                    ParquetWriter::new(file)
+                        .with_compression(self.compression)
+                        .with_statistics(self.statistics)
+                        .with_key_value_metadata(key_value_metadata(&self.metadata))
+                        .batched(df.schema())?,
+                )
+            }
+        };
+
+        writer.write_batch(df)?;
+        self.rows_written += df.height();
+        debug!(
+            "Wrote chunk of {} rows ({} total) to {}",
+            df.height(),
+            self.rows_written,
+            self.output_path
+        );
+
+        Ok(())
+    }
+
+    /// Finalizes the Parquet file and returns the number of rows written.
+    pub fn finish(self) -> Result<usize, Box<dyn std::error::Error>> {
+        let writer = self.writer.ok_or(format!(
+            "No data was written to parquet file: {}",
+            self.output_path
+        ))?;
+        writer.finish()?;
+
+        debug!(
+            "Successfully wrote {} rows to parquet file: {}",
+            self.rows_written, self.output_path
+        );
+        Ok(self.rows_written)
+    }
+}
@@ -0,0 +1,309 @@
+//! # Cell Peek Module
+//!
+//! This module reads the value of a variable at a single grid cell, for quick
+//! checks without writing a Parquet file.
+//!
+//! Each dimension is pinned either by coordinate value (matched exactly, like a
+//! list filter) or by index (like a stride filter with a count of one).
+//! Dimensions of length one may be left out. The cell is then read through the
+//! regular extraction, so packed values and missing values are decoded exactly
+//! as in a conversion.
+
+use crate::extract::{DimensionIndexManager, extract_data_to_dataframe};
+use crate::filters::{CoordinateCache, NCFilter, NCListFilter, NCStrideFilter};
+use crate::info::{detect_main_variable, resolve_variable};
+use crate::storage::{StorageBackend, StorageFactory};
+use anyhow::{Context, Result, bail};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// How a dimension is pinned to a single position
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CellSelector {
+    /// The position whose coordinate value equals this value
+    Coordinate(f64),
+    /// The position at this index
+    Index(usize),
+}
+
+/// Selection of one position along a dimension, given on the command line as
+/// `--at latitude:30` or `--index level:0`
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellSpec {
+    pub dimension: String,
+    pub selector: CellSelector,
+}
+
+impl CellSpec {
+    pub fn coordinate(dimension: &str, value: f64) -> Self {
+        CellSpec {
+            dimension: dimension.to_string(),
+            selector: CellSelector::Coordinate(value),
+        }
+    }
+
+    pub fn index(dimension: &str, index: usize) -> Self {
+        CellSpec {
+            dimension: dimension.to_string(),
+            selector: CellSelector::Index(index),
+        }
+    }
+
+    /// Filter selecting the positions this spec matches
+    fn to_filter(&self) -> Box<dyn NCFilter> {
+        match self.selector {
+            CellSelector::Coordinate(value) => {
+                Box::new(NCListFilter::new(&self.dimension, vec![value]))
+            }
+            CellSelector::Index(index) => {
+                Box::new(NCStrideFilter::new(&self.dimension, index, 1, Some(1)))
+            }
+        }
+    }
+}
+
+/// Resolved position of a peeked cell along one dimension
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeekedDimension {
+    pub name: String,
+    pub index: usize,
+    /// Coordinate value at `index`, if the dimension has a numeric coordinate variable
+    pub coordinate: Option<f64>,
+}
+
+/// Value of a variable at a single grid cell
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PeekedCell {
+    pub path: String,
+    pub variable: String,
+    /// Resolved positions, in the variable's dimension order
+    pub dimensions: Vec<PeekedDimension>,
+    /// Decoded value, or `None` when the cell holds a missing value
+    pub value: Option<f64>,
+    pub units: Option<String>,
+}
+
+impl std::fmt::Display for PeekedCell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Cell of {} in {}", self.variable, self.path)?;
+        for dim in &self.dimensions {
+            match dim.coordinate {
+                Some(coordinate) => writeln!(
+                    f,
+                    "  {}: index {} (coordinate {})",
+                    dim.name, dim.index, coordinate
+                )?,
+                None => writeln!(f, "  {}: index {}", dim.name, dim.index)?,
+            }
+        }
+        let value = self
+            .value
+            .map(|value| value.to_string())
+            .unwrap_or_else(|| "missing".to_string());
+        match &self.units {
+            Some(units) => write!(f, "  Value: {} {}", value, units),
+            None => write!(f, "  Value: {}", value),
+        }
+    }
+}
+
+/// Reads the value of `variable` (or the main data variable) at the cell
+/// selected by `specs` in a local or S3 NetCDF file
+pub async fn peek_netcdf_cell(
+    file_path: &str,
+    variable: Option<&str>,
+    specs: &[CellSpec],
+) -> Result<PeekedCell> {
+    // Handle S3 paths - download to temporary file first
+    let (temp_file, local_path) = if file_path.starts_with("s3://") {
+        let storage = StorageFactory::from_path(file_path).await?;
+        let data = storage
+            .read(file_path)
+            .await
+            .context("Failed to read S3 file for peeking")?;
+
+        let temp_file = crate::storage::temp_file().context("Failed to create temporary file")?;
+        let temp_path = temp_file.path().to_path_buf();
+        tokio::fs::write(&temp_path, data)
+            .await
+            .context("Failed to write temporary file")?;
+
+        (Some(temp_file), temp_path.to_string_lossy().to_string())
+    } else {
+        (None, StorageFactory::local_path(file_path)?.into_owned())
+    };
+
+    let file = netcdf::open(&local_path)
+        .with_context(|| format!("Failed to open NetCDF file: {}", file_path))?;
+    let cell = peek_cell(&file, file_path, variable, specs)?;
+    file.close().context("Failed to close NetCDF file")?;
+
+    // Keep temp file alive until after we close the netcdf file
+    drop(temp_file);
+
+    Ok(cell)
+}
+
+/// Reads the value of `variable` (or the main data variable) at the cell
+/// selected by `specs` in an open file; `path` is only recorded.
+///
+/// # Errors
+///
+/// Returns an error if a spec names a dimension the variable does not have or
+/// is given twice, if a dimension longer than one is not pinned, or if the
+/// specs do not select exactly one cell.
+pub fn peek_cell(
+    file: &netcdf::File,
+    path: &str,
+    variable: Option<&str>,
+    specs: &[CellSpec],
+) -> Result<PeekedCell> {
+    let variable = match variable {
+        Some(variable) => variable.to_string(),
+        None => detect_main_variable(file)?,
+    };
+    let var = resolve_variable(file, &variable)?;
+    let dimensions: Vec<(String, usize)> = var
+        .dimensions()
+        .iter()
+        .map(|dim| (dim.name().to_string(), dim.len()))
+        .collect();
+
+    for (i, spec) in specs.iter().enumerate() {
+        let Some((_, len)) = dimensions.iter().find(|(name, _)| *name == spec.dimension) else {
+            bail!(
+                "Variable '{}' has no dimension '{}' (dimensions: {})",
+                variable,
+                spec.dimension,
+                dimension_names(&dimensions)
+            );
+        };
+        if specs[..i]
+            .iter()
+            .any(|other| other.dimension == spec.dimension)
+        {
+            bail!("Dimension '{}' is selected more than once", spec.dimension);
+        }
+        if let CellSelector::Index(index) = spec.selector
+            && index >= *len
+        {
+            bail!(
+                "Index {} is out of range for dimension '{}' (length {})",
+                index,
+                spec.dimension,
+                len
+            );
+        }
+    }
+    let unpinned: Vec<&str> = dimensions
+        .iter()
+        .filter(|(name, len)| *len != 1 && !specs.iter().any(|spec| spec.dimension == *name))
+        .map(|(name, _)| name.as_str())
+        .collect();
+    if !unpinned.is_empty() {
+        bail!(
+            "No position given for dimension(s) {} of '{}'",
+            unpinned.join(", "),
+            variable
+        );
+    }
+
+    let filters: Vec<Box<dyn NCFilter>> = specs.iter().map(CellSpec::to_filter).collect();
+    let mut manager = DimensionIndexManager::new(&var).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut cache = CoordinateCache::new();
+    for (spec, filter) in specs.iter().zip(&filters) {
+        let result = filter
+            .apply_cached(file, &mut cache)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        if let CellSelector::Coordinate(value) = spec.selector
+            && result.is_empty()
+        {
+            bail!("No '{}' coordinate equals {}", spec.dimension, value);
+        }
+        manager
+            .apply_filter_result(&result)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+    let combinations = manager.get_all_coordinate_combinations();
+    let [indices] = combinations.as_slice() else {
+        bail!(
+            "Expected the selection to match one cell, but it matches {}",
+            combinations.len()
+        );
+    };
+
+    let df = extract_data_to_dataframe(file, &var, &variable, &filters)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let value = df
+        .column(&variable)?
+        .cast(&DataType::Float64)?
+        .f64()?
+        .get(0);
+    let dimensions = dimensions
+        .iter()
+        .zip(indices)
+        .map(|((name, _), &index)| PeekedDimension {
+            name: name.clone(),
+            index,
+            coordinate: cache
+                .get(file, name)
+                .ok()
+                .flatten()
+                .and_then(|values| values.get(index).copied()),
+        })
+        .collect();
+    let units = match var.attribute_value("units").transpose()? {
+        Some(value) => String::try_from(value).ok(),
+        None => None,
+    };
+
+    Ok(PeekedCell {
+        path: path.to_string(),
+        variable,
+        dimensions,
+        value,
+        units,
+    })
+}
+
+fn dimension_names(dimensions: &[(String, usize)]) -> String {
+    dimensions
+        .iter()
+        .map(|(name, _)| name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Print a peeked cell in human-readable format
+pub fn print_peeked_cell_human(cell: &PeekedCell) {
+    println!("{}", cell);
+}
+
+/// Print a peeked cell in JSON format
+pub fn print_peeked_cell_json(cell: &PeekedCell) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(cell)?);
+    Ok(())
+}
+
+/// Print a peeked cell in YAML format
+pub fn print_peeked_cell_yaml(cell: &PeekedCell) -> Result<()> {
+    let yaml = serde_yaml::to_string(cell).context("Failed to serialize peeked cell to YAML")?;
+    println!("{}", yaml);
+    Ok(())
+}
+
+/// Print a peeked cell in CSV format (one row per dimension)
+pub fn print_peeked_cell_csv(cell: &PeekedCell) -> Result<()> {
+    println!("variable,dimension,index,coordinate,value");
+    for dim in &cell.dimensions {
+        println!(
+            "{},{},{},{},{}",
+            cell.variable,
+            dim.name,
+            dim.index,
+            dim.coordinate.map(|c| c.to_string()).unwrap_or_default(),
+            cell.value.map(|v| v.to_string()).unwrap_or_default()
+        );
+    }
+    Ok(())
+}
@@ -13,9 +13,26 @@
 //! ## Built-in Processors
 //! - **ColumnRenamer**: Rename columns with mappings
 //! - **DateTimeConverter**: Convert numeric columns to datetime
-//! - **UnitConverter**: Convert between units (temperature, pressure, etc.)
+//! - **UnitConverter**: Convert between UDUNITS-style units (`"W m-2"`, `"hPa"`, `"degC"`, etc.)
 //! - **Aggregator**: Spatial/temporal aggregations
 //! - **FormulaApplier**: Apply mathematical expressions
+//! - **NullCanonicalizer**: Unify NaN and null as a single missing-value representation
+//! - **SeasonLabeler**: Label rows with their meteorological season (DJF/MAM/JJA/SON)
+//! - **CumulativeExtremum**: Running (expanding window) maximum or minimum of a column
+//! - **AsofJoiner**: Attach the nearest-in-time row of an auxiliary table (as-of join)
+//! - **ColumnTransform**: Apply a single-column function (log, exp, reciprocal, ...) in place
+//! - **ColumnRounder**: Round float columns to a number of decimals
+//! - **RowFilter**: Keep the rows matching a predicate (`t > 273.15`, `q is not null`, ...)
+//! - **Melter**: Unpivot wide variable columns into long `variable`/`value` rows
+//! - **Pivoter**: Spread a categorical column into one value column per category
+//! - **ColumnPacker**: Pack a float column into scaled integers, the inverse of NetCDF packing
+//!
+//! ## Custom Processors
+//! Library users can drive their own [`PostProcessor`] implementations from
+//! configuration: register a factory under a name in a [`ProcessorRegistry`] and
+//! build the pipeline with [`ProcessingPipeline::from_config_with_registry`]. A
+//! `{"type": "custom", "name": ..., "params": ...}` step then calls the factory
+//! with its `params`.
 //!
 //! ## Example
 //! ```rust
@@ -41,7 +58,9 @@
 //! let processed_df = pipeline.execute(sample_df).unwrap();
 //! ```
 
-use chrono::{DateTime, Utc};
+use crate::output::OutputFileFormat;
+use crate::{cftime, units};
+use chrono::{DateTime, Datelike, Utc};
 use log::{debug, warn};
 use polars::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -130,7 +149,7 @@ pub enum ProcessorConfig {
     /// Convert numeric column to datetime
     DatetimeConvert {
         column: String,
-        base: String, // ISO 8601 format
+        base: String, // ISO 8601 or CF-style reference time, normalized to UTC
         unit: TimeUnit,
     },
     /// Convert between units
@@ -139,10 +158,10 @@ pub enum ProcessorConfig {
         from_unit: String,
         to_unit: String,
     },
-    /// Aggregate data
+    /// Aggregate data; each column maps to one operation or a list of operations
     Aggregate {
         group_by: Vec<String>,
-        aggregations: HashMap<String, AggregationOp>,
+        aggregations: HashMap<String, AggregationOps>,
     },
     /// Apply mathematical formulas
     ApplyFormula {
@@ -150,10 +169,177 @@ pub enum ProcessorConfig {
         formula: String,
         source_columns: Vec<String>,
     },
+    /// Canonicalize missing values in float columns (NaN -> null by default)
+    CanonicalizeNulls {
+        #[serde(default)]
+        nulls_to_nan: bool,
+    },
+    /// Derive a meteorological season column from a datetime column
+    Season {
+        time_column: String,
+        target_column: String,
+        #[serde(default)]
+        hemisphere: Hemisphere,
+    },
+    /// Running maximum of a column, written to `<column>_cum_max`
+    CumMax {
+        column: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        order_by: Option<String>,
+    },
+    /// Running minimum of a column, written to `<column>_cum_min`
+    CumMin {
+        column: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        order_by: Option<String>,
+    },
+    /// Attach the nearest-in-time row of a Parquet or Arrow IPC table
+    AsofJoin {
+        table_path: String,
+        on_time: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        by: Option<Vec<String>>,
+        /// Maximum time difference of a match, e.g. `"30m"` or `"1h"`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tolerance: Option<String>,
+        #[serde(default)]
+        direction: AsofDirection,
+    },
+    /// Per-row heating or cooling degree-days of a temperature column
+    DegreeDays {
+        temperature_column: String,
+        /// Base temperature, in the units of `temperature_column`
+        base: f64,
+        kind: DegreeDayKind,
+        target_column: String,
+    },
+    /// Replace a column with a function of it: `log`, `log10`, `ln`, `exp`,
+    /// `reciprocal` or `negate`
+    Transform { column: String, op: String },
+    /// Round float columns to `decimals` decimal places: the listed `columns`, or
+    /// every float column not in `exclude` when none are listed
+    Round {
+        decimals: u32,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        columns: Vec<String>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        exclude: Vec<String>,
+    },
+    /// Keep the rows matching `predicate` (see [`RowPredicate`])
+    RowFilter { predicate: String },
+    /// Unpivot `value_vars` into long `variable`/`value` columns, repeating
+    /// `id_vars` for each of them
+    Melt {
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        id_vars: Vec<String>,
+        value_vars: Vec<String>,
+    },
+    /// Spread the distinct values of `columns` into one column each, holding
+    /// `values` per `index` row; `agg` combines rows that land in the same cell
+    Pivot {
+        index: Vec<String>,
+        columns: String,
+        values: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        agg: Option<AggregationOp>,
+    },
+    /// Pack a float column into `dtype` integers with `scale_factor` and
+    /// `add_offset`, recorded in the Parquet footer (see [`ColumnPacker`])
+    Pack {
+        column: String,
+        scale_factor: f64,
+        #[serde(default)]
+        add_offset: f64,
+        dtype: PackedType,
+    },
+    /// A processor registered under `name` in a [`ProcessorRegistry`], built from
+    /// `params`
+    Custom {
+        name: String,
+        #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+        params: serde_json::Value,
+    },
+}
+
+impl ProcessorConfig {
+    /// Whether each output row depends only on its input row, so the processor
+    /// gives the same rows when applied to consecutive chunks as to the whole frame
+    pub fn is_row_wise(&self) -> bool {
+        matches!(
+            self,
+            ProcessorConfig::RenameColumns { .. }
+                | ProcessorConfig::DatetimeConvert { .. }
+                | ProcessorConfig::UnitConvert { .. }
+                | ProcessorConfig::ApplyFormula { .. }
+                | ProcessorConfig::CanonicalizeNulls { .. }
+                | ProcessorConfig::Season { .. }
+                | ProcessorConfig::DegreeDays { .. }
+                | ProcessorConfig::Transform { .. }
+                | ProcessorConfig::Round { .. }
+                | ProcessorConfig::RowFilter { .. }
+                | ProcessorConfig::Pack { .. }
+        )
+    }
+
+    /// Whether the processor keeps every row, in order, so the output rows still
+    /// match the extracted rows one to one, as a cell index requires
+    pub fn keeps_rows(&self) -> bool {
+        match self {
+            ProcessorConfig::RowFilter { .. } => false,
+            // Ordering by another column sorts the rows
+            ProcessorConfig::CumMax { order_by, .. } | ProcessorConfig::CumMin { order_by, .. } => {
+                order_by.is_none()
+            }
+            ProcessorConfig::AsofJoin { .. } => true,
+            processor => processor.is_row_wise(),
+        }
+    }
+}
+
+/// Which side of the base temperature degree-days are counted on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DegreeDayKind {
+    /// `max(base - T, 0)`: demand for heating below the base
+    Heating,
+    /// `max(T - base, 0)`: demand for cooling above the base
+    Cooling,
+}
+
+/// Which auxiliary row an as-of join matches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AsofDirection {
+    /// The last row at or before the input time
+    #[default]
+    Backward,
+    /// The first row at or after the input time
+    Forward,
+    /// The row closest to the input time, in either direction
+    Nearest,
+}
+
+impl From<AsofDirection> for AsofStrategy {
+    fn from(direction: AsofDirection) -> Self {
+        match direction {
+            AsofDirection::Backward => AsofStrategy::Backward,
+            AsofDirection::Forward => AsofStrategy::Forward,
+            AsofDirection::Nearest => AsofStrategy::Nearest,
+        }
+    }
+}
+
+/// Hemisphere used when labelling seasons
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Hemisphere {
+    #[default]
+    North,
+    South,
 }
 
 /// Time units for datetime conversion
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum TimeUnit {
     Seconds,
@@ -166,7 +352,7 @@ pub enum TimeUnit {
 }
 
 /// Aggregation operations
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum AggregationOp {
     Mean,
@@ -178,6 +364,113 @@ pub enum AggregationOp {
     Var,
     First,
     Last,
+    /// Number of distinct values (nulls count as one value)
+    NUnique,
+    /// Most frequent non-null value; ties resolve to the smallest value
+    Mode,
+}
+
+/// Aggregation operations applied to one column, written as a single operation
+/// (`"mean"`) or a list (`["mean", "max"]`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum AggregationOps {
+    Single(AggregationOp),
+    Multiple(Vec<AggregationOp>),
+}
+
+impl AggregationOps {
+    pub fn ops(&self) -> &[AggregationOp] {
+        match self {
+            AggregationOps::Single(op) => std::slice::from_ref(op),
+            AggregationOps::Multiple(ops) => ops,
+        }
+    }
+}
+
+impl From<AggregationOp> for AggregationOps {
+    fn from(op: AggregationOp) -> Self {
+        AggregationOps::Single(op)
+    }
+}
+
+impl From<Vec<AggregationOp>> for AggregationOps {
+    fn from(ops: Vec<AggregationOp>) -> Self {
+        AggregationOps::Multiple(ops)
+    }
+}
+
+impl AggregationOp {
+    pub const ALL: [AggregationOp; 11] = [
+        AggregationOp::Mean,
+        AggregationOp::Sum,
+        AggregationOp::Min,
+        AggregationOp::Max,
+        AggregationOp::Count,
+        AggregationOp::Std,
+        AggregationOp::Var,
+        AggregationOp::First,
+        AggregationOp::Last,
+        AggregationOp::NUnique,
+        AggregationOp::Mode,
+    ];
+
+    /// Suffix of the output column, e.g. `temperature_mean`
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            AggregationOp::Mean => "mean",
+            AggregationOp::Sum => "sum",
+            AggregationOp::Min => "min",
+            AggregationOp::Max => "max",
+            AggregationOp::Count => "count",
+            AggregationOp::Std => "std",
+            AggregationOp::Var => "var",
+            AggregationOp::First => "first",
+            AggregationOp::Last => "last",
+            AggregationOp::NUnique => "nunique",
+            AggregationOp::Mode => "mode",
+        }
+    }
+
+    /// Aggregation expression of `input` within a group
+    pub fn expr(&self, input: Expr) -> Expr {
+        match self {
+            AggregationOp::Mean => input.mean(),
+            AggregationOp::Sum => input.sum(),
+            AggregationOp::Min => input.min(),
+            AggregationOp::Max => input.max(),
+            AggregationOp::Count => input.count(),
+            AggregationOp::Std => input.std(1), // Use population std
+            AggregationOp::Var => input.var(1), // Use population var
+            AggregationOp::First => input.first(),
+            AggregationOp::Last => input.last(),
+            AggregationOp::NUnique => input.n_unique(),
+            // `mode` yields every most frequent value; keep the smallest
+            AggregationOp::Mode => input.drop_nulls().mode().sort(Default::default()).first(),
+        }
+    }
+}
+
+impl std::str::FromStr for AggregationOp {
+    type Err = String;
+
+    /// Parses an operation by its configuration name, which is also its suffix
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        AggregationOp::ALL
+            .into_iter()
+            .find(|op| op.suffix() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown aggregation '{}' (known: {})",
+                    s,
+                    AggregationOp::ALL
+                        .iter()
+                        .map(|op| op.suffix())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
 }
 
 impl TimeUnit {
@@ -225,6 +518,15 @@ impl ProcessingPipeline {
 
     /// Create a processing pipeline from configuration
     pub fn from_config(config: &ProcessingPipelineConfig) -> PostProcessResult<Self> {
+        Self::from_config_with_registry(config, &ProcessorRegistry::default())
+    }
+
+    /// Create a processing pipeline from configuration, resolving `custom` steps
+    /// through `registry`
+    pub fn from_config_with_registry(
+        config: &ProcessingPipelineConfig,
+        registry: &ProcessorRegistry,
+    ) -> PostProcessResult<Self> {
         let mut pipeline = Self {
             name: config
                 .name
@@ -234,7 +536,7 @@ impl ProcessingPipeline {
         };
 
         for processor_config in &config.processors {
-            let processor = create_processor(processor_config)?;
+            let processor = create_processor_with_registry(processor_config, registry)?;
             pipeline.add_processor(processor);
         }
 
@@ -246,6 +548,15 @@ impl ProcessingPipeline {
         self.processors.push(processor);
     }
 
+    /// Compute the schema produced by running every processor in sequence
+    pub fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        let mut schema = input_schema.clone();
+        for processor in &self.processors {
+            schema = processor.output_schema(&schema)?;
+        }
+        Ok(schema)
+    }
+
     /// Execute the processing pipeline on a DataFrame
     pub fn execute(&mut self, mut df: DataFrame) -> PostProcessResult<DataFrame> {
         debug!(
@@ -294,21 +605,76 @@ impl Default for ProcessingPipeline {
     }
 }
 
-/// Helper function to create a processor from configuration
+/// Builds a custom processor from the `params` of its configuration
+pub type ProcessorFactory =
+    Box<dyn Fn(&serde_json::Value) -> PostProcessResult<Box<dyn PostProcessor>> + Send + Sync>;
+
+/// Factories of custom processors, keyed by the `name` of
+/// [`ProcessorConfig::Custom`] steps
+#[derive(Default)]
+pub struct ProcessorRegistry {
+    factories: HashMap<String, ProcessorFactory>,
+}
+
+impl ProcessorRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register `factory` under `name`, replacing any factory already registered
+    /// under it
+    pub fn register<F>(&mut self, name: &str, factory: F)
+    where
+        F: Fn(&serde_json::Value) -> PostProcessResult<Box<dyn PostProcessor>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        self.factories.insert(name.to_string(), Box::new(factory));
+    }
+
+    /// Whether a factory is registered under `name`
+    pub fn contains(&self, name: &str) -> bool {
+        self.factories.contains_key(name)
+    }
+
+    /// Build the processor registered under `name` from `params`
+    pub fn create(
+        &self,
+        name: &str,
+        params: &serde_json::Value,
+    ) -> PostProcessResult<Box<dyn PostProcessor>> {
+        let factory = self.factories.get(name).ok_or_else(|| {
+            PostProcessError::ConfigurationError(format!("Unknown custom processor '{}'", name))
+        })?;
+        factory(params)
+    }
+}
+
+/// Helper function to create a processor from configuration; `custom` steps
+/// fail, as no processor is registered (see [`create_processor_with_registry`])
 pub fn create_processor(config: &ProcessorConfig) -> PostProcessResult<Box<dyn PostProcessor>> {
+    create_processor_with_registry(config, &ProcessorRegistry::default())
+}
+
+/// Create a processor from configuration, resolving `custom` steps through
+/// `registry`
+pub fn create_processor_with_registry(
+    config: &ProcessorConfig,
+    registry: &ProcessorRegistry,
+) -> PostProcessResult<Box<dyn PostProcessor>> {
     match config {
         ProcessorConfig::RenameColumns { mappings } => {
             Ok(Box::new(ColumnRenamer::new(mappings.clone())))
         }
         ProcessorConfig::DatetimeConvert { column, base, unit } => {
-            let base_dt = DateTime::parse_from_rfc3339(base)
-                .map_err(|e| {
-                    PostProcessError::ConfigurationError(format!(
-                        "Invalid base datetime '{}': {}",
-                        base, e
-                    ))
-                })?
-                .with_timezone(&Utc);
+            let (base_dt, _) = cftime::parse_reference_datetime(base).map_err(|e| {
+                PostProcessError::ConfigurationError(format!(
+                    "Invalid base datetime '{}': {}",
+                    base, e
+                ))
+            })?;
             Ok(Box::new(DateTimeConverter::new(
                 column.clone(),
                 base_dt,
@@ -323,7 +689,7 @@ pub fn create_processor(config: &ProcessorConfig) -> PostProcessResult<Box<dyn P
             column.clone(),
             from_unit.clone(),
             to_unit.clone(),
-        ))),
+        )?)),
         ProcessorConfig::Aggregate {
             group_by,
             aggregations,
@@ -340,7 +706,187 @@ pub fn create_processor(config: &ProcessorConfig) -> PostProcessResult<Box<dyn P
             formula.clone(),
             source_columns.clone(),
         ))),
+        ProcessorConfig::CanonicalizeNulls { nulls_to_nan } => {
+            Ok(Box::new(NullCanonicalizer::new(*nulls_to_nan)))
+        }
+        ProcessorConfig::Season {
+            time_column,
+            target_column,
+            hemisphere,
+        } => Ok(Box::new(SeasonLabeler::new(
+            time_column.clone(),
+            target_column.clone(),
+            *hemisphere,
+        ))),
+        ProcessorConfig::CumMax { column, order_by } => Ok(Box::new(CumulativeExtremum::new(
+            column.clone(),
+            order_by.clone(),
+            Extremum::Max,
+        ))),
+        ProcessorConfig::CumMin { column, order_by } => Ok(Box::new(CumulativeExtremum::new(
+            column.clone(),
+            order_by.clone(),
+            Extremum::Min,
+        ))),
+        ProcessorConfig::AsofJoin {
+            table_path,
+            on_time,
+            by,
+            tolerance,
+            direction,
+        } => Ok(Box::new(AsofJoiner::new(
+            table_path.clone(),
+            on_time.clone(),
+            by.clone().unwrap_or_default(),
+            tolerance.clone(),
+            *direction,
+        ))),
+        ProcessorConfig::DegreeDays {
+            temperature_column,
+            base,
+            kind,
+            target_column,
+        } => Ok(Box::new(DegreeDayCalculator::new(
+            temperature_column.clone(),
+            *base,
+            *kind,
+            target_column.clone(),
+        ))),
+        ProcessorConfig::Transform { column, op } => Ok(Box::new(ColumnTransform::new(
+            column.clone(),
+            op.parse().map_err(PostProcessError::ConfigurationError)?,
+        ))),
+        ProcessorConfig::Round {
+            decimals,
+            columns,
+            exclude,
+        } => Ok(Box::new(ColumnRounder::new(
+            *decimals,
+            columns.clone(),
+            exclude.clone(),
+        ))),
+        ProcessorConfig::RowFilter { predicate } => Ok(Box::new(RowFilter::new(
+            predicate
+                .parse()
+                .map_err(PostProcessError::ConfigurationError)?,
+        ))),
+        ProcessorConfig::Melt {
+            id_vars,
+            value_vars,
+        } => Ok(Box::new(Melter::new(id_vars.clone(), value_vars.clone()))),
+        ProcessorConfig::Pivot {
+            index,
+            columns,
+            values,
+            agg,
+        } => Ok(Box::new(Pivoter::new(
+            index.clone(),
+            columns.clone(),
+            values.clone(),
+            *agg,
+        ))),
+        ProcessorConfig::Pack {
+            column,
+            scale_factor,
+            add_offset,
+            dtype,
+        } => Ok(Box::new(ColumnPacker::new(
+            column.clone(),
+            *scale_factor,
+            *add_offset,
+            *dtype,
+        )?)),
+        ProcessorConfig::Custom { name, params } => registry.create(name, params),
+    }
+}
+
+/// Physical quantities recognized by `auto_unit_convert`, each with a reference
+/// unit whose dimensions identify it
+pub const KNOWN_QUANTITIES: &[(&str, &str)] = &[
+    ("temperature", "K"),
+    ("pressure", "Pa"),
+    ("length", "m"),
+    ("speed", "m s-1"),
+    ("mass", "kg"),
+    ("energy", "J"),
+    ("power", "W"),
+    ("energy_flux", "W m-2"),
+    ("precipitation_flux", "kg m-2 s-1"),
+];
+
+/// Returns the known quantity measured in `units` (e.g. `"temperature"` for
+/// `"degC"`), or `None` when the units are unknown or measure something else
+pub fn quantity_of_units(units: &str) -> Option<&'static str> {
+    let unit = units::Unit::parse(units).ok()?;
+    KNOWN_QUANTITIES
+        .iter()
+        .find(|(_, reference)| {
+            units::Unit::parse(reference).is_ok_and(|reference| unit.is_compatible(&reference))
+        })
+        .map(|(quantity, _)| *quantity)
+}
+
+/// Builds the unit conversions that normalize columns to the target units of
+/// their quantity.
+///
+/// `column_units` pairs each column with its `units` attribute and `targets`
+/// maps quantities from [`KNOWN_QUANTITIES`] to the unit to convert them to
+/// (e.g. `temperature` to `celsius`). Columns whose units cannot be parsed,
+/// measure a quantity without a target, or already equal the target are left
+/// untouched.
+///
+/// # Errors
+///
+/// Returns a configuration error if a target names an unknown quantity or a
+/// unit that does not measure that quantity.
+pub fn auto_unit_conversions(
+    column_units: &[(String, String)],
+    targets: &HashMap<String, String>,
+) -> PostProcessResult<Vec<ProcessorConfig>> {
+    for (quantity, target) in targets {
+        let Some((_, reference)) = KNOWN_QUANTITIES.iter().find(|(name, _)| name == quantity)
+        else {
+            let known: Vec<&str> = KNOWN_QUANTITIES.iter().map(|(name, _)| *name).collect();
+            return Err(PostProcessError::ConfigurationError(format!(
+                "Unknown quantity '{}' in auto_unit_convert (known: {})",
+                quantity,
+                known.join(", ")
+            )));
+        };
+        units::conversion(reference, target).map_err(|e| {
+            PostProcessError::ConfigurationError(format!(
+                "Invalid {} target unit '{}': {}",
+                quantity, target, e
+            ))
+        })?;
+    }
+
+    let mut conversions = Vec::new();
+    for (column, column_unit) in column_units {
+        let Some(quantity) = quantity_of_units(column_unit) else {
+            debug!(
+                "Not converting '{}': units '{}' are not a known quantity",
+                column, column_unit
+            );
+            continue;
+        };
+        let Some(target) = targets.get(quantity) else {
+            continue;
+        };
+        if units::conversion(column_unit, target).is_ok_and(|conversion| conversion == (1.0, 0.0)) {
+            continue;
+        }
+        debug!(
+            "Converting {} column '{}' from {} to {}",
+            quantity, column, column_unit, target
+        );
+        conversions.push(ProcessorConfig::UnitConvert {
+            column: column.clone(),
+            from_unit: column_unit.clone(),
+            to_unit: target.clone(),
+        });
     }
+    Ok(conversions)
 }
 
 /// Create a pipeline from a vector of processor configurations
@@ -371,11 +917,12 @@ pub struct UnitConverter {
     from_unit: String,
     to_unit: String,
     conversion_factor: f64,
+    offset: f64,
 }
 
 pub struct Aggregator {
     group_by: Vec<String>,
-    aggregations: HashMap<String, AggregationOp>,
+    aggregations: HashMap<String, AggregationOps>,
 }
 
 pub struct FormulaApplier {
@@ -402,15 +949,18 @@ impl DateTimeConverter {
 }
 
 impl UnitConverter {
-    pub fn new(column: String, from_unit: String, to_unit: String) -> Self {
-        // Calculate conversion factor based on units
-        let conversion_factor = Self::calculate_conversion_factor(&from_unit, &to_unit);
-        Self {
+    /// Creates a converter between two UDUNITS-style unit strings (e.g. `"W m-2"` and
+    /// `"W cm-2"`), failing if either unit is unknown or their dimensions differ.
+    pub fn new(column: String, from_unit: String, to_unit: String) -> PostProcessResult<Self> {
+        let (conversion_factor, offset) = units::conversion(&from_unit, &to_unit)
+            .map_err(|e| PostProcessError::ConfigurationError(e.to_string()))?;
+        Ok(Self {
             column,
             from_unit,
             to_unit,
             conversion_factor,
-        }
+            offset,
+        })
     }
 
     pub fn with_conversion_factor(
@@ -424,26 +974,13 @@ impl UnitConverter {
             from_unit,
             to_unit,
             conversion_factor: factor,
-        }
-    }
-
-    fn calculate_conversion_factor(from_unit: &str, to_unit: &str) -> f64 {
-        // Simplified conversion - will be expanded
-        match (
-            from_unit.to_lowercase().as_str(),
-            to_unit.to_lowercase().as_str(),
-        ) {
-            ("kelvin", "celsius") | ("k", "c") => 1.0, // Special case: K to C = K - 273.15
-            ("celsius", "kelvin") | ("c", "k") => 1.0, // Special case: C to K = C + 273.15
-            ("celsius", "fahrenheit") | ("c", "f") => 9.0 / 5.0,
-            ("fahrenheit", "celsius") | ("f", "c") => 5.0 / 9.0,
-            _ => 1.0, // Default: no conversion
+            offset: 0.0,
         }
     }
 }
 
 impl Aggregator {
-    pub fn new(group_by: Vec<String>, aggregations: HashMap<String, AggregationOp>) -> Self {
+    pub fn new(group_by: Vec<String>, aggregations: HashMap<String, AggregationOps>) -> Self {
         Self {
             group_by,
             aggregations,
@@ -563,8 +1100,8 @@ impl PostProcessor for DateTimeConverter {
 impl PostProcessor for UnitConverter {
     fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
         debug!(
-            "Converting column '{}' from {} to {} (factor: {})",
-            self.column, self.from_unit, self.to_unit, self.conversion_factor
+            "Converting column '{}' from {} to {} (factor: {}, offset: {})",
+            self.column, self.from_unit, self.to_unit, self.conversion_factor, self.offset
         );
 
         // Check if column exists
@@ -573,50 +1110,15 @@ impl PostProcessor for UnitConverter {
             return Err(PostProcessError::ColumnNotFound(self.column.clone()));
         }
 
-        let result = if (self.from_unit.to_lowercase() == "kelvin"
-            || self.from_unit.to_lowercase() == "k")
-            && (self.to_unit.to_lowercase() == "celsius" || self.to_unit.to_lowercase() == "c")
-        {
-            // Special case: Kelvin to Celsius (K - 273.15)
-            df.lazy()
-                .with_columns([(col(&self.column) - lit(273.15)).alias(&self.column)])
-                .collect()?
-        } else if (self.from_unit.to_lowercase() == "celsius"
-            || self.from_unit.to_lowercase() == "c")
-            && (self.to_unit.to_lowercase() == "kelvin" || self.to_unit.to_lowercase() == "k")
-        {
-            // Special case: Celsius to Kelvin (C + 273.15)
-            df.lazy()
-                .with_columns([(col(&self.column) + lit(273.15)).alias(&self.column)])
-                .collect()?
-        } else if (self.from_unit.to_lowercase() == "celsius"
-            || self.from_unit.to_lowercase() == "c")
-            && (self.to_unit.to_lowercase() == "fahrenheit" || self.to_unit.to_lowercase() == "f")
-        {
-            // Special case: Celsius to Fahrenheit (C * 9/5 + 32)
-            df.lazy()
-                .with_columns(
-                    [(col(&self.column) * lit(9.0 / 5.0) + lit(32.0)).alias(&self.column)],
-                )
-                .collect()?
-        } else if (self.from_unit.to_lowercase() == "fahrenheit"
-            || self.from_unit.to_lowercase() == "f")
-            && (self.to_unit.to_lowercase() == "celsius" || self.to_unit.to_lowercase() == "c")
-        {
-            // Special case: Fahrenheit to Celsius ((F - 32) * 5/9)
-            df.lazy()
-                .with_columns([
-                    ((col(&self.column) - lit(32.0)) * lit(5.0 / 9.0)).alias(&self.column)
-                ])
-                .collect()?
-        } else {
-            // Simple multiplication conversion
-            df.lazy()
-                .with_columns([
-                    (col(&self.column) * lit(self.conversion_factor)).alias(&self.column)
-                ])
-                .collect()?
-        };
+        let mut converted = col(&self.column) * lit(self.conversion_factor);
+        if self.offset != 0.0 {
+            converted = converted + lit(self.offset);
+        }
+
+        let result = df
+            .lazy()
+            .with_columns([converted.alias(&self.column)])
+            .collect()?;
 
         Ok(result)
     }
@@ -645,29 +1147,45 @@ impl PostProcessor for Aggregator {
             }
         }
 
-        // Check if all aggregation columns exist
-        for col_name in self.aggregations.keys() {
+        // Check if all aggregation columns exist, each with distinct operations
+        for (col_name, agg_ops) in &self.aggregations {
             if !column_names.contains(&col_name.as_str()) {
                 return Err(PostProcessError::ColumnNotFound(col_name.clone()));
             }
+            if self.group_by.contains(col_name) {
+                return Err(PostProcessError::ConfigurationError(format!(
+                    "Column '{}' cannot be both grouped by and aggregated",
+                    col_name
+                )));
+            }
+            let ops = agg_ops.ops();
+            if ops.is_empty() {
+                return Err(PostProcessError::ConfigurationError(format!(
+                    "No aggregation operations given for column '{}'",
+                    col_name
+                )));
+            }
+            if let Some(op) = ops
+                .iter()
+                .enumerate()
+                .find_map(|(i, op)| ops[..i].contains(op).then_some(op))
+            {
+                return Err(PostProcessError::ConfigurationError(format!(
+                    "Aggregation '{}' is listed twice for column '{}'",
+                    op.suffix(),
+                    col_name
+                )));
+            }
         }
 
-        // Build aggregation expressions
+        // Build aggregation expressions, one output column per (column, operation)
         let mut agg_exprs = Vec::new();
 
-        for (col_name, agg_op) in &self.aggregations {
-            let (expr, suffix) = match agg_op {
-                AggregationOp::Mean => (col(col_name).mean(), "mean"),
-                AggregationOp::Sum => (col(col_name).sum(), "sum"),
-                AggregationOp::Min => (col(col_name).min(), "min"),
-                AggregationOp::Max => (col(col_name).max(), "max"),
-                AggregationOp::Count => (col(col_name).count(), "count"),
-                AggregationOp::Std => (col(col_name).std(1), "std"), // Use population std
-                AggregationOp::Var => (col(col_name).var(1), "var"), // Use population var
-                AggregationOp::First => (col(col_name).first(), "first"),
-                AggregationOp::Last => (col(col_name).last(), "last"),
-            };
-            agg_exprs.push(expr.alias(format!("{}_{}", col_name, suffix)));
+        for (col_name, agg_ops) in &self.aggregations {
+            for agg_op in agg_ops.ops() {
+                let expr = agg_op.expr(col(col_name));
+                agg_exprs.push(expr.alias(format!("{}_{}", col_name, agg_op.suffix())));
+            }
         }
 
         let result = if !self.group_by.is_empty() {
@@ -954,3 +1472,1361 @@ impl FormulaApplier {
         }
     }
 }
+
+/// Unifies the missing-value representation of all float columns.
+///
+/// By default every `NaN` becomes a proper null; with `nulls_to_nan` set the
+/// conversion goes the other way and nulls are written as `NaN`.
+pub struct NullCanonicalizer {
+    nulls_to_nan: bool,
+}
+
+impl NullCanonicalizer {
+    pub fn new(nulls_to_nan: bool) -> Self {
+        Self { nulls_to_nan }
+    }
+}
+
+impl PostProcessor for NullCanonicalizer {
+    fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
+        let float_columns: Vec<(String, DataType)> = df
+            .get_columns()
+            .iter()
+            .filter(|column| column.dtype().is_float())
+            .map(|column| (column.name().to_string(), column.dtype().clone()))
+            .collect();
+
+        debug!(
+            "Canonicalizing missing values in {} float columns ({})",
+            float_columns.len(),
+            if self.nulls_to_nan {
+                "null -> NaN"
+            } else {
+                "NaN -> null"
+            }
+        );
+
+        if float_columns.is_empty() {
+            return Ok(df);
+        }
+
+        let exprs: Vec<Expr> = float_columns
+            .iter()
+            .map(|(name, dtype)| {
+                if self.nulls_to_nan {
+                    col(name.as_str()).fill_null(lit(f64::NAN).cast(dtype.clone()))
+                } else {
+                    col(name.as_str()).fill_nan(lit(NULL).cast(dtype.clone()))
+                }
+            })
+            .collect();
+
+        Ok(df.lazy().with_columns(exprs).collect()?)
+    }
+
+    fn name(&self) -> &str {
+        "NullCanonicalizer"
+    }
+
+    fn description(&self) -> &str {
+        "Converts between NaN and null so float columns share one missing-value representation"
+    }
+}
+
+/// Labels each row with the meteorological season of a datetime column.
+///
+/// Seasons are the usual three-month blocks `DJF`, `MAM`, `JJA` and `SON`. For the
+/// southern hemisphere the mapping is shifted by six months, so that a label always
+/// names the same part of the year relative to the local summer: January falls in
+/// `JJA` (summer) and July in `DJF` (winter).
+pub struct SeasonLabeler {
+    time_column: String,
+    target_column: String,
+    hemisphere: Hemisphere,
+}
+
+impl SeasonLabeler {
+    pub fn new(time_column: String, target_column: String, hemisphere: Hemisphere) -> Self {
+        Self {
+            time_column,
+            target_column,
+            hemisphere,
+        }
+    }
+
+    /// Returns the season label of `month` (1-12) in the given hemisphere
+    pub fn season_for_month(month: u32, hemisphere: Hemisphere) -> &'static str {
+        let month = match hemisphere {
+            Hemisphere::North => month,
+            Hemisphere::South => (month + 5) % 12 + 1,
+        };
+        match month {
+            12 | 1 | 2 => "DJF",
+            3..=5 => "MAM",
+            6..=8 => "JJA",
+            _ => "SON",
+        }
+    }
+
+    fn check_time_column(&self, dtype: Option<&DataType>) -> PostProcessResult<()> {
+        match dtype {
+            Some(DataType::Datetime(_, _)) => Ok(()),
+            Some(other) => Err(PostProcessError::ConversionError(format!(
+                "Season requires a datetime column, but '{}' is {}",
+                self.time_column, other
+            ))),
+            None => Err(PostProcessError::ColumnNotFound(self.time_column.clone())),
+        }
+    }
+}
+
+impl PostProcessor for SeasonLabeler {
+    fn process(&self, mut df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!(
+            "Labelling seasons of '{}' into '{}' ({:?} hemisphere)",
+            self.time_column, self.target_column, self.hemisphere
+        );
+
+        let time = df
+            .column(&self.time_column)
+            .map_err(|_| PostProcessError::ColumnNotFound(self.time_column.clone()))?;
+        self.check_time_column(Some(time.dtype()))?;
+        let DataType::Datetime(unit, _) = time.dtype() else {
+            unreachable!("checked above");
+        };
+        let unit = *unit;
+
+        let timestamps = time.cast(&DataType::Int64)?;
+        let seasons: Vec<Option<&str>> = timestamps
+            .i64()?
+            .into_iter()
+            .map(|timestamp| {
+                let datetime = match unit {
+                    polars::prelude::TimeUnit::Milliseconds => {
+                        DateTime::from_timestamp_millis(timestamp?)
+                    }
+                    polars::prelude::TimeUnit::Microseconds => {
+                        DateTime::from_timestamp_micros(timestamp?)
+                    }
+                    polars::prelude::TimeUnit::Nanoseconds => {
+                        Some(DateTime::from_timestamp_nanos(timestamp?))
+                    }
+                };
+                datetime.map(|datetime| Self::season_for_month(datetime.month(), self.hemisphere))
+            })
+            .collect();
+
+        df.with_column(Column::new(self.target_column.as_str().into(), seasons))?;
+        Ok(df)
+    }
+
+    fn name(&self) -> &str {
+        "SeasonLabeler"
+    }
+
+    fn description(&self) -> &str {
+        "Derives the meteorological season (DJF/MAM/JJA/SON) of a datetime column"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        self.check_time_column(schema.get(&self.time_column))
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        let mut new_schema = input_schema.clone();
+        new_schema.with_column(self.target_column.clone().into(), DataType::String);
+        Ok(new_schema)
+    }
+}
+
+/// Which running extremum a [`CumulativeExtremum`] computes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Extremum {
+    Max,
+    Min,
+}
+
+/// Adds the running (expanding window) maximum or minimum of a column.
+///
+/// The result is written to `<column>_cum_max` or `<column>_cum_min`. When
+/// `order_by` is set, rows are first sorted by that column (e.g. time), and the
+/// output keeps that order.
+///
+/// Null values are skipped: their output is null, and the running value carries
+/// over them unchanged to the next non-null row.
+pub struct CumulativeExtremum {
+    column: String,
+    order_by: Option<String>,
+    extremum: Extremum,
+}
+
+impl CumulativeExtremum {
+    pub fn new(column: String, order_by: Option<String>, extremum: Extremum) -> Self {
+        Self {
+            column,
+            order_by,
+            extremum,
+        }
+    }
+
+    /// Name of the column the running extremum is written to
+    pub fn target_column(&self) -> String {
+        match self.extremum {
+            Extremum::Max => format!("{}_cum_max", self.column),
+            Extremum::Min => format!("{}_cum_min", self.column),
+        }
+    }
+}
+
+impl PostProcessor for CumulativeExtremum {
+    fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!(
+            "Computing running {:?} of '{}' ordered by {:?}",
+            self.extremum, self.column, self.order_by
+        );
+
+        self.validate_schema(&df.schema())?;
+
+        let mut lazy = df.lazy();
+        if let Some(ref order_by) = self.order_by {
+            lazy = lazy.sort([order_by.as_str()], SortMultipleOptions::default());
+        }
+        let running = match self.extremum {
+            Extremum::Max => col(&self.column).cum_max(false),
+            Extremum::Min => col(&self.column).cum_min(false),
+        };
+
+        Ok(lazy
+            .with_columns([running.alias(self.target_column())])
+            .collect()?)
+    }
+
+    fn name(&self) -> &str {
+        match self.extremum {
+            Extremum::Max => "CumulativeMax",
+            Extremum::Min => "CumulativeMin",
+        }
+    }
+
+    fn description(&self) -> &str {
+        "Adds the running maximum or minimum of a column, optionally ordered by another column"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        for column in std::iter::once(&self.column).chain(self.order_by.as_ref()) {
+            if !schema.contains(column) {
+                return Err(PostProcessError::ColumnNotFound(column.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        let dtype = input_schema
+            .get(&self.column)
+            .ok_or_else(|| PostProcessError::ColumnNotFound(self.column.clone()))?;
+        let mut new_schema = input_schema.clone();
+        new_schema.with_column(self.target_column().into(), dtype.clone());
+        Ok(new_schema)
+    }
+}
+
+/// Joins each row with the nearest-in-time row of an auxiliary table.
+///
+/// The table is read from a local Parquet or Arrow IPC file (chosen by extension,
+/// see [`OutputFileFormat::from_path`]) when the processor runs. Both `on_time`
+/// columns must be datetimes sorted in ascending order; with `by`, rows are only
+/// matched within equal values of those columns, and each group must be sorted.
+/// Rows without a match within `tolerance` get nulls in the table's columns, and
+/// columns present on both sides get a `_right` suffix.
+pub struct AsofJoiner {
+    table_path: String,
+    on_time: String,
+    by: Vec<String>,
+    tolerance: Option<String>,
+    direction: AsofDirection,
+}
+
+impl AsofJoiner {
+    pub fn new(
+        table_path: String,
+        on_time: String,
+        by: Vec<String>,
+        tolerance: Option<String>,
+        direction: AsofDirection,
+    ) -> Self {
+        Self {
+            table_path,
+            on_time,
+            by,
+            tolerance,
+            direction,
+        }
+    }
+
+    fn read_table(&self) -> PostProcessResult<DataFrame> {
+        let file = std::fs::File::open(&self.table_path).map_err(|e| {
+            PostProcessError::ProcessingError(format!(
+                "Failed to open join table '{}': {}",
+                self.table_path, e
+            ))
+        })?;
+        Ok(match OutputFileFormat::from_path(&self.table_path) {
+            OutputFileFormat::Parquet => ParquetReader::new(file).finish()?,
+            OutputFileFormat::ArrowIpc => IpcReader::new(file).finish()?,
+        })
+    }
+
+    /// Checks that `df` has the join columns and a sorted datetime time column
+    fn check_join_columns(&self, df: &DataFrame, side: &str) -> PostProcessResult<()> {
+        for column in &self.by {
+            if df.column(column).is_err() {
+                return Err(PostProcessError::ColumnNotFound(column.clone()));
+            }
+        }
+
+        let time = df
+            .column(&self.on_time)
+            .map_err(|_| PostProcessError::ColumnNotFound(self.on_time.clone()))?;
+        if !matches!(time.dtype(), DataType::Datetime(..)) {
+            return Err(PostProcessError::ProcessingError(format!(
+                "As-of join column '{}' of the {} must be a datetime, found {}",
+                self.on_time,
+                side,
+                time.dtype()
+            )));
+        }
+        // Sortedness can only be checked globally; within `by` groups it is up to the data
+        if self.by.is_empty()
+            && !time
+                .as_materialized_series()
+                .is_sorted(SortOptions::default())?
+        {
+            return Err(PostProcessError::ProcessingError(format!(
+                "As-of join column '{}' of the {} must be sorted in ascending order",
+                self.on_time, side
+            )));
+        }
+        Ok(())
+    }
+}
+
+impl PostProcessor for AsofJoiner {
+    fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!(
+            "As-of joining '{}' on '{}' ({:?}, tolerance {:?})",
+            self.table_path, self.on_time, self.direction, self.tolerance
+        );
+
+        self.check_join_columns(&df, "input")?;
+        let mut table = self.read_table()?;
+        self.check_join_columns(&table, "join table")?;
+
+        // Datetimes only compare in the same unit and time zone
+        let time_dtype = df.column(&self.on_time)?.dtype().clone();
+        if table.column(&self.on_time)?.dtype() != &time_dtype {
+            table = table
+                .lazy()
+                .with_column(col(&self.on_time).cast(time_dtype))
+                .collect()?;
+        }
+
+        let by: Option<Vec<PlSmallStr>> = (!self.by.is_empty()).then(|| {
+            self.by
+                .iter()
+                .map(|column| column.as_str().into())
+                .collect()
+        });
+        let options = AsOfOptions {
+            strategy: self.direction.into(),
+            tolerance_str: self.tolerance.as_deref().map(PlSmallStr::from),
+            left_by: by.clone(),
+            right_by: by,
+            allow_eq: true,
+            check_sortedness: false,
+            ..AsOfOptions::default()
+        };
+
+        Ok(df
+            .lazy()
+            .join_builder()
+            .with(table.lazy())
+            .left_on([col(&self.on_time)])
+            .right_on([col(&self.on_time)])
+            .how(JoinType::AsOf(Box::new(options)))
+            .finish()
+            .collect()?)
+    }
+
+    fn name(&self) -> &str {
+        "AsofJoiner"
+    }
+
+    fn description(&self) -> &str {
+        "Attaches the nearest-in-time row of an auxiliary table"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        for column in std::iter::once(&self.on_time).chain(&self.by) {
+            if !schema.contains(column) {
+                return Err(PostProcessError::ColumnNotFound(column.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        let table = self.read_table()?;
+        let mut new_schema = input_schema.clone();
+        for (name, dtype) in table.schema().iter() {
+            if name == self.on_time.as_str() || self.by.iter().any(|column| column == name) {
+                continue;
+            }
+            let name = if input_schema.contains(name) {
+                format!("{}_right", name).into()
+            } else {
+                name.clone()
+            };
+            new_schema.with_column(name, dtype.clone());
+        }
+        Ok(new_schema)
+    }
+}
+
+/// Computes the per-row heating or cooling degree-day contribution of a
+/// temperature column.
+///
+/// Heating degree-days are `max(base - T, 0)` and cooling degree-days are
+/// `max(T - base, 0)`, written as a float column to `target_column`. Summing the
+/// result over daily mean temperatures (e.g. with an `aggregate` step) gives the
+/// degree-days of a period. Null temperatures produce nulls.
+pub struct DegreeDayCalculator {
+    temperature_column: String,
+    base: f64,
+    kind: DegreeDayKind,
+    target_column: String,
+}
+
+impl DegreeDayCalculator {
+    pub fn new(
+        temperature_column: String,
+        base: f64,
+        kind: DegreeDayKind,
+        target_column: String,
+    ) -> Self {
+        Self {
+            temperature_column,
+            base,
+            kind,
+            target_column,
+        }
+    }
+
+    fn check_temperature_column(&self, dtype: Option<&DataType>) -> PostProcessResult<()> {
+        match dtype {
+            Some(dtype) if dtype.is_primitive_numeric() => Ok(()),
+            Some(other) => Err(PostProcessError::ConversionError(format!(
+                "Degree-days require a numeric temperature column, but '{}' is {}",
+                self.temperature_column, other
+            ))),
+            None => Err(PostProcessError::ColumnNotFound(
+                self.temperature_column.clone(),
+            )),
+        }
+    }
+}
+
+impl PostProcessor for DegreeDayCalculator {
+    fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!(
+            "Computing {:?} degree-days of '{}' over base {} into '{}'",
+            self.kind, self.temperature_column, self.base, self.target_column
+        );
+
+        self.validate_schema(&df.schema())?;
+
+        let temperature = col(&self.temperature_column).cast(DataType::Float64);
+        let difference = match self.kind {
+            DegreeDayKind::Heating => lit(self.base) - temperature,
+            DegreeDayKind::Cooling => temperature - lit(self.base),
+        };
+        // A null difference fails the condition and stays null
+        let degree_days = when(difference.clone().lt(lit(0.0)))
+            .then(lit(0.0))
+            .otherwise(difference);
+
+        Ok(df
+            .lazy()
+            .with_column(degree_days.alias(self.target_column.as_str()))
+            .collect()?)
+    }
+
+    fn name(&self) -> &str {
+        "DegreeDayCalculator"
+    }
+
+    fn description(&self) -> &str {
+        "Computes heating or cooling degree-days of a temperature column"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        self.check_temperature_column(schema.get(&self.temperature_column))
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        let mut new_schema = input_schema.clone();
+        new_schema.with_column(self.target_column.clone().into(), DataType::Float64);
+        Ok(new_schema)
+    }
+}
+
+/// Single-column functions applied by [`ColumnTransform`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransformOp {
+    /// Natural logarithm, same as `ln`
+    Log,
+    Log10,
+    Ln,
+    Exp,
+    /// `1 / x`
+    Reciprocal,
+    /// `-x`
+    Negate,
+}
+
+impl TransformOp {
+    pub const ALL: [TransformOp; 6] = [
+        TransformOp::Log,
+        TransformOp::Log10,
+        TransformOp::Ln,
+        TransformOp::Exp,
+        TransformOp::Reciprocal,
+        TransformOp::Negate,
+    ];
+
+    /// Name of the operation in configuration files
+    pub fn name(&self) -> &'static str {
+        match self {
+            TransformOp::Log => "log",
+            TransformOp::Log10 => "log10",
+            TransformOp::Ln => "ln",
+            TransformOp::Exp => "exp",
+            TransformOp::Reciprocal => "reciprocal",
+            TransformOp::Negate => "negate",
+        }
+    }
+
+    /// Applies the operation to one value, following IEEE 754 rules (e.g. the
+    /// logarithm of a negative value is NaN and the reciprocal of 0 is infinite)
+    pub fn apply(&self, value: f64) -> f64 {
+        match self {
+            TransformOp::Log | TransformOp::Ln => value.ln(),
+            TransformOp::Log10 => value.log10(),
+            TransformOp::Exp => value.exp(),
+            TransformOp::Reciprocal => value.recip(),
+            TransformOp::Negate => -value,
+        }
+    }
+}
+
+impl std::str::FromStr for TransformOp {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        TransformOp::ALL
+            .into_iter()
+            .find(|op| op.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown transform '{}' (known: {})",
+                    s,
+                    TransformOp::ALL
+                        .iter()
+                        .map(|op| op.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+/// Replaces a numeric column with a function of its values (see [`TransformOp`]).
+///
+/// The column keeps its name and position and becomes a float column; nulls stay
+/// null. This covers common single-column math without a formula.
+pub struct ColumnTransform {
+    column: String,
+    op: TransformOp,
+}
+
+impl ColumnTransform {
+    pub fn new(column: String, op: TransformOp) -> Self {
+        Self { column, op }
+    }
+}
+
+impl PostProcessor for ColumnTransform {
+    fn process(&self, mut df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!("Applying {} to column '{}'", self.op.name(), self.column);
+
+        self.validate_schema(&df.schema())?;
+
+        let values = df.column(&self.column)?.cast(&DataType::Float64)?;
+        let op = self.op;
+        let transformed = values
+            .f64()?
+            .apply_values(|value| op.apply(value))
+            .with_name(self.column.as_str().into());
+        df.with_column(transformed)?;
+        Ok(df)
+    }
+
+    fn name(&self) -> &str {
+        "ColumnTransform"
+    }
+
+    fn description(&self) -> &str {
+        "Replaces a column with its logarithm, exponential, reciprocal or negation"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        match schema.get(&self.column) {
+            Some(dtype) if dtype.is_primitive_numeric() => Ok(()),
+            Some(other) => Err(PostProcessError::ConversionError(format!(
+                "Cannot apply {} to column '{}' of type {}",
+                self.op.name(),
+                self.column,
+                other
+            ))),
+            None => Err(PostProcessError::ColumnNotFound(self.column.clone())),
+        }
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        self.validate_schema(input_schema)?;
+        let mut new_schema = input_schema.clone();
+        new_schema.with_column(self.column.clone().into(), DataType::Float64);
+        Ok(new_schema)
+    }
+}
+
+/// Rounds float columns to a fixed number of decimal places.
+///
+/// With no `columns`, every float column except those in `exclude` is rounded;
+/// other columns are left untouched. Halves are rounded away from zero. Rounding
+/// drops false precision and helps Parquet encode and compress the values.
+pub struct ColumnRounder {
+    decimals: u32,
+    columns: Vec<String>,
+    exclude: Vec<String>,
+}
+
+impl ColumnRounder {
+    pub fn new(decimals: u32, columns: Vec<String>, exclude: Vec<String>) -> Self {
+        Self {
+            decimals,
+            columns,
+            exclude,
+        }
+    }
+
+    /// Columns of `schema` this processor rounds
+    fn target_columns(&self, schema: &Schema) -> Vec<String> {
+        if !self.columns.is_empty() {
+            return self.columns.clone();
+        }
+        schema
+            .iter()
+            .filter(|(name, dtype)| dtype.is_float() && !self.exclude.contains(&name.to_string()))
+            .map(|(name, _)| name.to_string())
+            .collect()
+    }
+}
+
+impl PostProcessor for ColumnRounder {
+    fn process(&self, mut df: DataFrame) -> PostProcessResult<DataFrame> {
+        self.validate_schema(&df.schema())?;
+
+        for column in self.target_columns(&df.schema()) {
+            debug!("Rounding column '{}' to {} decimals", column, self.decimals);
+            let rounded = df
+                .column(&column)?
+                .as_materialized_series()
+                .round(self.decimals, RoundMode::HalfAwayFromZero)?;
+            df.with_column(rounded)?;
+        }
+        Ok(df)
+    }
+
+    fn name(&self) -> &str {
+        "ColumnRounder"
+    }
+
+    fn description(&self) -> &str {
+        "Rounds float columns to a number of decimal places"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        for column in &self.columns {
+            match schema.get(column) {
+                Some(dtype) if dtype.is_float() => {}
+                Some(other) => {
+                    return Err(PostProcessError::ConversionError(format!(
+                        "Cannot round column '{}' of type {}",
+                        column, other
+                    )));
+                }
+                None => return Err(PostProcessError::ColumnNotFound(column.clone())),
+            }
+        }
+        Ok(())
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        self.validate_schema(input_schema)?;
+        Ok(input_schema.clone())
+    }
+}
+
+/// Comparison operators of a [`RowPredicate::Compare`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl ComparisonOp {
+    /// Two-character operators first, so that `<=` is not read as `<`
+    pub const ALL: [ComparisonOp; 6] = [
+        ComparisonOp::Eq,
+        ComparisonOp::NotEq,
+        ComparisonOp::LtEq,
+        ComparisonOp::GtEq,
+        ComparisonOp::Lt,
+        ComparisonOp::Gt,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            ComparisonOp::Eq => "==",
+            ComparisonOp::NotEq => "!=",
+            ComparisonOp::Lt => "<",
+            ComparisonOp::LtEq => "<=",
+            ComparisonOp::Gt => ">",
+            ComparisonOp::GtEq => ">=",
+        }
+    }
+}
+
+/// Row predicates understood by [`RowFilter`].
+///
+/// Predicates are written `column <op> value` (with `op` one of `==`, `!=`, `<`,
+/// `<=`, `>` or `>=`), `column is null`, `column is not null` or
+/// `column between low and high`; keywords are case-insensitive.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RowPredicate {
+    Compare {
+        column: String,
+        op: ComparisonOp,
+        value: f64,
+    },
+    IsNull {
+        column: String,
+    },
+    IsNotNull {
+        column: String,
+    },
+    /// Both bounds are included
+    Between {
+        column: String,
+        low: f64,
+        high: f64,
+    },
+}
+
+impl RowPredicate {
+    pub fn column(&self) -> &str {
+        match self {
+            RowPredicate::Compare { column, .. }
+            | RowPredicate::IsNull { column }
+            | RowPredicate::IsNotNull { column }
+            | RowPredicate::Between { column, .. } => column,
+        }
+    }
+
+    /// The predicate as a boolean Polars expression
+    pub fn expr(&self) -> Expr {
+        match self {
+            RowPredicate::Compare { column, op, value } => {
+                let (column, value) = (col(column.as_str()), lit(*value));
+                match op {
+                    ComparisonOp::Eq => column.eq(value),
+                    ComparisonOp::NotEq => column.neq(value),
+                    ComparisonOp::Lt => column.lt(value),
+                    ComparisonOp::LtEq => column.lt_eq(value),
+                    ComparisonOp::Gt => column.gt(value),
+                    ComparisonOp::GtEq => column.gt_eq(value),
+                }
+            }
+            RowPredicate::IsNull { column } => col(column.as_str()).is_null(),
+            RowPredicate::IsNotNull { column } => col(column.as_str()).is_not_null(),
+            RowPredicate::Between { column, low, high } => col(column.as_str())
+                .gt_eq(lit(*low))
+                .and(col(column.as_str()).lt_eq(lit(*high))),
+        }
+    }
+}
+
+/// Parses one side of a predicate as a column name
+fn predicate_column(column: &str, predicate: &str) -> Result<String, String> {
+    let column = column.trim();
+    if column.is_empty() {
+        return Err(format!("Missing column in predicate '{}'", predicate));
+    }
+    Ok(column.to_string())
+}
+
+/// Parses one side of a predicate as a number
+fn predicate_value(value: &str, predicate: &str) -> Result<f64, String> {
+    value.trim().parse().map_err(|_| {
+        format!(
+            "Invalid number '{}' in predicate '{}'",
+            value.trim(),
+            predicate
+        )
+    })
+}
+
+impl std::str::FromStr for RowPredicate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let predicate = s.trim();
+        // Keywords are ASCII, so byte offsets in the lowercase copy match `predicate`
+        let lowercase = predicate.to_ascii_lowercase();
+
+        if let Some(column) = lowercase.strip_suffix(" is not null") {
+            return Ok(RowPredicate::IsNotNull {
+                column: predicate_column(&predicate[..column.len()], predicate)?,
+            });
+        }
+        if let Some(column) = lowercase.strip_suffix(" is null") {
+            return Ok(RowPredicate::IsNull {
+                column: predicate_column(&predicate[..column.len()], predicate)?,
+            });
+        }
+        if let Some(between) = lowercase.find(" between ") {
+            let bounds = &lowercase[between + " between ".len()..];
+            let and = bounds.find(" and ").ok_or_else(|| {
+                format!(
+                    "Expected 'column between low and high', got '{}'",
+                    predicate
+                )
+            })?;
+            let bounds_start = between + " between ".len();
+            let low = predicate_value(&predicate[bounds_start..bounds_start + and], predicate)?;
+            let high =
+                predicate_value(&predicate[bounds_start + and + " and ".len()..], predicate)?;
+            if low > high {
+                return Err(format!(
+                    "Lower bound {} is above upper bound {} in predicate '{}'",
+                    low, high, predicate
+                ));
+            }
+            return Ok(RowPredicate::Between {
+                column: predicate_column(&predicate[..between], predicate)?,
+                low,
+                high,
+            });
+        }
+        for op in ComparisonOp::ALL {
+            if let Some((column, value)) = predicate.split_once(op.symbol()) {
+                return Ok(RowPredicate::Compare {
+                    column: predicate_column(column, predicate)?,
+                    op,
+                    value: predicate_value(value, predicate)?,
+                });
+            }
+        }
+
+        Err(format!(
+            "Unable to parse predicate '{}' (expected 'column <op> value', 'column is null', \
+             'column is not null' or 'column between low and high')",
+            predicate
+        ))
+    }
+}
+
+/// Keeps the rows matching a [`RowPredicate`] and drops the others.
+///
+/// Comparisons with a null value do not match, so nulls are only kept by
+/// `is null`. `NaN` is not null; canonicalize nulls first to treat it as missing.
+pub struct RowFilter {
+    predicate: RowPredicate,
+}
+
+impl RowFilter {
+    pub fn new(predicate: RowPredicate) -> Self {
+        Self { predicate }
+    }
+}
+
+impl PostProcessor for RowFilter {
+    fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!("Filtering rows with {:?}", self.predicate);
+
+        self.validate_schema(&df.schema())?;
+
+        Ok(df.lazy().filter(self.predicate.expr()).collect()?)
+    }
+
+    fn name(&self) -> &str {
+        "RowFilter"
+    }
+
+    fn description(&self) -> &str {
+        "Keeps the rows matching a comparison, null check or range predicate"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        let column = self.predicate.column();
+        match (&self.predicate, schema.get(column)) {
+            (_, None) => Err(PostProcessError::ColumnNotFound(column.to_string())),
+            (RowPredicate::IsNull { .. } | RowPredicate::IsNotNull { .. }, Some(_)) => Ok(()),
+            (_, Some(dtype)) if dtype.is_primitive_numeric() => Ok(()),
+            (_, Some(other)) => Err(PostProcessError::ConversionError(format!(
+                "Cannot compare column '{}' of type {} with a number",
+                column, other
+            ))),
+        }
+    }
+}
+
+/// Unpivots a wide DataFrame into long format.
+///
+/// Each input row becomes one row per value column: the `id_vars` are repeated,
+/// `variable` holds the name of the value column and `value` its content, cast to
+/// the common supertype of the value columns. Columns in neither list are dropped.
+pub struct Melter {
+    id_vars: Vec<String>,
+    value_vars: Vec<String>,
+}
+
+impl Melter {
+    pub fn new(id_vars: Vec<String>, value_vars: Vec<String>) -> Self {
+        Self {
+            id_vars,
+            value_vars,
+        }
+    }
+}
+
+impl PostProcessor for Melter {
+    fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!(
+            "Melting {:?} with id columns {:?}",
+            self.value_vars, self.id_vars
+        );
+
+        self.validate_schema(&df.schema())?;
+
+        Ok(df.unpivot(self.value_vars.clone(), self.id_vars.clone())?)
+    }
+
+    fn name(&self) -> &str {
+        "Melter"
+    }
+
+    fn description(&self) -> &str {
+        "Unpivots value columns into long variable/value rows"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        if self.value_vars.is_empty() {
+            return Err(PostProcessError::ConfigurationError(
+                "melt needs at least one value column".to_string(),
+            ));
+        }
+        for column in self.id_vars.iter().chain(&self.value_vars) {
+            if !schema.contains(column) {
+                return Err(PostProcessError::ColumnNotFound(column.clone()));
+            }
+        }
+        if let Some(column) = self.id_vars.iter().find(|c| self.value_vars.contains(c)) {
+            return Err(PostProcessError::ConfigurationError(format!(
+                "Column '{}' cannot be both an id and a value column",
+                column
+            )));
+        }
+        Ok(())
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        self.validate_schema(input_schema)?;
+        // Unpivoting an empty frame resolves the value supertype without any data
+        let empty = DataFrame::empty_with_schema(input_schema);
+        let melted = empty.unpivot(self.value_vars.clone(), self.id_vars.clone())?;
+        Ok(Schema::clone(melted.schema()))
+    }
+}
+
+/// Pivots a long DataFrame into wide format.
+///
+/// Each distinct value of `columns` becomes a column named after it, holding the
+/// `values` of the rows with that value; the output has one row per distinct
+/// combination of `index` columns, in order of first appearance. Cells without a
+/// matching row are null. When several rows map to the same cell, `agg` combines
+/// them; without it the pivot is rejected instead of keeping an arbitrary row.
+pub struct Pivoter {
+    index: Vec<String>,
+    columns: String,
+    values: String,
+    agg: Option<AggregationOp>,
+}
+
+impl Pivoter {
+    pub fn new(
+        index: Vec<String>,
+        columns: String,
+        values: String,
+        agg: Option<AggregationOp>,
+    ) -> Self {
+        Self {
+            index,
+            columns,
+            values,
+            agg,
+        }
+    }
+
+    /// Whether two rows share both their index values and their `columns` value
+    fn has_collisions(&self, df: &DataFrame) -> PostProcessResult<bool> {
+        let keys: Vec<Expr> = self
+            .index
+            .iter()
+            .chain(std::iter::once(&self.columns))
+            .map(col)
+            .collect();
+        let cells = df
+            .clone()
+            .lazy()
+            .select(keys)
+            .unique(None, UniqueKeepStrategy::Any)
+            .collect()?;
+        Ok(cells.height() < df.height())
+    }
+}
+
+impl PostProcessor for Pivoter {
+    fn process(&self, df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!(
+            "Pivoting '{}' by '{}' over {:?} ({:?})",
+            self.values, self.columns, self.index, self.agg
+        );
+
+        self.validate_schema(&df.schema())?;
+
+        if self.agg.is_none() && self.has_collisions(&df)? {
+            return Err(PostProcessError::ConfigurationError(format!(
+                "Several rows share the same {:?} and '{}' values; set `agg` to combine them",
+                self.index, self.columns
+            )));
+        }
+
+        // Pivot aggregations see each cell's values as the unnamed column
+        let agg_expr = self.agg.map(|op| op.expr(col("")));
+        Ok(polars::lazy::frame::pivot::pivot_stable(
+            &df,
+            [self.columns.as_str()],
+            Some(self.index.iter().map(String::as_str)),
+            Some([self.values.as_str()]),
+            false,
+            agg_expr,
+            None,
+        )?)
+    }
+
+    fn name(&self) -> &str {
+        "Pivoter"
+    }
+
+    fn description(&self) -> &str {
+        "Spreads a categorical column into one value column per category"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        if self.index.is_empty() {
+            return Err(PostProcessError::ConfigurationError(
+                "pivot needs at least one index column".to_string(),
+            ));
+        }
+        for column in self.index.iter().chain([&self.columns, &self.values]) {
+            if !schema.contains(column) {
+                return Err(PostProcessError::ColumnNotFound(column.clone()));
+            }
+        }
+        if self.columns == self.values {
+            return Err(PostProcessError::ConfigurationError(format!(
+                "Column '{}' cannot be both the pivot columns and values",
+                self.columns
+            )));
+        }
+        if let Some(column) = [&self.columns, &self.values]
+            .into_iter()
+            .find(|column| self.index.contains(*column))
+        {
+            return Err(PostProcessError::ConfigurationError(format!(
+                "Column '{}' cannot be both an index and a pivoted column",
+                column
+            )));
+        }
+        Ok(())
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        self.validate_schema(input_schema)?;
+        Err(PostProcessError::ConfigurationError(format!(
+            "The columns created by a pivot depend on the values of '{}', so the output schema is only known after processing",
+            self.columns
+        )))
+    }
+}
+
+/// Integer types a [`ColumnPacker`] packs a float column into
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PackedType {
+    Int8,
+    Int16,
+    Int32,
+    UInt8,
+    UInt16,
+    UInt32,
+}
+
+impl PackedType {
+    pub const ALL: [PackedType; 6] = [
+        PackedType::Int8,
+        PackedType::Int16,
+        PackedType::Int32,
+        PackedType::UInt8,
+        PackedType::UInt16,
+        PackedType::UInt32,
+    ];
+
+    /// Name of the type in configuration files
+    pub fn name(&self) -> &'static str {
+        match self {
+            PackedType::Int8 => "int8",
+            PackedType::Int16 => "int16",
+            PackedType::Int32 => "int32",
+            PackedType::UInt8 => "uint8",
+            PackedType::UInt16 => "uint16",
+            PackedType::UInt32 => "uint32",
+        }
+    }
+
+    pub fn data_type(&self) -> DataType {
+        match self {
+            PackedType::Int8 => DataType::Int8,
+            PackedType::Int16 => DataType::Int16,
+            PackedType::Int32 => DataType::Int32,
+            PackedType::UInt8 => DataType::UInt8,
+            PackedType::UInt16 => DataType::UInt16,
+            PackedType::UInt32 => DataType::UInt32,
+        }
+    }
+
+    /// Smallest and largest values of the type
+    pub fn range(&self) -> (f64, f64) {
+        match self {
+            PackedType::Int8 => (i8::MIN as f64, i8::MAX as f64),
+            PackedType::Int16 => (i16::MIN as f64, i16::MAX as f64),
+            PackedType::Int32 => (i32::MIN as f64, i32::MAX as f64),
+            PackedType::UInt8 => (0.0, u8::MAX as f64),
+            PackedType::UInt16 => (0.0, u16::MAX as f64),
+            PackedType::UInt32 => (0.0, u32::MAX as f64),
+        }
+    }
+}
+
+impl std::str::FromStr for PackedType {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PackedType::ALL
+            .into_iter()
+            .find(|dtype| dtype.name() == s)
+            .ok_or_else(|| {
+                format!(
+                    "Unknown packed type '{}' (known: {})",
+                    s,
+                    PackedType::ALL
+                        .iter()
+                        .map(|dtype| dtype.name())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            })
+    }
+}
+
+/// Packs a float column into an integer column, the inverse of NetCDF packing.
+///
+/// Each value is stored as `round((value - add_offset) / scale_factor)` in
+/// `dtype`, so readers recover it within half a `scale_factor` as
+/// `packed * scale_factor + add_offset` (see [`ColumnPacker::unpack`]). NaN and
+/// null values become null; a value outside the range of `dtype` once packed is
+/// an error. The parameters are stored in the Parquet footer under
+/// [`ColumnPacker::metadata_key`] (see [`packing_metadata`]).
+pub struct ColumnPacker {
+    column: String,
+    scale_factor: f64,
+    add_offset: f64,
+    dtype: PackedType,
+}
+
+impl ColumnPacker {
+    pub fn new(
+        column: String,
+        scale_factor: f64,
+        add_offset: f64,
+        dtype: PackedType,
+    ) -> PostProcessResult<Self> {
+        if !scale_factor.is_finite() || scale_factor == 0.0 || !add_offset.is_finite() {
+            return Err(PostProcessError::ConfigurationError(format!(
+                "Cannot pack column '{}' with scale_factor {} and add_offset {}: both must be finite and the scale nonzero",
+                column, scale_factor, add_offset
+            )));
+        }
+        Ok(Self {
+            column,
+            scale_factor,
+            add_offset,
+            dtype,
+        })
+    }
+
+    /// Key of the Parquet footer entry describing the packing of `column`
+    pub fn metadata_key(column: &str) -> String {
+        format!("nc2parquet:packing:{}", column)
+    }
+
+    /// Footer entry describing this packing: a JSON object with the
+    /// `scale_factor`, `add_offset` and `dtype` of the column
+    pub fn metadata(&self) -> (String, String) {
+        let value = serde_json::json!({
+            "scale_factor": self.scale_factor,
+            "add_offset": self.add_offset,
+            "dtype": self.dtype.name(),
+        });
+        (Self::metadata_key(&self.column), value.to_string())
+    }
+
+    /// Recovers the float values of a column packed by this packer
+    pub fn unpack(&self, packed: &Column) -> PostProcessResult<Series> {
+        let values = packed.cast(&DataType::Float64)?;
+        Ok(values
+            .f64()?
+            .apply_values(|value| value * self.scale_factor + self.add_offset)
+            .with_name(packed.name().clone())
+            .into_series())
+    }
+}
+
+impl PostProcessor for ColumnPacker {
+    fn process(&self, mut df: DataFrame) -> PostProcessResult<DataFrame> {
+        debug!(
+            "Packing column '{}' into {} (scale_factor {}, add_offset {})",
+            self.column,
+            self.dtype.name(),
+            self.scale_factor,
+            self.add_offset
+        );
+
+        self.validate_schema(&df.schema())?;
+
+        let values = df.column(&self.column)?.cast(&DataType::Float64)?;
+        let quantized: Vec<Option<f64>> = values
+            .f64()?
+            .into_iter()
+            .map(|value| {
+                value
+                    .filter(|value| !value.is_nan())
+                    .map(|value| ((value - self.add_offset) / self.scale_factor).round())
+            })
+            .collect();
+
+        let (min, max) = self.dtype.range();
+        let out_of_range = quantized
+            .iter()
+            .flatten()
+            .filter(|packed| **packed < min || **packed > max)
+            .count();
+        if out_of_range > 0 {
+            return Err(PostProcessError::ConversionError(format!(
+                "{} value(s) of column '{}' fall outside the {} range once packed with scale_factor {} and add_offset {}",
+                out_of_range,
+                self.column,
+                self.dtype.name(),
+                self.scale_factor,
+                self.add_offset
+            )));
+        }
+
+        let packed =
+            Series::new(self.column.as_str().into(), quantized).cast(&self.dtype.data_type())?;
+        df.with_column(packed)?;
+        Ok(df)
+    }
+
+    fn name(&self) -> &str {
+        "ColumnPacker"
+    }
+
+    fn description(&self) -> &str {
+        "Packs a float column into integers with a scale factor and offset"
+    }
+
+    fn validate_schema(&self, schema: &Schema) -> PostProcessResult<()> {
+        match schema.get(&self.column) {
+            Some(dtype) if dtype.is_primitive_numeric() => Ok(()),
+            Some(other) => Err(PostProcessError::ConversionError(format!(
+                "Cannot pack column '{}' of type {}",
+                self.column, other
+            ))),
+            None => Err(PostProcessError::ColumnNotFound(self.column.clone())),
+        }
+    }
+
+    fn output_schema(&self, input_schema: &Schema) -> PostProcessResult<Schema> {
+        self.validate_schema(input_schema)?;
+        let mut new_schema = input_schema.clone();
+        new_schema.with_column(self.column.clone().into(), self.dtype.data_type());
+        Ok(new_schema)
+    }
+}
+
+/// Footer metadata entries of the columns packed by `configs` (see
+/// [`ColumnPacker::metadata`]); invalid packings are skipped, as they fail when
+/// the pipeline is built.
+pub fn packing_metadata(configs: &[ProcessorConfig]) -> Vec<(String, String)> {
+    configs
+        .iter()
+        .filter_map(|config| match config {
+            ProcessorConfig::Pack {
+                column,
+                scale_factor,
+                add_offset,
+                dtype,
+            } => ColumnPacker::new(column.clone(), *scale_factor, *add_offset, *dtype)
+                .ok()
+                .map(|packer| packer.metadata()),
+            _ => None,
+        })
+        .collect()
+}
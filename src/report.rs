@@ -0,0 +1,107 @@
+//! # Run Report Module
+//!
+//! This module builds the machine-readable record of a conversion written by
+//! `nc2parquet convert --report <path>`: the input and output paths, the extracted
+//! variables, the applied filters and post-processors, and the size of the result.
+//! Reports are written through the storage abstraction, so they can be stored on
+//! S3 next to the output.
+
+use crate::input::{FilterConfig, JobConfig};
+use crate::postprocess::ProcessorConfig;
+use crate::storage::{StorageBackend, StorageFactory};
+use anyhow::{Context, Result};
+use log::debug;
+use polars::prelude::*;
+use serde::Serialize;
+use std::io::Cursor;
+use std::time::Duration;
+
+/// Record of a single conversion job
+#[derive(Serialize)]
+pub struct RunReport {
+    pub input: String,
+    pub output: String,
+    pub variables: Vec<String>,
+    pub filters: Vec<FilterConfig>,
+    pub postprocessors: Vec<ProcessorConfig>,
+    /// Number of rows in the output file
+    pub rows: usize,
+    /// Size of the output file in bytes
+    pub output_bytes: u64,
+    /// Wall-clock processing time, excluding the time spent building the report
+    pub elapsed_seconds: f64,
+    /// Whether the write was skipped because the output was unchanged
+    pub skipped_unchanged: bool,
+}
+
+impl RunReport {
+    /// Builds the report of a finished job from its configuration and output statistics
+    pub fn new(config: &JobConfig, stats: OutputStats, elapsed: Duration) -> Self {
+        RunReport {
+            input: config.nc_key.clone(),
+            output: config.parquet_key.clone(),
+            variables: vec![config.variable_name.clone()],
+            filters: config
+                .filters
+                .iter()
+                .filter(|filter| filter.is_enabled())
+                .cloned()
+                .collect(),
+            postprocessors: config
+                .postprocessing
+                .as_ref()
+                .map(|pipeline| pipeline.processors.clone())
+                .unwrap_or_default(),
+            rows: stats.rows,
+            output_bytes: stats.bytes,
+            elapsed_seconds: elapsed.as_secs_f64(),
+            skipped_unchanged: false,
+        }
+    }
+
+    /// Marks the job as skipped because its output was unchanged
+    pub fn with_skipped_unchanged(mut self, skipped_unchanged: bool) -> Self {
+        self.skipped_unchanged = skipped_unchanged;
+        self
+    }
+
+    /// Serializes the report as pretty-printed JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).context("Failed to serialize run report")
+    }
+
+    /// Writes the report as JSON to a local or S3 path
+    pub async fn write(&self, path: &str) -> Result<()> {
+        let json = self.to_json()?;
+        let storage = StorageFactory::from_path(path).await?;
+        storage
+            .write(path, json.as_bytes())
+            .await
+            .with_context(|| format!("Failed to write run report: {}", path))?;
+        debug!("Wrote run report: {}", path);
+        Ok(())
+    }
+}
+
+/// Row count and byte size of a written Parquet file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutputStats {
+    pub rows: usize,
+    pub bytes: u64,
+}
+
+/// Reads the row count (from the Parquet footer) and byte size of an output file
+pub async fn read_output_stats(parquet_path: &str) -> Result<OutputStats> {
+    let storage = StorageFactory::from_path(parquet_path).await?;
+    let data = storage
+        .read(parquet_path)
+        .await
+        .with_context(|| format!("Failed to read Parquet file: {}", parquet_path))?;
+    let bytes = data.len() as u64;
+
+    let rows = ParquetReader::new(Cursor::new(data))
+        .num_rows()
+        .with_context(|| format!("Failed to read Parquet metadata: {}", parquet_path))?;
+
+    Ok(OutputStats { rows, bytes })
+}
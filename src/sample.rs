@@ -0,0 +1,254 @@
+//! # Row Sample Module
+//!
+//! This module builds the first rows a conversion would write, for a quick look
+//! at the data without producing a Parquet file.
+//!
+//! The rows go through the job's filters, post-processing and row limit exactly
+//! as in a conversion. When every post-processor is row-wise, the data is
+//! extracted in small chunks and extraction stops as soon as the sample is
+//! complete, so sampling stays fast however large the variable is.
+
+use crate::error::{Nc2ParquetError, Nc2ParquetResult};
+use crate::extract::extract_data_to_dataframe_chunked;
+use crate::input::JobConfig;
+use crate::metadata::value_column_name;
+use crate::postprocess::ProcessorConfig;
+use crate::{
+    build_job_output, drop_coordinate_columns, input_path_async, job_filters, job_pipeline,
+    job_variable, local_config, local_input_path, open_netcdf,
+};
+use anyhow::{Context, Result};
+use log::debug;
+use polars::prelude::*;
+use serde::ser::{Serialize, SerializeMap, Serializer};
+use serde_json::{Number, Value};
+
+/// Builds the first `rows` rows a job would write, without writing anything.
+///
+/// The rows are those of [`build_dataframe`](crate::build_dataframe), including the job's own `limit`.
+/// When every post-processor is row-wise (see [`ProcessorConfig::is_row_wise`]),
+/// the data is extracted in chunks of `rows` and extraction stops once enough
+/// rows are produced, so sampling a large variable stays fast. Otherwise the
+/// processors need every row and the whole selection is extracted first.
+///
+/// `nc_key` must be a local path; use `sample_dataframe_async` for S3 inputs.
+///
+/// # Errors
+///
+/// This function will return an error if:
+/// - The NetCDF file cannot be opened
+/// - The specified variable is not found in the NetCDF file
+/// - Any filter or post-processing step fails
+pub fn sample_dataframe(config: &JobConfig, rows: usize) -> Nc2ParquetResult<DataFrame> {
+    let config: &JobConfig = &local_config(config)?;
+    let (nc_path, temp_file) = local_input_path(&config.nc_key)?;
+    let df = sample_job_dataframe(nc_path, config, rows)?;
+
+    // Keep the temporary file alive until the NetCDF file is closed, then remove it
+    drop(temp_file);
+
+    Ok(df)
+}
+
+/// Samples a job's rows like `sample_dataframe`, downloading S3 input to a
+/// temporary file first.
+pub async fn sample_dataframe_async(
+    config: &JobConfig,
+    rows: usize,
+) -> Nc2ParquetResult<DataFrame> {
+    let config: &JobConfig = &local_config(config)?;
+    let (nc_path, temp_file) = input_path_async(config).await?;
+    let df = sample_job_dataframe(nc_path, config, rows)?;
+
+    // Keep the temporary file alive until the NetCDF file is closed, then remove it
+    drop(temp_file);
+
+    Ok(df)
+}
+
+/// Returned by the chunk callback of [`sample_job_dataframe`] to stop the
+/// extraction once the sample is complete
+#[derive(Debug)]
+struct SampleComplete;
+
+impl std::fmt::Display for SampleComplete {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sample complete")
+    }
+}
+
+impl std::error::Error for SampleComplete {}
+
+/// Opens the NetCDF file at `nc_path` and builds the first `rows` rows of the
+/// job's DataFrame, stopping the extraction early when the processors allow it
+fn sample_job_dataframe(
+    nc_path: impl AsRef<std::path::Path>,
+    config: &JobConfig,
+    rows: usize,
+) -> Nc2ParquetResult<DataFrame> {
+    let rows = config.limit.map_or(rows, |limit| limit.min(rows));
+    let row_wise = config
+        .postprocessing
+        .as_ref()
+        .is_none_or(|postprocess_config| {
+            postprocess_config
+                .processors
+                .iter()
+                .all(ProcessorConfig::is_row_wise)
+        });
+    if !row_wise {
+        debug!("Post-processing needs every row, extracting the whole selection");
+        let config = JobConfig {
+            limit: Some(rows),
+            write_cell_index: false,
+            write_manifest: false,
+            ..config.clone()
+        };
+        return Ok(build_job_output(nc_path, &config, &mut |_| {})?.df);
+    }
+
+    let file = open_netcdf(nc_path, &config.nc_key)?;
+    let var = job_variable(&file, config)?;
+    let mut pipeline = job_pipeline(&var, config)?;
+    let filters = job_filters(config)?;
+
+    let mut sample: Option<DataFrame> = None;
+    let result = extract_data_to_dataframe_chunked(
+        &file,
+        &var,
+        &value_column_name(&var, config.rename_from_attributes),
+        &filters,
+        &config.extract_options(),
+        rows.max(1),
+        |chunk| {
+            let mut chunk = match pipeline.as_mut() {
+                Some(pipeline) => pipeline.execute(chunk)?,
+                None => chunk,
+            };
+            if config.drop_coordinates {
+                chunk = drop_coordinate_columns(chunk, &var, config)?;
+            }
+            let sample = match sample.as_mut() {
+                Some(sample) => {
+                    sample.vstack_mut(&chunk)?;
+                    sample
+                }
+                None => sample.insert(chunk),
+            };
+            if sample.height() >= rows {
+                return Err(SampleComplete.into());
+            }
+            Ok(())
+        },
+    );
+    if let Err(e) = result
+        && !e.is::<SampleComplete>()
+    {
+        return Err(Nc2ParquetError::extraction(e));
+    }
+    file.close()?;
+
+    // The chunk callback is called at least once, even when no rows match
+    let sample = sample.expect("the extraction produced no chunk");
+    Ok(sample.head(Some(rows)))
+}
+
+/// One sampled row, serialized as a map from column name to value that keeps
+/// the DataFrame's column order
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleRow(pub Vec<(String, Value)>);
+
+impl Serialize for SampleRow {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (column, value) in &self.0 {
+            map.serialize_entry(column, value)?;
+        }
+        map.end()
+    }
+}
+
+/// The sampled rows with their values as JSON. Missing and NaN values become
+/// `null`, datetimes their formatted value.
+pub fn sample_rows(df: &DataFrame) -> Result<Vec<SampleRow>> {
+    (0..df.height())
+        .map(|row| {
+            let values = df
+                .get_columns()
+                .iter()
+                .map(|column| Ok((column.name().to_string(), json_value(column.get(row)?))))
+                .collect::<Result<_>>()?;
+            Ok(SampleRow(values))
+        })
+        .collect()
+}
+
+fn json_value(value: AnyValue) -> Value {
+    match value {
+        AnyValue::Null => Value::Null,
+        AnyValue::Boolean(b) => Value::Bool(b),
+        v if v.is_signed_integer() => v.extract::<i64>().into(),
+        v if v.is_unsigned_integer() => v.extract::<u64>().into(),
+        v if v.is_float() => v
+            .extract::<f64>()
+            .and_then(Number::from_f64)
+            .map_or(Value::Null, Value::Number),
+        v => Value::String(display_value(&v)),
+    }
+}
+
+/// A value as printed in CSV output: strings unquoted, nulls empty
+fn display_value(value: &AnyValue) -> String {
+    match value.get_str() {
+        Some(s) => s.to_string(),
+        None if value.is_null() => String::new(),
+        None => value.to_string(),
+    }
+}
+
+/// Quotes a CSV field when it contains a separator, a quote or a line break
+fn csv_field(field: String) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field
+    }
+}
+
+/// Print sampled rows in human-readable format, as a table
+pub fn print_sample_human(df: &DataFrame) {
+    println!("{}", df);
+}
+
+/// Print sampled rows in JSON format, as an array of objects
+pub fn print_sample_json(df: &DataFrame) -> Result<()> {
+    println!("{}", serde_json::to_string_pretty(&sample_rows(df)?)?);
+    Ok(())
+}
+
+/// Print sampled rows in YAML format, as a list of mappings
+pub fn print_sample_yaml(df: &DataFrame) -> Result<()> {
+    let yaml = serde_yaml::to_string(&sample_rows(df)?)
+        .context("Failed to serialize sampled rows to YAML")?;
+    println!("{}", yaml);
+    Ok(())
+}
+
+/// Print sampled rows in CSV format, with a header row
+pub fn print_sample_csv(df: &DataFrame) -> Result<()> {
+    let header: Vec<String> = df
+        .get_column_names()
+        .iter()
+        .map(|name| csv_field(name.to_string()))
+        .collect();
+    println!("{}", header.join(","));
+    for row in 0..df.height() {
+        let fields = df
+            .get_columns()
+            .iter()
+            .map(|column| Ok(csv_field(display_value(&column.get(row)?))))
+            .collect::<Result<Vec<_>>>()?;
+        println!("{}", fields.join(","));
+    }
+    Ok(())
+}
@@ -0,0 +1,309 @@
+//! # Parquet Schema Check Module
+//!
+//! This module compares the schema an nc2parquet job configuration would produce
+//! with the schema of an existing Parquet file, reporting missing, unexpected,
+//! and mistyped columns.
+
+use crate::cftime::read_time_references;
+use crate::extract::{
+    coordinate_column_order, extraction_schema, index_column_name, row_dimensions,
+};
+use crate::info::{detect_main_variable, resolve_variable};
+use crate::input::JobConfig;
+use crate::metadata::value_column_name;
+use crate::storage::{StorageBackend, StorageFactory};
+use crate::{coordinate_column_names, job_pipeline};
+use anyhow::{Context, Result, bail};
+use log::debug;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::io::Cursor;
+
+/// A single difference between the expected and actual schema
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SchemaMismatch {
+    /// Column expected by the configuration but absent from the Parquet file
+    MissingColumn { name: String, expected: String },
+    /// Column present in the Parquet file but not produced by the configuration
+    UnexpectedColumn { name: String, actual: String },
+    /// Column present in both with different data types
+    TypeMismatch {
+        name: String,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl std::fmt::Display for SchemaMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SchemaMismatch::MissingColumn { name, expected } => {
+                write!(f, "- {}: {} (missing from parquet)", name, expected)
+            }
+            SchemaMismatch::UnexpectedColumn { name, actual } => {
+                write!(f, "+ {}: {} (not produced by config)", name, actual)
+            }
+            SchemaMismatch::TypeMismatch {
+                name,
+                expected,
+                actual,
+            } => write!(f, "~ {}: expected {}, found {}", name, expected, actual),
+        }
+    }
+}
+
+/// Result of comparing an expected schema with a Parquet file's schema
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemaCheckReport {
+    pub parquet_path: String,
+    pub expected: Vec<(String, String)>,
+    pub actual: Vec<(String, String)>,
+    pub mismatches: Vec<SchemaMismatch>,
+}
+
+impl SchemaCheckReport {
+    /// Whether the Parquet schema matches the expected schema exactly
+    pub fn is_match(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Computes the schema a job configuration would write.
+///
+/// The schema of the extracted DataFrame is derived from the NetCDF variable and
+/// then passed through the `output_schema` chain of the job's post-processing
+/// pipeline (see [`job_pipeline`]).
+pub async fn expected_schema(config: &JobConfig) -> Result<Schema> {
+    // Handle S3 paths - download to temporary file first
+    let (temp_file, local_path) = if config.nc_key.starts_with("s3://") {
+        let storage = StorageFactory::from_path(&config.nc_key).await?;
+        let data = storage
+            .read(&config.nc_key)
+            .await
+            .context("Failed to read S3 file for schema check")?;
+
+        let temp_file = crate::storage::temp_file().context("Failed to create temporary file")?;
+        let temp_path = temp_file.path().to_path_buf();
+        tokio::fs::write(&temp_path, data)
+            .await
+            .context("Failed to write temporary file")?;
+
+        (Some(temp_file), temp_path.to_string_lossy().to_string())
+    } else {
+        (
+            None,
+            StorageFactory::local_path(&config.nc_key)?.into_owned(),
+        )
+    };
+
+    let file = netcdf::open(&local_path)
+        .with_context(|| format!("Failed to open NetCDF file: {}", config.nc_key))?;
+    let variable_name = if config.variable_name.is_empty() {
+        detect_main_variable(&file)?
+    } else {
+        config.variable_name.clone()
+    };
+    let var = resolve_variable(&file, &variable_name)?;
+
+    let column_name = value_column_name(&var, config.rename_from_attributes);
+    let mut schema = extraction_schema(
+        &var,
+        &column_name,
+        config.variable_overrides.get(&variable_name),
+    )
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let dimensions: Vec<String> = row_dimensions(&var).iter().map(|dim| dim.name()).collect();
+    let column_order = coordinate_column_order(&dimensions, &config.extract_options())
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    for (i, &position) in column_order.iter().enumerate() {
+        let dimension = &dimensions[position];
+        if let Some(dtype) = schema.shift_remove(dimension) {
+            schema.insert_at_index(i, dimension.as_str().into(), dtype)?;
+        }
+    }
+    if config.include_indices {
+        for (i, &position) in column_order.iter().enumerate() {
+            schema.insert_at_index(
+                dimensions.len() + i,
+                index_column_name(&dimensions[position]).into(),
+                DataType::UInt64,
+            )?;
+        }
+    }
+    if config.decode_times {
+        let time_references =
+            read_time_references(&file, &dimensions).map_err(|e| anyhow::anyhow!("{}", e))?;
+        for dimension in time_references.keys() {
+            schema.with_column(
+                dimension.as_str().into(),
+                DataType::Datetime(TimeUnit::Milliseconds, None),
+            );
+        }
+    }
+    for (dimension, new_name) in &config.coordinate_renames {
+        if schema.rename(dimension, new_name.as_str().into()).is_none() {
+            bail!(
+                "Coordinate rename references unknown dimension '{}'",
+                dimension
+            );
+        }
+        let index_name = index_column_name(dimension);
+        schema.rename(&index_name, index_column_name(new_name).into());
+    }
+    let pipeline = job_pipeline(&var, config).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let dropped_coordinates = if config.drop_coordinates {
+        coordinate_column_names(&var, config)
+    } else {
+        Vec::new()
+    };
+    drop(var);
+    file.close().context("Failed to close NetCDF file")?;
+    drop(temp_file);
+
+    if let Some(pipeline) = pipeline {
+        schema = pipeline
+            .output_schema(&schema)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+    for name in &dropped_coordinates {
+        schema.shift_remove(name.as_str());
+        schema.shift_remove(index_column_name(name).as_str());
+    }
+
+    debug!("Expected schema for {}: {:?}", config.parquet_key, schema);
+    Ok(schema)
+}
+
+/// Reads the schema of a Parquet file (local or S3) without loading its data
+pub async fn read_parquet_schema(parquet_path: &str) -> Result<Schema> {
+    let storage = StorageFactory::from_path(parquet_path).await?;
+    let data = storage
+        .read(parquet_path)
+        .await
+        .with_context(|| format!("Failed to read Parquet file: {}", parquet_path))?;
+
+    let arrow_schema = ParquetReader::new(Cursor::new(data))
+        .schema()
+        .with_context(|| format!("Failed to read Parquet schema: {}", parquet_path))?;
+
+    Ok(Schema::from_arrow_schema(&arrow_schema))
+}
+
+/// Compares two schemas by column name and data type.
+///
+/// Column order is not significant.
+pub fn compare_schemas(expected: &Schema, actual: &Schema) -> Vec<SchemaMismatch> {
+    let mut mismatches = Vec::new();
+
+    for (name, expected_dtype) in expected.iter() {
+        match actual.get(name) {
+            None => mismatches.push(SchemaMismatch::MissingColumn {
+                name: name.to_string(),
+                expected: expected_dtype.to_string(),
+            }),
+            Some(actual_dtype) if actual_dtype != expected_dtype => {
+                mismatches.push(SchemaMismatch::TypeMismatch {
+                    name: name.to_string(),
+                    expected: expected_dtype.to_string(),
+                    actual: actual_dtype.to_string(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+
+    for (name, actual_dtype) in actual.iter() {
+        if !expected.contains(name) {
+            mismatches.push(SchemaMismatch::UnexpectedColumn {
+                name: name.to_string(),
+                actual: actual_dtype.to_string(),
+            });
+        }
+    }
+
+    mismatches
+}
+
+/// Checks that a Parquet file matches the schema a job configuration would produce
+pub async fn check_parquet_schema(
+    config: &JobConfig,
+    parquet_path: &str,
+) -> Result<SchemaCheckReport> {
+    let expected = expected_schema(config).await?;
+    let actual = read_parquet_schema(parquet_path).await?;
+    let mismatches = compare_schemas(&expected, &actual);
+
+    Ok(SchemaCheckReport {
+        parquet_path: parquet_path.to_string(),
+        expected: schema_fields(&expected),
+        actual: schema_fields(&actual),
+        mismatches,
+    })
+}
+
+fn schema_fields(schema: &Schema) -> Vec<(String, String)> {
+    schema
+        .iter()
+        .map(|(name, dtype)| (name.to_string(), dtype.to_string()))
+        .collect()
+}
+
+/// Print a schema check report in human-readable format
+pub fn print_schema_report_human(report: &SchemaCheckReport) {
+    println!("Schema Check: {}", report.parquet_path);
+    println!("  Expected columns: {}", report.expected.len());
+    for (name, dtype) in &report.expected {
+        println!("    {} ({})", name, dtype);
+    }
+    if report.is_match() {
+        println!("  ✓ Parquet schema matches the configuration");
+    } else {
+        println!("  ✗ {} mismatch(es):", report.mismatches.len());
+        for mismatch in &report.mismatches {
+            println!("    {}", mismatch);
+        }
+    }
+}
+
+/// Print a schema check report in JSON format
+pub fn print_schema_report_json(report: &SchemaCheckReport) -> Result<()> {
+    let json = serde_json::json!({
+        "parquet_path": report.parquet_path,
+        "matches": report.is_match(),
+        "expected": report.expected,
+        "actual": report.actual,
+        "mismatches": report.mismatches,
+    });
+    println!("{}", serde_json::to_string_pretty(&json)?);
+    Ok(())
+}
+
+/// Print a schema check report in YAML format
+pub fn print_schema_report_yaml(report: &SchemaCheckReport) -> Result<()> {
+    let yaml =
+        serde_yaml::to_string(report).context("Failed to serialize schema report to YAML")?;
+    println!("{}", yaml);
+    Ok(())
+}
+
+/// Print a schema check report in CSV format (mismatches only)
+pub fn print_schema_report_csv(report: &SchemaCheckReport) -> Result<()> {
+    println!("column,status,expected,actual");
+    for mismatch in &report.mismatches {
+        match mismatch {
+            SchemaMismatch::MissingColumn { name, expected } => {
+                println!("{},missing,{},", name, expected)
+            }
+            SchemaMismatch::UnexpectedColumn { name, actual } => {
+                println!("{},unexpected,,{}", name, actual)
+            }
+            SchemaMismatch::TypeMismatch {
+                name,
+                expected,
+                actual,
+            } => println!("{},type_mismatch,{},{}", name, expected, actual),
+        }
+    }
+    Ok(())
+}
@@ -11,11 +11,42 @@
 //! - **Async operations**: Full async support for all storage operations
 //! - **Error handling**: Comprehensive error types with detailed context
 //! - **Credential management**: AWS credentials from environment variables
+//! - **Concurrency limit**: All S3 operations share a global limit on in-flight requests
 //!
 //! ## Path Patterns
 //!
 //! - **S3 paths**: `s3://bucket-name/path/to/file.nc`
 //! - **Local paths**: `/absolute/path/to/file.nc` or `relative/path/to/file.nc`
+//! - **File URIs**: `file:///absolute/path/to/file.nc`, read and written as local paths
+//!
+//! ## S3 Concurrency
+//!
+//! Every [`S3Storage`] instance draws from one process-wide [`ConcurrencyLimiter`],
+//! so no more than [`DEFAULT_S3_CONCURRENCY`] S3 requests are in flight at once,
+//! however many storages are created. Call [`set_s3_concurrency`] before the first
+//! S3 operation to change the limit.
+//!
+//! ## AWS Region
+//!
+//! S3 clients resolve their region from the ambient AWS configuration
+//! (`AWS_REGION`, `~/.aws/config`, instance metadata, ...). Call [`set_aws_region`]
+//! before the first S3 operation to use an explicit region instead, e.g. in
+//! minimal containers without any AWS configuration.
+//!
+//! ## Temporary Files
+//!
+//! S3 inputs are downloaded, and compressed inputs decompressed, to temporary
+//! files created by [`temp_file`]. They go to the system temporary directory
+//! unless [`set_temp_dir`] names another one, e.g. a volume larger than a tmpfs.
+//!
+//! ## S3 Download Cache
+//!
+//! Call [`set_s3_cache_dir`] to keep a copy of every object read from S3 in a
+//! local directory. Each read still sends a `HeadObject` request, and the cached
+//! copy is used when it was downloaded at the object's current ETag; otherwise the
+//! object is downloaded again and replaces the copy of its previous version.
+//! Copies are written to a temporary file in the cache directory and renamed into
+//! place, so concurrent jobs sharing the directory never read a partial copy.
 //!
 //! ## Usage Example
 //!
@@ -37,11 +68,209 @@
 //! }
 //! ```
 
-use aws_config::BehaviorVersion;
+use aws_config::{BehaviorVersion, Region};
 use aws_sdk_s3::Client as S3Client;
-use std::path::Path;
+use log::warn;
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock};
+use tempfile::NamedTempFile;
 use thiserror::Error;
 use tokio::fs;
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// Default maximum number of concurrent S3 operations
+pub const DEFAULT_S3_CONCURRENCY: usize = 32;
+
+/// Process-wide limiter shared by every [`S3Storage`]
+static S3_LIMITER: OnceLock<ConcurrencyLimiter> = OnceLock::new();
+
+/// Sets the maximum number of concurrent S3 operations for the whole process.
+///
+/// The limit is fixed the first time an S3 storage is created, so this must be
+/// called before any S3 operation; a value of 0 is treated as 1. Returns `false`
+/// if the limit was already fixed.
+pub fn set_s3_concurrency(limit: usize) -> bool {
+    S3_LIMITER.set(ConcurrencyLimiter::new(limit)).is_ok()
+}
+
+/// Returns the limiter shared by all S3 operations
+pub fn s3_limiter() -> &'static ConcurrencyLimiter {
+    S3_LIMITER.get_or_init(|| ConcurrencyLimiter::new(DEFAULT_S3_CONCURRENCY))
+}
+
+/// Region of the S3 clients, when not resolved from the ambient AWS configuration
+static AWS_REGION: OnceLock<String> = OnceLock::new();
+
+/// Sets the AWS region used by every [`S3Storage`] created afterwards with
+/// [`S3Storage::new`]. Returns `false` if a region was already set.
+pub fn set_aws_region(region: impl Into<String>) -> bool {
+    AWS_REGION.set(region.into()).is_ok()
+}
+
+/// Returns the region set by [`set_aws_region`], if any
+pub fn aws_region() -> Option<&'static str> {
+    AWS_REGION.get().map(String::as_str)
+}
+
+/// Directory of the temporary input copies, when not the system's
+static TEMP_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Sets the directory in which [`temp_file`] creates temporary files for the
+/// whole process. Returns `false` if a directory was already set.
+pub fn set_temp_dir(dir: impl Into<PathBuf>) -> bool {
+    TEMP_DIR.set(dir.into()).is_ok()
+}
+
+/// Returns the directory set by [`set_temp_dir`], if any
+pub fn temp_dir() -> Option<&'static Path> {
+    TEMP_DIR.get().map(PathBuf::as_path)
+}
+
+/// Directory of the S3 download cache, when enabled
+static S3_CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+/// Enables the download cache in `dir` for every [`S3Storage`] created afterwards
+/// with [`S3Storage::from_config`] (or [`S3Storage::new`]). The directory is
+/// created when the first object is cached. Returns `false` if a directory was
+/// already set.
+pub fn set_s3_cache_dir(dir: impl Into<PathBuf>) -> bool {
+    S3_CACHE_DIR.set(dir.into()).is_ok()
+}
+
+/// Returns the directory set by [`set_s3_cache_dir`], if any
+pub fn s3_cache_dir() -> Option<&'static Path> {
+    S3_CACHE_DIR.get().map(PathBuf::as_path)
+}
+
+/// Returns the file name of the cached copy of `bucket`/`key` at version `etag`.
+///
+/// The name is the hash of the object's location, shared by all its versions,
+/// followed by a hash of the ETag, so names are safe whatever the key contains.
+pub fn s3_cache_entry_name(bucket: &str, key: &str, etag: &str) -> String {
+    let version = format!("{:x}", Sha256::digest(etag.as_bytes()));
+    format!("{}-{}", s3_cache_location(bucket, key), &version[..16])
+}
+
+/// Hash of an object's location, the prefix of the names of its cached copies
+fn s3_cache_location(bucket: &str, key: &str) -> String {
+    format!(
+        "{:x}",
+        Sha256::digest(format!("{}/{}", bucket, key).as_bytes())
+    )
+}
+
+/// Stores `data` in `dir` as the cached copy of `bucket`/`key` at version `etag`
+/// and removes the copies of its other versions.
+///
+/// The data is written to a temporary file in `dir` that is then renamed over the
+/// entry, so readers see either no copy or a complete one.
+pub async fn store_s3_cache_entry(
+    dir: &Path,
+    bucket: &str,
+    key: &str,
+    etag: &str,
+    data: &[u8],
+) -> StorageResult<()> {
+    fs::create_dir_all(dir).await?;
+    let temp = NamedTempFile::new_in(dir)?;
+    fs::write(temp.path(), data).await?;
+    let name = s3_cache_entry_name(bucket, key, etag);
+    temp.persist(dir.join(&name))
+        .map_err(|e| StorageError::Io(e.error))?;
+
+    let stale_prefix = format!("{}-", s3_cache_location(bucket, key));
+    let mut entries = fs::read_dir(dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if let Some(file_name) = entry.file_name().to_str()
+            && file_name.starts_with(&stale_prefix)
+            && file_name != name
+        {
+            // Another job may have removed it already
+            let _ = fs::remove_file(entry.path()).await;
+        }
+    }
+    Ok(())
+}
+
+/// Creates a temporary file in the directory set by [`set_temp_dir`], or in the
+/// system temporary directory. The file is removed when the handle is dropped.
+pub fn temp_file() -> std::io::Result<NamedTempFile> {
+    match temp_dir() {
+        Some(dir) => NamedTempFile::new_in(dir),
+        None => NamedTempFile::new(),
+    }
+}
+
+/// Checks that `dir` is an existing directory in which files can be created
+pub fn check_temp_dir(dir: &Path) -> StorageResult<()> {
+    if !dir.is_dir() {
+        return Err(StorageError::PathNotFound(format!(
+            "temporary directory {}",
+            dir.display()
+        )));
+    }
+    NamedTempFile::new_in(dir).map_err(|e| {
+        StorageError::PermissionDenied(format!(
+            "cannot create temporary files in {}: {}",
+            dir.display(),
+            e
+        ))
+    })?;
+    Ok(())
+}
+
+/// Checks that a local output can be created, without creating it.
+///
+/// Missing directories are created when the output is written, so the nearest
+/// existing directory on the output's path must accept new files.
+pub fn check_local_output(output_path: &str) -> StorageResult<()> {
+    let path = StorageFactory::local_path(output_path)?;
+    let mut dir = Path::new(path.as_ref()).parent().unwrap_or(Path::new(""));
+    while !dir.as_os_str().is_empty() && !dir.exists() {
+        dir = dir.parent().unwrap_or(Path::new(""));
+    }
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+
+    if !dir.is_dir() {
+        return Err(StorageError::InvalidPath(format!(
+            "{} is not a directory, so {} cannot be created",
+            dir.display(),
+            output_path
+        )));
+    }
+    NamedTempFile::new_in(dir).map_err(|e| {
+        StorageError::PermissionDenied(format!(
+            "cannot create {}, files cannot be created in {}: {}",
+            output_path,
+            dir.display(),
+            e
+        ))
+    })?;
+    Ok(())
+}
+
+/// Suffix of the empty object written next to an output by [`check_writable`]
+pub const WRITE_PROBE_SUFFIX: &str = ".write-check";
+
+/// Checks that `output_path` can be written by writing an empty object next to
+/// it (at `output_path` + [`WRITE_PROBE_SUFFIX`]) and deleting it again.
+///
+/// Unlike [`check_local_output`] this goes through `storage`, so it also checks
+/// S3 permissions, at the cost of a put and a delete request.
+pub async fn check_writable<S: StorageBackend + ?Sized>(
+    storage: &S,
+    output_path: &str,
+) -> StorageResult<()> {
+    let probe_path = format!("{}{}", output_path, WRITE_PROBE_SUFFIX);
+    storage.write(&probe_path, &[]).await?;
+    storage.delete(&probe_path).await
+}
 
 /// Errors that can occur during storage operations
 #[derive(Error, Debug)]
@@ -65,6 +294,12 @@ pub enum StorageError {
         #[from] aws_sdk_s3::error::SdkError<aws_sdk_s3::operation::head_object::HeadObjectError>,
     ),
 
+    #[error("AWS S3 DeleteObject error: {0}")]
+    S3DeleteObject(
+        #[from]
+        aws_sdk_s3::error::SdkError<aws_sdk_s3::operation::delete_object::DeleteObjectError>,
+    ),
+
     #[error("AWS ByteStream error: {0}")]
     ByteStream(String),
 
@@ -79,6 +314,9 @@ pub enum StorageError {
 
     #[error("Invalid path format: {0}")]
     InvalidPath(String),
+
+    #[error("Not a NetCDF file: {0}")]
+    NotNetCdf(String),
 }
 
 /// Result type for storage operations
@@ -126,6 +364,107 @@ pub trait StorageBackend: Send + Sync {
     /// # Errors
     /// Returns `StorageError` if the existence cannot be determined
     async fn exists(&self, path: &str) -> StorageResult<bool>;
+
+    /// Deletes a file; deleting a file that does not exist is not an error
+    ///
+    /// # Arguments
+    /// * `path` - The path of the file to delete
+    ///
+    /// # Errors
+    /// Returns `StorageError` if the file cannot be deleted
+    async fn delete(&self, path: &str) -> StorageResult<()>;
+
+    /// Reads `len` bytes of a file starting at byte `start`
+    ///
+    /// The result is shorter than `len` when the file ends first. The default
+    /// implementation reads the whole file and slices it; backends that can
+    /// fetch a byte range directly override it.
+    ///
+    /// # Errors
+    /// Returns `StorageError` if the file cannot be read
+    async fn read_range(&self, path: &str, start: u64, len: u64) -> StorageResult<Vec<u8>> {
+        let data = self.read(path).await?;
+        let start = usize::try_from(start).unwrap_or(usize::MAX).min(data.len());
+        let end = start
+            .saturating_add(usize::try_from(len).unwrap_or(usize::MAX))
+            .min(data.len());
+        Ok(data[start..end].to_vec())
+    }
+}
+
+/// Caps the number of storage operations running at the same time.
+///
+/// Clones share the same permits, so one limiter can bound operations issued
+/// from many storages and tasks.
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimiter {
+    semaphore: Arc<Semaphore>,
+    limit: usize,
+}
+
+impl ConcurrencyLimiter {
+    /// Creates a limiter allowing `limit` concurrent operations (0 is treated as 1)
+    pub fn new(limit: usize) -> Self {
+        let limit = limit.max(1);
+        Self {
+            semaphore: Arc::new(Semaphore::new(limit)),
+            limit,
+        }
+    }
+
+    /// Maximum number of concurrent operations
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+
+    /// Waits until an operation may start; it ends when the permit is dropped
+    pub async fn acquire(&self) -> SemaphorePermit<'_> {
+        self.semaphore
+            .acquire()
+            .await
+            .expect("concurrency limiter semaphore is never closed")
+    }
+}
+
+/// Storage backend wrapper that runs at most `limiter.limit()` operations at once
+#[derive(Debug, Clone)]
+pub struct LimitedStorage<B> {
+    inner: B,
+    limiter: ConcurrencyLimiter,
+}
+
+impl<B: StorageBackend> LimitedStorage<B> {
+    pub fn new(inner: B, limiter: ConcurrencyLimiter) -> Self {
+        Self { inner, limiter }
+    }
+}
+
+#[async_trait::async_trait]
+impl<B: StorageBackend> StorageBackend for LimitedStorage<B> {
+    async fn read(&self, path: &str) -> StorageResult<Vec<u8>> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.read(path).await
+    }
+
+    async fn write(&self, path: &str, data: &[u8]) -> StorageResult<()> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.write(path, data).await
+    }
+
+    async fn exists(&self, path: &str) -> StorageResult<bool> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.exists(path).await
+    }
+
+    async fn delete(&self, path: &str) -> StorageResult<()> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.delete(path).await
+    }
+
+    async fn read_range(&self, path: &str, start: u64, len: u64) -> StorageResult<Vec<u8>> {
+        let _permit = self.limiter.acquire().await;
+        self.inner.read_range(path, start, len).await
+    }
 }
 
 /// Local filesystem storage backend
@@ -137,6 +476,7 @@ pub struct LocalStorage;
 #[async_trait::async_trait]
 impl StorageBackend for LocalStorage {
     async fn read(&self, path: &str) -> StorageResult<Vec<u8>> {
+        let path = &*StorageFactory::local_path(path)?;
         match fs::read(path).await {
             Ok(data) => Ok(data),
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
@@ -150,6 +490,7 @@ impl StorageBackend for LocalStorage {
     }
 
     async fn write(&self, path: &str, data: &[u8]) -> StorageResult<()> {
+        let path = &*StorageFactory::local_path(path)?;
         // Create parent directories if they don't exist
         if let Some(parent) = Path::new(path).parent() {
             fs::create_dir_all(parent).await.map_err(StorageError::Io)?;
@@ -165,21 +506,39 @@ impl StorageBackend for LocalStorage {
     }
 
     async fn exists(&self, path: &str) -> StorageResult<bool> {
+        let path = &*StorageFactory::local_path(path)?;
         match fs::metadata(path).await {
             Ok(_) => Ok(true),
             Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(false),
             Err(e) => Err(StorageError::Io(e)),
         }
     }
+
+    async fn delete(&self, path: &str) -> StorageResult<()> {
+        let path = &*StorageFactory::local_path(path)?;
+        match fs::remove_file(path).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                Err(StorageError::PermissionDenied(path.to_string()))
+            }
+            Err(e) => Err(StorageError::Io(e)),
+        }
+    }
 }
 
 /// Amazon S3 storage backend
 ///
 /// Implements storage operations for S3 objects using the AWS SDK.
 /// Credentials are automatically loaded from environment variables or AWS configuration.
+/// Operations wait for a permit from the global [`s3_limiter`] before sending a request.
+/// With a cache directory, reads reuse the copies downloaded at the object's current
+/// ETag (see [`set_s3_cache_dir`]).
 #[derive(Debug, Clone)]
 pub struct S3Storage {
     client: S3Client,
+    limiter: ConcurrencyLimiter,
+    cache_dir: Option<PathBuf>,
 }
 
 impl S3Storage {
@@ -190,13 +549,27 @@ impl S3Storage {
     /// - AWS credentials file
     /// - IAM roles (when running on AWS infrastructure)
     ///
+    /// The region is the one set by [`set_aws_region`], falling back to the
+    /// ambient AWS configuration.
+    ///
     /// # Returns
     /// Returns a configured S3Storage instance
     pub async fn new() -> StorageResult<Self> {
-        let config = aws_config::defaults(BehaviorVersion::latest()).load().await;
-        let client = S3Client::new(&config);
+        Self::with_region(aws_region()).await
+    }
+
+    /// Creates a new S3Storage instance in `region`, or in the region of the
+    /// ambient AWS configuration when `None`
+    ///
+    /// Credentials are loaded as in [`S3Storage::new`].
+    pub async fn with_region(region: Option<&str>) -> StorageResult<Self> {
+        let mut loader = aws_config::defaults(BehaviorVersion::latest());
+        if let Some(region) = region {
+            loader = loader.region(Region::new(region.to_string()));
+        }
+        let config = loader.load().await;
 
-        Ok(S3Storage { client })
+        Ok(Self::from_config(&config))
     }
 
     /// Creates a new S3Storage instance with custom configuration
@@ -208,7 +581,117 @@ impl S3Storage {
     /// Returns a configured S3Storage instance
     pub fn from_config(config: &aws_config::SdkConfig) -> Self {
         let client = S3Client::new(config);
-        S3Storage { client }
+        S3Storage {
+            client,
+            limiter: s3_limiter().clone(),
+            cache_dir: s3_cache_dir().map(Path::to_path_buf),
+        }
+    }
+
+    /// Caches the objects read by this storage in `dir`, or disables the cache
+    /// when `None`, overriding [`set_s3_cache_dir`]
+    pub fn with_cache_dir(mut self, dir: Option<PathBuf>) -> Self {
+        self.cache_dir = dir;
+        self
+    }
+
+    /// Returns the directory of the download cache, if enabled
+    pub fn cache_dir(&self) -> Option<&Path> {
+        self.cache_dir.as_deref()
+    }
+
+    /// Downloads the whole object, returning its content and its ETag
+    async fn get_object(
+        &self,
+        path: &str,
+        bucket: &str,
+        key: &str,
+    ) -> StorageResult<(Vec<u8>, Option<String>)> {
+        // Held until the whole body has been received
+        let _permit = self.limiter.acquire().await;
+
+        let response = self
+            .client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(|e| match &e {
+                aws_sdk_s3::error::SdkError::ServiceError(service_err)
+                    if service_err.err().is_no_such_key() =>
+                {
+                    StorageError::PathNotFound(path.to_string())
+                }
+                _ => StorageError::S3GetObject(e),
+            })?;
+        let etag = response.e_tag().map(str::to_string);
+
+        let data = response
+            .body
+            .collect()
+            .await
+            .map_err(|e| StorageError::ByteStream(e.to_string()))?
+            .into_bytes()
+            .to_vec();
+
+        Ok((data, etag))
+    }
+
+    /// Returns the current ETag of the object, with a `HeadObject` request
+    async fn head_etag(
+        &self,
+        path: &str,
+        bucket: &str,
+        key: &str,
+    ) -> StorageResult<Option<String>> {
+        let _permit = self.limiter.acquire().await;
+
+        match self
+            .client
+            .head_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+        {
+            Ok(response) => Ok(response.e_tag().map(str::to_string)),
+            Err(aws_sdk_s3::error::SdkError::ServiceError(service_err))
+                if service_err.err().is_not_found() =>
+            {
+                Err(StorageError::PathNotFound(path.to_string()))
+            }
+            Err(e) => Err(StorageError::S3HeadObject(e)),
+        }
+    }
+
+    /// Reads the object from its copy in `dir` when that copy was downloaded at
+    /// the object's current ETag, and downloads and caches it otherwise
+    async fn read_cached(
+        &self,
+        dir: &Path,
+        path: &str,
+        bucket: &str,
+        key: &str,
+    ) -> StorageResult<Vec<u8>> {
+        if let Some(etag) = self.head_etag(path, bucket, key).await? {
+            let entry = dir.join(s3_cache_entry_name(bucket, key, &etag));
+            // A missing copy, e.g. one replaced by a concurrent job, is downloaded again
+            if let Ok(data) = fs::read(&entry).await {
+                return Ok(data);
+            }
+        }
+
+        // The ETag of the download itself names the copy, in case the object
+        // changed since the HeadObject request
+        let (data, etag) = self.get_object(path, bucket, key).await?;
+        if let Some(etag) = etag
+            && let Err(e) = store_s3_cache_entry(dir, bucket, key, &etag, &data).await
+        {
+            // The download succeeded, so a cache that cannot be written only costs time
+            warn!("Failed to cache {} in {}: {}", path, dir.display(), e);
+        }
+        Ok(data)
     }
 
     /// Parses an S3 path into bucket and key components
@@ -248,36 +731,15 @@ impl S3Storage {
 impl StorageBackend for S3Storage {
     async fn read(&self, path: &str) -> StorageResult<Vec<u8>> {
         let (bucket, key) = Self::parse_s3_path(path)?;
-
-        let response = self
-            .client
-            .get_object()
-            .bucket(bucket)
-            .key(key)
-            .send()
-            .await
-            .map_err(|e| match &e {
-                aws_sdk_s3::error::SdkError::ServiceError(service_err)
-                    if service_err.err().is_no_such_key() =>
-                {
-                    StorageError::PathNotFound(path.to_string())
-                }
-                _ => StorageError::S3GetObject(e),
-            })?;
-
-        let data = response
-            .body
-            .collect()
-            .await
-            .map_err(|e| StorageError::ByteStream(e.to_string()))?
-            .into_bytes()
-            .to_vec();
-
-        Ok(data)
+        match self.cache_dir {
+            Some(ref dir) => self.read_cached(dir, path, &bucket, &key).await,
+            None => Ok(self.get_object(path, &bucket, &key).await?.0),
+        }
     }
 
     async fn write(&self, path: &str, data: &[u8]) -> StorageResult<()> {
         let (bucket, key) = Self::parse_s3_path(path)?;
+        let _permit = self.limiter.acquire().await;
 
         self.client
             .put_object()
@@ -293,6 +755,7 @@ impl StorageBackend for S3Storage {
 
     async fn exists(&self, path: &str) -> StorageResult<bool> {
         let (bucket, key) = Self::parse_s3_path(path)?;
+        let _permit = self.limiter.acquire().await;
 
         match self
             .client
@@ -311,6 +774,57 @@ impl StorageBackend for S3Storage {
             Err(e) => Err(StorageError::S3HeadObject(e)),
         }
     }
+
+    async fn delete(&self, path: &str) -> StorageResult<()> {
+        let (bucket, key) = Self::parse_s3_path(path)?;
+        let _permit = self.limiter.acquire().await;
+
+        self.client
+            .delete_object()
+            .bucket(bucket)
+            .key(key)
+            .send()
+            .await
+            .map_err(StorageError::S3DeleteObject)?;
+
+        Ok(())
+    }
+
+    /// Fetches only the requested bytes, with an HTTP `Range` header
+    async fn read_range(&self, path: &str, start: u64, len: u64) -> StorageResult<Vec<u8>> {
+        let (bucket, key) = Self::parse_s3_path(path)?;
+        if len == 0 {
+            return Ok(Vec::new());
+        }
+        let _permit = self.limiter.acquire().await;
+
+        let response = self
+            .client
+            .get_object()
+            .bucket(bucket)
+            .key(key)
+            .range(format!("bytes={}-{}", start, start.saturating_add(len - 1)))
+            .send()
+            .await
+            .map_err(|e| match &e {
+                aws_sdk_s3::error::SdkError::ServiceError(service_err)
+                    if service_err.err().is_no_such_key() =>
+                {
+                    StorageError::PathNotFound(path.to_string())
+                }
+                _ => StorageError::S3GetObject(e),
+            })?;
+
+        let data = response
+            .body
+            .collect()
+            .await
+            .map_err(|e| StorageError::ByteStream(e.to_string()))?
+            .into_bytes()
+            .to_vec();
+
+        Ok(data)
+    }
 }
 
 /// Storage backend enumeration
@@ -344,6 +858,53 @@ impl StorageBackend for Storage {
             Storage::S3(storage) => storage.exists(path).await,
         }
     }
+
+    async fn delete(&self, path: &str) -> StorageResult<()> {
+        match self {
+            Storage::Local(storage) => storage.delete(path).await,
+            Storage::S3(storage) => storage.delete(path).await,
+        }
+    }
+
+    async fn read_range(&self, path: &str, start: u64, len: u64) -> StorageResult<Vec<u8>> {
+        match self {
+            Storage::Local(storage) => storage.read_range(path, start, len).await,
+            Storage::S3(storage) => storage.read_range(path, start, len).await,
+        }
+    }
+}
+
+/// Signature of an HDF5 file, the container of NetCDF-4 files
+const HDF5_SIGNATURE: &[u8; 8] = b"\x89HDF\r\n\x1a\n";
+
+/// Identifies the NetCDF format from the first bytes of a file.
+///
+/// Returns `None` when the bytes match neither a classic (`CDF` followed by
+/// version 1, 2 or 5) nor an HDF5 signature.
+pub fn netcdf_format_from_magic(bytes: &[u8]) -> Option<&'static str> {
+    match bytes {
+        [b'C', b'D', b'F', 1, ..] => Some("NetCDF classic"),
+        [b'C', b'D', b'F', 2, ..] => Some("NetCDF 64-bit offset"),
+        [b'C', b'D', b'F', 5, ..] => Some("NetCDF CDF-5"),
+        _ if bytes.starts_with(HDF5_SIGNATURE) => Some("NetCDF-4/HDF5"),
+        _ => None,
+    }
+}
+
+/// Checks that `path` is a NetCDF file by reading only its first 8 bytes.
+///
+/// On S3 this is a single ranged GET instead of a download of the whole object.
+/// Returns the detected format (see [`netcdf_format_from_magic`]).
+///
+/// # Errors
+/// Returns `StorageError::NotNetCdf` if the file does not start with a NetCDF
+/// signature, or any error from reading it
+pub async fn validate_netcdf_magic<S: StorageBackend + ?Sized>(
+    storage: &S,
+    path: &str,
+) -> StorageResult<&'static str> {
+    let magic = storage.read_range(path, 0, 8).await?;
+    netcdf_format_from_magic(&magic).ok_or_else(|| StorageError::NotNetCdf(path.to_string()))
 }
 
 /// Factory for creating storage backends based on path patterns
@@ -408,6 +969,76 @@ impl StorageFactory {
     pub fn is_local_path(path: &str) -> bool {
         !Self::is_s3_path(path)
     }
+
+    /// Determines if a path is a `file://` URI (or the short `file:/path` form)
+    pub fn is_file_uri(path: &str) -> bool {
+        path.get(..6)
+            .is_some_and(|scheme| scheme.eq_ignore_ascii_case("file:/"))
+    }
+
+    /// Returns the local filesystem path named by `path`
+    ///
+    /// `file:///data/in.nc`, `file://localhost/data/in.nc` and `file:/data/in.nc`
+    /// all name `/data/in.nc`; percent-escapes such as `%20` are decoded. Paths that
+    /// are not `file://` URIs are returned unchanged.
+    ///
+    /// # Errors
+    /// Returns `StorageError::InvalidPath` for a URI naming another host, or with an
+    /// invalid percent-escape
+    #[allow(clippy::result_large_err)]
+    pub fn local_path(path: &str) -> StorageResult<Cow<'_, str>> {
+        if !Self::is_file_uri(path) {
+            return Ok(Cow::Borrowed(path));
+        }
+
+        let rest = &path[5..]; // Remove "file:"
+        let local = match rest.strip_prefix("//") {
+            Some(authority) => {
+                let (host, local) =
+                    authority.split_at(authority.find('/').unwrap_or(authority.len()));
+                if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") {
+                    return Err(StorageError::InvalidPath(format!(
+                        "file URI names host '{}', not the local machine: {}",
+                        host, path
+                    )));
+                }
+                local
+            }
+            None => rest,
+        };
+        if local.is_empty() {
+            return Err(StorageError::InvalidPath(format!(
+                "file URI has no path: {}",
+                path
+            )));
+        }
+
+        let decoded = percent_decode(local).ok_or_else(|| {
+            StorageError::InvalidPath(format!("Invalid percent-escape in file URI: {}", path))
+        })?;
+        // `file:///C:/data/in.nc` names `C:/data/in.nc` on Windows
+        if cfg!(windows) && decoded.as_bytes().get(2) == Some(&b':') {
+            return Ok(Cow::Owned(decoded[1..].to_string()));
+        }
+        Ok(Cow::Owned(decoded))
+    }
+}
+
+/// Decodes the `%XX` escapes of a URI path, or returns `None` if an escape is
+/// malformed or the result is not UTF-8
+fn percent_decode(path: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(path.len());
+    let mut iter = path.bytes();
+    while let Some(byte) = iter.next() {
+        if byte == b'%' {
+            let hex = [iter.next()?, iter.next()?];
+            let hex = std::str::from_utf8(&hex).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+        } else {
+            bytes.push(byte);
+        }
+    }
+    String::from_utf8(bytes).ok()
 }
 
 #[cfg(test)]
@@ -449,6 +1080,116 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_local_storage_read_range() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = LocalStorage;
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("range.txt");
+        let file_path_str = file_path.to_str().unwrap();
+        storage.write(file_path_str, b"Hello, world!").await?;
+
+        assert_eq!(storage.read_range(file_path_str, 7, 5).await?, b"world");
+        // Ranges past the end are truncated
+        assert_eq!(storage.read_range(file_path_str, 7, 100).await?, b"world!");
+        assert!(storage.read_range(file_path_str, 100, 8).await?.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_validate_netcdf_magic() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = LocalStorage;
+        let data_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("examples/data");
+        let classic = data_dir.join("simple_xy.nc");
+        assert_eq!(
+            validate_netcdf_magic(&storage, classic.to_str().unwrap()).await?,
+            "NetCDF classic"
+        );
+
+        assert_eq!(
+            netcdf_format_from_magic(b"\x89HDF\r\n\x1a\n\0\0"),
+            Some("NetCDF-4/HDF5")
+        );
+        assert_eq!(netcdf_format_from_magic(b"CDF"), None);
+
+        let temp_dir = TempDir::new()?;
+        let text_path = temp_dir.path().join("not_netcdf.nc");
+        let text_path_str = text_path.to_str().unwrap();
+        storage.write(text_path_str, b"just some text").await?;
+        let result = validate_netcdf_magic(&storage, text_path_str).await;
+        assert!(matches!(result, Err(StorageError::NotNetCdf(_))));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_temp_dir() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        check_temp_dir(temp_dir.path())?;
+        // The probe file is removed again
+        assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 0);
+
+        let missing = temp_dir.path().join("missing");
+        assert!(matches!(
+            check_temp_dir(&missing),
+            Err(StorageError::PathNotFound(_))
+        ));
+
+        let file = temp_dir.path().join("file.txt");
+        std::fs::write(&file, b"not a directory")?;
+        assert!(matches!(
+            check_temp_dir(&file),
+            Err(StorageError::PathNotFound(_))
+        ));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_check_output_writable() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let output = temp_dir.path().join("out.parquet");
+        let output_str = output.to_str().unwrap();
+        check_local_output(output_str)?;
+
+        // Missing directories are created on write, so their existing ancestor is probed
+        let nested = temp_dir.path().join("a").join("b").join("out.parquet");
+        check_local_output(nested.to_str().unwrap())?;
+        assert!(!temp_dir.path().join("a").exists());
+
+        // A bare file name is written to the working directory
+        check_local_output("out.parquet")?;
+
+        let file = temp_dir.path().join("file.txt");
+        std::fs::write(&file, b"not a directory")?;
+        let below_file = file.join("out.parquet");
+        assert!(matches!(
+            check_local_output(below_file.to_str().unwrap()),
+            Err(StorageError::InvalidPath(_))
+        ));
+
+        // The probe object is written and deleted again
+        let storage = LocalStorage;
+        check_writable(&storage, output_str).await?;
+        assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 1);
+        assert!(!output.exists());
+
+        // Deleting a missing file is not an error
+        storage.delete(output_str).await?;
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_s3_storage_with_region() -> Result<(), Box<dyn std::error::Error>> {
+        let storage = S3Storage::with_region(Some("sa-east-1")).await?;
+        assert_eq!(
+            storage.client.config().region(),
+            Some(&Region::new("sa-east-1"))
+        );
+        Ok(())
+    }
+
     #[test]
     fn test_s3_path_parsing() {
         // Valid S3 paths
@@ -479,9 +1220,143 @@ mod tests {
         assert!(StorageFactory::is_local_path("relative/path/file.nc"));
         assert!(!StorageFactory::is_local_path("s3://my-bucket/file.nc"));
 
+        // File URIs are local paths
+        assert!(StorageFactory::is_file_uri("file:///local/path/file.nc"));
+        assert!(StorageFactory::is_local_path("file:///local/path/file.nc"));
+        assert!(!StorageFactory::is_file_uri("/local/path/file.nc"));
+        assert!(!StorageFactory::is_file_uri("file.nc"));
+
         // Test factory creation for local paths
         let local_storage = StorageFactory::from_path("/local/path/file.nc").await?;
         assert!(matches!(local_storage, Storage::Local(_)));
+        let uri_storage = StorageFactory::from_path("file:///local/path/file.nc").await?;
+        assert!(matches!(uri_storage, Storage::Local(_)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_uri_local_path() {
+        let local = |path| StorageFactory::local_path(path).unwrap().into_owned();
+
+        assert_eq!(local("file:///data/in.nc"), "/data/in.nc");
+        assert_eq!(local("file://localhost/data/in.nc"), "/data/in.nc");
+        assert_eq!(local("FILE://LocalHost/data/in.nc"), "/data/in.nc");
+        assert_eq!(local("file:/data/in.nc"), "/data/in.nc");
+        assert_eq!(
+            local("file:///data/my%20run/in%2B1.nc"),
+            "/data/my run/in+1.nc"
+        );
+
+        // Plain paths are returned unchanged
+        assert_eq!(local("/data/in.nc"), "/data/in.nc");
+        assert_eq!(local("data/in.nc"), "data/in.nc");
+        assert_eq!(local("s3://bucket/in.nc"), "s3://bucket/in.nc");
+
+        for invalid in [
+            "file://server/share/in.nc",
+            "file://",
+            "file:///data/in%2.nc",
+            "file:///data/in%zz.nc",
+        ] {
+            assert!(
+                matches!(
+                    StorageFactory::local_path(invalid),
+                    Err(StorageError::InvalidPath(_))
+                ),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_file_uri() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let file_path = temp_dir.path().join("nested").join("data.bin");
+        let uri = format!("file://{}", file_path.display());
+
+        let storage = StorageFactory::from_path(&uri).await?;
+        storage.write(&uri, b"uri data").await?;
+        assert!(storage.exists(&uri).await?);
+        assert_eq!(std::fs::read(&file_path)?, b"uri data");
+        assert_eq!(storage.read(&uri).await?, b"uri data");
+
+        Ok(())
+    }
+
+    /// Backend that records how many operations run at the same time
+    #[derive(Default)]
+    struct InstrumentedStorage {
+        active: std::sync::atomic::AtomicUsize,
+        max_active: std::sync::atomic::AtomicUsize,
+    }
+
+    impl InstrumentedStorage {
+        async fn operation(&self) {
+            use std::sync::atomic::Ordering;
+
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_active.fetch_max(active, Ordering::SeqCst);
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl StorageBackend for Arc<InstrumentedStorage> {
+        async fn read(&self, _path: &str) -> StorageResult<Vec<u8>> {
+            self.operation().await;
+            Ok(Vec::new())
+        }
+
+        async fn write(&self, _path: &str, _data: &[u8]) -> StorageResult<()> {
+            self.operation().await;
+            Ok(())
+        }
+
+        async fn exists(&self, _path: &str) -> StorageResult<bool> {
+            self.operation().await;
+            Ok(true)
+        }
+
+        async fn delete(&self, _path: &str) -> StorageResult<()> {
+            self.operation().await;
+            Ok(())
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_limited_storage_caps_concurrent_operations()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let backend = Arc::new(InstrumentedStorage::default());
+        let storage = Arc::new(LimitedStorage::new(
+            backend.clone(),
+            ConcurrencyLimiter::new(3),
+        ));
+
+        let mut tasks = tokio::task::JoinSet::new();
+        for i in 0..24 {
+            let storage = storage.clone();
+            tasks.spawn(async move {
+                let path = format!("s3://bucket/object-{}", i);
+                match i % 3 {
+                    0 => storage.read(&path).await.map(|_| ()),
+                    1 => storage.write(&path, b"data").await,
+                    _ => storage.exists(&path).await.map(|_| ()),
+                }
+            });
+        }
+        while let Some(result) = tasks.join_next().await {
+            result??;
+        }
+
+        let max_active = backend.max_active.load(std::sync::atomic::Ordering::SeqCst);
+        assert_eq!(max_active, 3);
+        assert_eq!(backend.active.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        // A limit of 0 still lets operations through one at a time
+        assert_eq!(ConcurrencyLimiter::new(0).limit(), 1);
 
         Ok(())
     }
@@ -505,6 +1380,32 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_s3_cache_entries() -> Result<(), Box<dyn std::error::Error>> {
+        let temp_dir = TempDir::new()?;
+        let cache_dir = temp_dir.path().join("cache");
+
+        let first = s3_cache_entry_name("bucket", "data/file.nc", "\"v1\"");
+        let second = s3_cache_entry_name("bucket", "data/file.nc", "\"v2\"");
+        let other = s3_cache_entry_name("bucket", "data/other.nc", "\"v1\"");
+        assert_ne!(first, second);
+        assert_ne!(first, other);
+        assert!(!first.contains('/'));
+
+        // The directory is created, and a new version replaces the previous one
+        store_s3_cache_entry(&cache_dir, "bucket", "data/file.nc", "\"v1\"", b"first").await?;
+        store_s3_cache_entry(&cache_dir, "bucket", "data/other.nc", "\"v1\"", b"other").await?;
+        store_s3_cache_entry(&cache_dir, "bucket", "data/file.nc", "\"v2\"", b"second").await?;
+
+        assert!(!cache_dir.join(&first).exists());
+        assert_eq!(std::fs::read(cache_dir.join(&second))?, b"second");
+        assert_eq!(std::fs::read(cache_dir.join(&other))?, b"other");
+        // No temporary file is left behind
+        assert_eq!(std::fs::read_dir(&cache_dir)?.count(), 2);
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -595,4 +1496,29 @@ mod integration_tests {
         );
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_s3_validate_netcdf_magic_noaa_public_dataset()
+    -> Result<(), Box<dyn std::error::Error>> {
+        let noaa_path = "s3://noaa-cdr-total-solar-irradiance-pds/data/daily/tsi_v02r01_daily_s18820101_e18821231_c20170717.nc";
+
+        let storage_result = StorageFactory::from_path(noaa_path).await;
+        if storage_result.is_err() {
+            println!("Skipping NOAA range test - unable to create storage from path");
+            return Ok(());
+        }
+        let storage = storage_result?;
+
+        // Only the first 8 bytes are requested
+        let range_result = storage.read_range(noaa_path, 0, 8).await;
+        if range_result.is_err() {
+            println!("Skipping NOAA range test - unable to read file (network or access issue)");
+            return Ok(());
+        }
+        assert_eq!(range_result?.len(), 8);
+
+        let format = validate_netcdf_magic(&storage, noaa_path).await?;
+        println!("NOAA range test passed - detected {}", format);
+        Ok(())
+    }
 }
@@ -0,0 +1,447 @@
+//! # Units Module
+//!
+//! A parser for a curated subset of UDUNITS-2 unit strings, as used in the
+//! `units` attribute of CF-compliant NetCDF files (e.g. `"kg m-2 s-1"`, `"W m-2"`,
+//! `"hPa"`, `"degC"`).
+//!
+//! ## Supported Syntax
+//! - **Products**: terms separated by spaces, `.`, `*` or `·` (`"kg m-2"`, `"N.m"`)
+//! - **Quotients**: `/` divides by the following term (`"m/s"`, `"m/s2"`)
+//! - **Exponents**: integer suffixes with optional `^` or `**` (`"m-2"`, `"s^-1"`, `"m**2"`)
+//! - **Numeric factors**: plain numbers (`"1e-3 kg"`)
+//! - **Prefixes**: SI prefix symbols on unit symbols (`"km"`, `"hPa"`, `"µm"`) and prefix
+//!   names on unit names (`"kilometer"`)
+//!
+//! Offset units (`degC`, `degF`) keep their offset only when used alone; inside a
+//! product or with an exponent they denote temperature differences, as in UDUNITS.
+//!
+//! For backwards compatibility the names `kelvin`, `celsius`, `fahrenheit` and the
+//! single letters `k`, `c` and `f` are accepted case-insensitively when they make up
+//! the whole unit string.
+
+use thiserror::Error;
+
+/// Errors that can occur while parsing or converting units
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum UnitError {
+    #[error("Unknown unit '{0}'")]
+    UnknownUnit(String),
+
+    #[error("Invalid unit syntax in '{0}'")]
+    InvalidSyntax(String),
+
+    #[error("Cannot convert '{from}' to '{to}': incompatible dimensions")]
+    IncompatibleDimensions { from: String, to: String },
+}
+
+/// Result type for unit operations
+pub type UnitResult<T> = Result<T, UnitError>;
+
+/// Number of base dimensions: length, mass, time, current, temperature, amount, luminosity
+const BASE_DIMENSIONS: usize = 7;
+
+/// A parsed unit, expressed relative to SI base units as `base = value * scale + offset`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Unit {
+    scale: f64,
+    offset: f64,
+    dimensions: [i32; BASE_DIMENSIONS],
+}
+
+impl Unit {
+    const fn new(scale: f64, dimensions: [i32; BASE_DIMENSIONS]) -> Self {
+        Self {
+            scale,
+            offset: 0.0,
+            dimensions,
+        }
+    }
+
+    const fn with_offset(scale: f64, offset: f64, dimensions: [i32; BASE_DIMENSIONS]) -> Self {
+        Self {
+            scale,
+            offset,
+            dimensions,
+        }
+    }
+
+    fn dimensionless(scale: f64) -> Self {
+        Self::new(scale, [0; BASE_DIMENSIONS])
+    }
+
+    /// Parses a UDUNITS-style unit string
+    pub fn parse(unit: &str) -> UnitResult<Self> {
+        let trimmed = unit.trim();
+        if let Some(legacy) = legacy_unit(trimmed) {
+            return Ok(legacy);
+        }
+        if trimmed.is_empty() {
+            return Err(UnitError::InvalidSyntax(unit.to_string()));
+        }
+
+        let terms = tokenize(trimmed).ok_or_else(|| UnitError::InvalidSyntax(unit.to_string()))?;
+
+        // A single offset unit with no exponent keeps its offset (degC -> K is affine)
+        if let [(Term::Symbol(name), 1, false)] = terms.as_slice() {
+            return lookup(name).ok_or_else(|| UnitError::UnknownUnit(name.clone()));
+        }
+
+        let mut result = Unit::dimensionless(1.0);
+        for (term, exponent, divide) in terms {
+            let factor = match term {
+                Term::Number(value) => Unit::dimensionless(value),
+                Term::Symbol(name) => lookup(&name).ok_or(UnitError::UnknownUnit(name))?,
+            };
+            let exponent = if divide { -exponent } else { exponent };
+            result = result.multiply(&factor.powi(exponent));
+        }
+        Ok(result)
+    }
+
+    /// Whether two units measure the same physical dimensions
+    pub fn is_compatible(&self, other: &Unit) -> bool {
+        self.dimensions == other.dimensions
+    }
+
+    /// Returns `(scale, offset)` such that `value_in_other = value * scale + offset`
+    pub fn conversion_to(&self, other: &Unit) -> Option<(f64, f64)> {
+        if !self.is_compatible(other) {
+            return None;
+        }
+        Some((
+            self.scale / other.scale,
+            (self.offset - other.offset) / other.scale,
+        ))
+    }
+
+    fn multiply(&self, other: &Unit) -> Unit {
+        let mut dimensions = self.dimensions;
+        for (dim, other_dim) in dimensions.iter_mut().zip(other.dimensions) {
+            *dim += other_dim;
+        }
+        Unit::new(self.scale * other.scale, dimensions)
+    }
+
+    fn powi(&self, exponent: i32) -> Unit {
+        Unit::new(
+            self.scale.powi(exponent),
+            self.dimensions.map(|dim| dim * exponent),
+        )
+    }
+}
+
+/// Computes the affine conversion between two unit strings.
+///
+/// Returns `(scale, offset)` such that `value_in_to = value_in_from * scale + offset`.
+///
+/// # Errors
+///
+/// Returns an error if either unit cannot be parsed or if the units have
+/// different physical dimensions (e.g. `"m"` and `"s"`).
+pub fn conversion(from: &str, to: &str) -> UnitResult<(f64, f64)> {
+    let from_unit = Unit::parse(from)?;
+    let to_unit = Unit::parse(to)?;
+    from_unit
+        .conversion_to(&to_unit)
+        .ok_or_else(|| UnitError::IncompatibleDimensions {
+            from: from.to_string(),
+            to: to.to_string(),
+        })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Term {
+    Number(f64),
+    Symbol(String),
+}
+
+/// Splits a unit string into `(term, exponent, divide)` triples
+fn tokenize(unit: &str) -> Option<Vec<(Term, i32, bool)>> {
+    let chars: Vec<char> = unit.chars().collect();
+    let mut terms = Vec::new();
+    let mut divide = false;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() || c == '*' || c == '·' || c == '.' {
+            i += 1;
+            continue;
+        }
+        if c == '/' {
+            if divide {
+                return None;
+            }
+            divide = true;
+            i += 1;
+            continue;
+        }
+
+        let term = if c.is_ascii_digit() {
+            let start = i;
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            if i < chars.len()
+                && (chars[i] == 'e' || chars[i] == 'E')
+                && chars
+                    .get(i + 1)
+                    .is_some_and(|&n| n.is_ascii_digit() || n == '-' || n == '+')
+            {
+                i += 2;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+            }
+            let text: String = chars[start..i].iter().collect();
+            Term::Number(text.parse().ok()?)
+        } else if is_symbol_char(c) {
+            let start = i;
+            while i < chars.len() && is_symbol_char(chars[i]) {
+                i += 1;
+            }
+            Term::Symbol(chars[start..i].iter().collect())
+        } else {
+            return None;
+        };
+
+        // Optional exponent: "2", "-2", "^2", "^-1", "**2"
+        let mut exponent = 1;
+        if matches!(term, Term::Symbol(_)) {
+            let mut j = i;
+            if chars.get(j) == Some(&'^') {
+                j += 1;
+            } else if chars.get(j) == Some(&'*') && chars.get(j + 1) == Some(&'*') {
+                j += 2;
+            }
+            let start = j;
+            if matches!(chars.get(j), Some('-') | Some('+')) {
+                j += 1;
+            }
+            let digits_start = j;
+            while j < chars.len() && chars[j].is_ascii_digit() {
+                j += 1;
+            }
+            if j > digits_start {
+                let text: String = chars[start..j].iter().collect();
+                exponent = text.parse().ok()?;
+                i = j;
+            } else if start != i {
+                return None;
+            }
+        }
+
+        terms.push((term, exponent, divide));
+        divide = false;
+    }
+
+    if divide || terms.is_empty() {
+        return None;
+    }
+    Some(terms)
+}
+
+fn is_symbol_char(c: char) -> bool {
+    c.is_alphabetic() || c == '_' || c == '%' || c == '°'
+}
+
+/// Units accepted by earlier versions, matched case-insensitively on the whole string
+fn legacy_unit(unit: &str) -> Option<Unit> {
+    match unit.to_lowercase().as_str() {
+        "kelvin" | "k" => lookup("K"),
+        "celsius" | "c" => lookup("degC"),
+        "fahrenheit" | "f" => lookup("degF"),
+        _ => None,
+    }
+}
+
+const LENGTH: [i32; BASE_DIMENSIONS] = [1, 0, 0, 0, 0, 0, 0];
+const MASS: [i32; BASE_DIMENSIONS] = [0, 1, 0, 0, 0, 0, 0];
+const TIME: [i32; BASE_DIMENSIONS] = [0, 0, 1, 0, 0, 0, 0];
+const CURRENT: [i32; BASE_DIMENSIONS] = [0, 0, 0, 1, 0, 0, 0];
+const TEMPERATURE: [i32; BASE_DIMENSIONS] = [0, 0, 0, 0, 1, 0, 0];
+const AMOUNT: [i32; BASE_DIMENSIONS] = [0, 0, 0, 0, 0, 1, 0];
+const LUMINOSITY: [i32; BASE_DIMENSIONS] = [0, 0, 0, 0, 0, 0, 1];
+const DIMENSIONLESS: [i32; BASE_DIMENSIONS] = [0; BASE_DIMENSIONS];
+const FREQUENCY: [i32; BASE_DIMENSIONS] = [0, 0, -1, 0, 0, 0, 0];
+const FORCE: [i32; BASE_DIMENSIONS] = [1, 1, -2, 0, 0, 0, 0];
+const PRESSURE: [i32; BASE_DIMENSIONS] = [-1, 1, -2, 0, 0, 0, 0];
+const ENERGY: [i32; BASE_DIMENSIONS] = [2, 1, -2, 0, 0, 0, 0];
+const POWER: [i32; BASE_DIMENSIONS] = [2, 1, -3, 0, 0, 0, 0];
+const VOLTAGE: [i32; BASE_DIMENSIONS] = [2, 1, -3, -1, 0, 0, 0];
+const VOLUME: [i32; BASE_DIMENSIONS] = [3, 0, 0, 0, 0, 0, 0];
+
+/// Unit symbols (case-sensitive) and whether they accept SI prefix symbols
+const SYMBOLS: &[(&str, Unit, bool)] = &[
+    ("m", Unit::new(1.0, LENGTH), true),
+    ("g", Unit::new(1e-3, MASS), true),
+    ("s", Unit::new(1.0, TIME), true),
+    ("A", Unit::new(1.0, CURRENT), true),
+    ("K", Unit::new(1.0, TEMPERATURE), true),
+    ("mol", Unit::new(1.0, AMOUNT), true),
+    ("cd", Unit::new(1.0, LUMINOSITY), true),
+    ("rad", Unit::new(1.0, DIMENSIONLESS), true),
+    ("sr", Unit::new(1.0, DIMENSIONLESS), true),
+    ("Hz", Unit::new(1.0, FREQUENCY), true),
+    ("N", Unit::new(1.0, FORCE), true),
+    ("Pa", Unit::new(1.0, PRESSURE), true),
+    ("J", Unit::new(1.0, ENERGY), true),
+    ("W", Unit::new(1.0, POWER), true),
+    ("V", Unit::new(1.0, VOLTAGE), true),
+    ("L", Unit::new(1e-3, VOLUME), true),
+    ("l", Unit::new(1e-3, VOLUME), true),
+    ("t", Unit::new(1e3, MASS), false),
+    ("bar", Unit::new(1e5, PRESSURE), true),
+    ("atm", Unit::new(101325.0, PRESSURE), false),
+    ("min", Unit::new(60.0, TIME), false),
+    ("h", Unit::new(3600.0, TIME), false),
+    ("hr", Unit::new(3600.0, TIME), false),
+    ("d", Unit::new(86400.0, TIME), false),
+    ("yr", Unit::new(3.15569259747e7, TIME), false),
+    ("degC", Unit::with_offset(1.0, 273.15, TEMPERATURE), false),
+    ("°C", Unit::with_offset(1.0, 273.15, TEMPERATURE), false),
+    (
+        "degF",
+        Unit::with_offset(5.0 / 9.0, 459.67 * 5.0 / 9.0, TEMPERATURE),
+        false,
+    ),
+    (
+        "°F",
+        Unit::with_offset(5.0 / 9.0, 459.67 * 5.0 / 9.0, TEMPERATURE),
+        false,
+    ),
+    ("%", Unit::new(1e-2, DIMENSIONLESS), false),
+    (
+        "°",
+        Unit::new(std::f64::consts::PI / 180.0, DIMENSIONLESS),
+        false,
+    ),
+];
+
+/// Unit names (matched in lowercase, singular or plural) and whether they accept prefix names
+const NAMES: &[(&str, Unit, bool)] = &[
+    ("meter", Unit::new(1.0, LENGTH), true),
+    ("metre", Unit::new(1.0, LENGTH), true),
+    ("gram", Unit::new(1e-3, MASS), true),
+    ("second", Unit::new(1.0, TIME), true),
+    ("ampere", Unit::new(1.0, CURRENT), true),
+    ("kelvin", Unit::new(1.0, TEMPERATURE), true),
+    ("mole", Unit::new(1.0, AMOUNT), true),
+    ("candela", Unit::new(1.0, LUMINOSITY), true),
+    ("radian", Unit::new(1.0, DIMENSIONLESS), true),
+    ("hertz", Unit::new(1.0, FREQUENCY), true),
+    ("newton", Unit::new(1.0, FORCE), true),
+    ("pascal", Unit::new(1.0, PRESSURE), true),
+    ("joule", Unit::new(1.0, ENERGY), true),
+    ("watt", Unit::new(1.0, POWER), true),
+    ("volt", Unit::new(1.0, VOLTAGE), true),
+    ("liter", Unit::new(1e-3, VOLUME), true),
+    ("litre", Unit::new(1e-3, VOLUME), true),
+    ("minute", Unit::new(60.0, TIME), false),
+    ("hour", Unit::new(3600.0, TIME), false),
+    ("day", Unit::new(86400.0, TIME), false),
+    ("year", Unit::new(3.15569259747e7, TIME), false),
+    (
+        "degree_celsius",
+        Unit::with_offset(1.0, 273.15, TEMPERATURE),
+        false,
+    ),
+    (
+        "degree_fahrenheit",
+        Unit::with_offset(5.0 / 9.0, 459.67 * 5.0 / 9.0, TEMPERATURE),
+        false,
+    ),
+    ("percent", Unit::new(1e-2, DIMENSIONLESS), false),
+    (
+        "degree",
+        Unit::new(std::f64::consts::PI / 180.0, DIMENSIONLESS),
+        false,
+    ),
+    (
+        "degree_north",
+        Unit::new(std::f64::consts::PI / 180.0, DIMENSIONLESS),
+        false,
+    ),
+    (
+        "degree_east",
+        Unit::new(std::f64::consts::PI / 180.0, DIMENSIONLESS),
+        false,
+    ),
+];
+
+const PREFIX_SYMBOLS: &[(&str, f64)] = &[
+    ("da", 1e1),
+    ("Y", 1e24),
+    ("Z", 1e21),
+    ("E", 1e18),
+    ("P", 1e15),
+    ("T", 1e12),
+    ("G", 1e9),
+    ("M", 1e6),
+    ("k", 1e3),
+    ("h", 1e2),
+    ("d", 1e-1),
+    ("c", 1e-2),
+    ("m", 1e-3),
+    ("u", 1e-6),
+    ("µ", 1e-6),
+    ("n", 1e-9),
+    ("p", 1e-12),
+    ("f", 1e-15),
+    ("a", 1e-18),
+];
+
+const PREFIX_NAMES: &[(&str, f64)] = &[
+    ("tera", 1e12),
+    ("giga", 1e9),
+    ("mega", 1e6),
+    ("kilo", 1e3),
+    ("hecto", 1e2),
+    ("deca", 1e1),
+    ("deka", 1e1),
+    ("deci", 1e-1),
+    ("centi", 1e-2),
+    ("milli", 1e-3),
+    ("micro", 1e-6),
+    ("nano", 1e-9),
+    ("pico", 1e-12),
+];
+
+/// Resolves a single unit symbol or name, with an optional prefix
+fn lookup(name: &str) -> Option<Unit> {
+    if let Some((_, unit, _)) = SYMBOLS.iter().find(|(symbol, _, _)| *symbol == name) {
+        return Some(unit.clone());
+    }
+
+    for (prefix, factor) in PREFIX_SYMBOLS {
+        if let Some(rest) = name.strip_prefix(prefix)
+            && let Some((_, unit, _)) = SYMBOLS
+                .iter()
+                .find(|(symbol, _, prefixable)| *prefixable && *symbol == rest)
+        {
+            return Some(Unit::new(unit.scale * factor, unit.dimensions));
+        }
+    }
+
+    let lower = name.to_lowercase();
+    let lookup_name = |candidate: &str, prefixed: bool| {
+        NAMES
+            .iter()
+            .find(|(unit_name, _, prefixable)| {
+                (!prefixed || *prefixable)
+                    && (*unit_name == candidate
+                        || candidate.strip_suffix('s') == Some(*unit_name)
+                        || candidate.replace("degrees_", "degree_") == *unit_name)
+            })
+            .map(|(_, unit, _)| unit.clone())
+    };
+
+    if let Some(unit) = lookup_name(&lower, false) {
+        return Some(unit);
+    }
+    PREFIX_NAMES.iter().find_map(|(prefix, factor)| {
+        let rest = lower.strip_prefix(prefix)?;
+        let unit = lookup_name(rest, true)?;
+        Some(Unit::new(unit.scale * factor, unit.dimensions))
+    })
+}